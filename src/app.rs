@@ -1,71 +1,252 @@
 use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
-use crate::gcp::GcpClientTrait;
-use crate::state::create_backup_flow::CreateBackupFlow;
+use crate::failure::FailureKind;
+use crate::gcp::{self, GcpClientTrait};
+use crate::keymap::KeyMap;
+use crate::operation_store::{OperationStore, TrackedOperation};
+use crate::selectable_list::SelectableList;
+use crate::session::SavedSession;
+use crate::state::create_backup_flow::{BatchBackupOperation, CreateBackupFlow};
+use crate::state::loading::{
+    BackgroundResult, Completed, LoadingState, RequestKind, RestoreStart, StatusCheck,
+};
 use crate::state::restore_flow::RestoreFlow;
+use crate::state::watch_flow::WatchFlow;
 use crate::types::{
-    AppState, Backup, CreateBackupConfig, InputMode, Operation, OperationMode, RestoreConfig,
-    RestoreRequest, RestoreBackupContext, SqlInstance,
+    AppState, Backup, CreateBackupConfig, CreateBackupRequest, DateRange, DisplayZone, InputMode,
+    InstanceStatus, Inventory, InventoryEntry, MaintenanceWindow, Operation, OperationMode,
+    OperationPhase, ProtectPattern, RestoreConfig, RestoreRequest, SqlInstance,
+    describe_with_labels, parse_label_input,
 };
 
+/// Delay before re-issuing a restore that failed because the target was busy.
+pub const RESTORE_RETRY_DELAY: Duration = Duration::from_secs(30);
+/// How many times a busy-target restore is retried before giving up.
+pub const MAX_RESTORE_RETRIES: u32 = 3;
+/// How long the type-to-confirm prompt for a protected restore target must stay open
+/// before the restore is accepted.
+pub const PROTECTED_CONFIRM_DELAY: Duration = Duration::from_secs(5);
+/// How long the startup prerequisite check may take unless `--timeout-prereq` says otherwise.
+pub const DEFAULT_PREREQ_TIMEOUT: Duration = Duration::from_secs(30);
+/// How many recently used projects are kept for the "Recent" suggestions.
+pub const MAX_REMEMBERED_PROJECTS: usize = 8;
+/// Start of the made-up operation ids of dry runs, which the API must never be asked about.
+pub const DRY_RUN_OPERATION_PREFIX: &str = "dry-run-";
+/// How often `--watch` asks Cloud SQL for the status of the operation it follows.
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+/// How many status checks in a row may fail before `--watch` gives up.
+pub const MAX_WATCH_FAILURES: u32 = 12;
+/// How long before an instance's maintenance window an operation on it is warned about.
+pub const MAINTENANCE_WARNING_LEAD: TimeDelta = TimeDelta::hours(1);
+/// Crude restore estimate when no earlier restore onto the target is known: a fixed
+/// overhead plus a minute for every so many GB of the target's disk.
+pub const RESTORE_OVERHEAD_MINUTES: u64 = 5;
+pub const RESTORE_GB_PER_MINUTE: u64 = 10;
+const REASON_REQUIRED: &str =
+    "A reason is required for restores (--require-reason). Press ESC to clear.";
+
 pub struct App {
     pub operation_mode: Option<OperationMode>,
     pub state: AppState,
     pub dry_run_mode: bool,
+    pub engine_filter: Option<String>,
+    /// Admin API base URL, used for the requests shown in dry-run previews.
+    pub api_endpoint: String,
+    /// Safe mode (`--no-restore`): only backups can be created, the restore path is hidden.
+    restore_disabled: bool,
+    /// Always stack the restore sections in one column (`--compact`), not only when narrow.
+    pub compact_layout: bool,
+    /// Render warnings without blinking or flashing colors.
+    pub reduce_motion: bool,
+    /// Moving past either end of a list continues from the other end (`--wrap-selection`).
+    pub wrap_selection: bool,
+    /// Reload the list being browsed this often while idle (`--auto-refresh`).
+    pub auto_refresh: Option<Duration>,
+    /// Show only the essential key hints in the footer; toggled with `v`.
+    pub compact_footer: bool,
+    /// Restore targets matching this need the target instance typed out to confirm.
+    pub protect_pattern: ProtectPattern,
+    /// Restores cannot be started without a reason (`--require-reason`).
+    pub require_reason: bool,
+    /// Record each finished restore as a labeled backup of the target (`--mark-restores`).
+    pub mark_restores: bool,
+    /// How long checking gcloud and its login may take at startup (`--timeout-prereq`).
+    pub prereq_timeout: Duration,
+    /// The zone backup and operation times are shown in (`--local-time`).
+    pub display_zone: DisplayZone,
+    /// Source project (`--project`) and instance (`--instance`) to start a restore from.
+    pub start_project: Option<String>,
+    pub start_instance: Option<String>,
+    /// Selections to replay at startup (`--resume`).
+    pub resumed_session: Option<SavedSession>,
+    pub keymap: KeyMap,
     pub input_mode: InputMode,
     pub input_buffer: String,
-    pub gcp_client: Box<dyn GcpClientTrait>,
+    pub gcp_client: Arc<dyn GcpClientTrait>,
     pub authenticated_user: Option<String>,
+    pub token_refreshed_at: Option<DateTime<Utc>>,
     pub remembered_projects: Vec<String>,
     pub remembered_instances: Vec<String>,
-    pub selected_operation_index: usize,
-    pub loading: bool,
+    /// The operations offered on the first screen.
+    pub operations: SelectableList<OperationMode>,
+    pub loading: LoadingState,
     pub show_help: bool,
+    /// Lines scrolled off the top of the help popup.
+    pub help_scroll: u16,
     pub manual_input_active: bool,
     pub manual_input_buffer: String,
     pub manual_input_type: String,
     pub restore_flow: RestoreFlow,
     pub create_backup_flow: CreateBackupFlow,
+    /// The operation followed with `--watch`; the TUI then shows only that operation.
+    pub watch_flow: Option<WatchFlow>,
     pub error: Option<String>,
+    /// Lines scrolled off the top of the error popup, for messages longer than it.
+    pub error_scroll: u16,
+    pub instance_load_error: Option<String>,
+    pub dry_run_preview: Option<String>,
+    /// The equivalent `gcloud` command for the current operation, shown on request.
+    pub gcloud_preview: Option<String>,
+    /// Shows the full status of the running restore or backup over the progress screen.
+    pub show_operation_details: bool,
+    /// Operations started by this or earlier sessions, persisted across restarts.
+    pub operation_store: OperationStore,
+    /// Set at startup when a previous session left operations unfinished.
+    pub resume_prompt: bool,
+    /// Ids of the operations started this session, oldest first.
+    pub session_operations: Vec<String>,
+    /// Numbers every GCP request made, so that results can be matched to the newest one.
+    request_sequence: u64,
+    /// The newest request of each kind whose result is still wanted. Results of older
+    /// requests, and of abandoned ones, are discarded instead of overwriting newer ones.
+    awaited_requests: HashMap<RequestKind, u64>,
+    /// Set by [`App::enable_background_requests`]: requests then run on their own task
+    /// and send their result here instead of being awaited by the key handler.
+    background_sender: Option<UnboundedSender<Completed>>,
+    background_receiver: Option<UnboundedReceiver<Completed>>,
+    /// Last polled status of the instance the current step acts on, as
+    /// `((project, instance), status)`.
+    pub instance_state: Option<((String, String), InstanceStatus)>,
+    /// Stops the periodic status polling; a manual refresh still checks once.
+    pub polling_paused: bool,
+    pub should_quit: bool,
 }
 
 impl App {
     pub fn new(gcp_client: Box<dyn GcpClientTrait>, dry_run_mode: bool) -> Self {
+        let gcp_client: Arc<dyn GcpClientTrait> = gcp_client.into();
         Self {
             operation_mode: None,
             state: AppState::SelectingOperation,
             dry_run_mode,
+            engine_filter: None,
+            api_endpoint: gcp::DEFAULT_API_ENDPOINT.to_string(),
+            restore_disabled: false,
+            compact_layout: false,
+            reduce_motion: false,
+            wrap_selection: false,
+            auto_refresh: None,
+            compact_footer: false,
+            protect_pattern: ProtectPattern::default(),
+            require_reason: false,
+            mark_restores: false,
+            prereq_timeout: DEFAULT_PREREQ_TIMEOUT,
+            display_zone: DisplayZone::Utc,
+            start_project: None,
+            start_instance: None,
+            resumed_session: None,
+            keymap: KeyMap::default(),
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
             gcp_client,
             authenticated_user: None,
+            token_refreshed_at: None,
             remembered_projects: Vec::new(),
             remembered_instances: Vec::new(),
-            selected_operation_index: 0,
-            loading: false,
+            operations: SelectableList::new(offered_operations(false)),
+            loading: LoadingState::default(),
             show_help: false,
+            help_scroll: 0,
             manual_input_active: false,
             manual_input_buffer: String::new(),
             manual_input_type: String::new(),
             restore_flow: RestoreFlow::new(),
             create_backup_flow: CreateBackupFlow::new(),
+            watch_flow: None,
             error: None,
+            error_scroll: 0,
+            instance_load_error: None,
+            dry_run_preview: None,
+            gcloud_preview: None,
+            show_operation_details: false,
+            operation_store: OperationStore::default(),
+            resume_prompt: false,
+            session_operations: Vec::new(),
+            request_sequence: 0,
+            awaited_requests: HashMap::new(),
+            background_sender: None,
+            background_receiver: None,
+            instance_state: None,
+            polling_paused: false,
+            should_quit: false,
         }
     }
 
+    /// The operations offered on the first screen, in display order.
+    pub fn available_operations(&self) -> Vec<OperationMode> {
+        self.operations.items().to_vec()
+    }
+
+    pub fn restore_disabled(&self) -> bool {
+        self.restore_disabled
+    }
+
+    /// Turns safe mode on or off, updating the operations offered.
+    pub fn set_restore_disabled(&mut self, disabled: bool) {
+        self.restore_disabled = disabled;
+        self.operations.set_items(offered_operations(disabled));
+    }
+
     pub async fn initialize(&mut self) -> Result<()> {
         self.state = AppState::CheckingPrerequisites;
-        self.loading = true;
         self.error = None;
 
-        match self.gcp_client.check_prerequisites().await {
+        let checked =
+            tokio::time::timeout(self.prereq_timeout, self.gcp_client.check_prerequisites()).await;
+        let checked = checked.unwrap_or_else(|_| {
+            Err(FailureKind::Timeout.error(format!(
+                "Prerequisite check timed out after {}s. Make sure `gcloud auth list` finishes \
+                 when run in a terminal, or allow more time with --timeout-prereq.",
+                self.prereq_timeout.as_secs_f32()
+            )))
+        });
+        match checked {
             Ok(user) => {
                 self.authenticated_user = Some(user);
-                self.loading = false;
+                if self.watch_flow.is_some() {
+                    self.state = AppState::WatchingOperation;
+                    self.poll_watched_operation().await;
+                    return Ok(());
+                }
                 self.state = AppState::SelectingOperation;
+                self.resume_prompt = !self.operation_store.in_flight.is_empty();
+                if !self.restore_disabled {
+                    if let Some(session) = self.resumed_session.take() {
+                        self.resume_restore(session).await?;
+                    } else if let Some(project) = self.start_project.clone() {
+                        let instance = self.start_instance.clone();
+                        self.start_restore_from(&project, instance.as_deref()).await?;
+                    }
+                }
             }
             Err(e) => {
-                self.loading = false;
                 self.state = AppState::Error(e.to_string());
             }
         }
@@ -73,251 +254,1551 @@ impl App {
         Ok(())
     }
 
+    /// Starts a restore from `project`, and from `instance` if given, as if the user had
+    /// picked them. If a step fails, its selection screen is left showing the error.
+    async fn start_restore_from(&mut self, project: &str, instance: Option<&str>) -> Result<()> {
+        self.operation_mode = Some(OperationMode::Restore);
+        self.remember_project(project);
+        self.restore_flow.source_project = Some(project.to_string());
+        self.state = AppState::SelectingSourceInstance;
+        self.load_instances_now(project).await?;
+        let Some(instance) = instance else {
+            return Ok(());
+        };
+        if !self.highlight_loaded_instance(project, instance) {
+            return Ok(());
+        }
+        self.select_current_item().await?;
+        if self.error.is_some() {
+            self.restore_flow.source_instance = None;
+            self.restore_flow.source_version = None;
+            self.state = AppState::SelectingSourceInstance;
+        }
+        Ok(())
+    }
+
+    /// Replays the selections of a saved session, stopping at the first one that no longer
+    /// exists. A complete session ends on the restore confirmation, which is left to the
+    /// user.
+    async fn resume_restore(&mut self, session: SavedSession) -> Result<()> {
+        let Some(project) = session.source_project else {
+            return Ok(());
+        };
+        self.start_restore_from(&project, session.source_instance.as_deref())
+            .await?;
+        let Some(backup_id) = session.backup_id.filter(|_| self.state == AppState::SelectingBackup)
+        else {
+            return Ok(());
+        };
+        if !self
+            .restore_flow
+            .backups
+            .select_where(|b| b.id == backup_id)
+        {
+            self.error = Some(format!(
+                "Backup {} no longer exists. Press ESC to clear.",
+                backup_id
+            ));
+            return Ok(());
+        }
+        self.select_current_item().await?;
+
+        let Some(target_project) = session.target_project else {
+            return Ok(());
+        };
+        self.remember_project(&target_project);
+        self.restore_flow.target_project = Some(target_project.clone());
+        self.state = AppState::SelectingTargetInstance;
+        self.load_instances_now(&target_project).await?;
+        if let Some(target_instance) = session.target_instance {
+            // The remembered target may have been deleted or renamed since the session
+            // was saved.
+            let gone = self.instance_load_error.is_none()
+                && !self
+                    .restore_flow
+                    .instances
+                    .items()
+                    .iter()
+                    .any(|i| i.name == target_instance);
+            if gone {
+                self.error = Some(format!(
+                    "Target instance {} from the saved session is no longer in {}. It may \
+                     have been deleted or renamed, so pick the restore target again. Press \
+                     ESC to clear.",
+                    target_instance, target_project
+                ));
+                return Ok(());
+            }
+            if self.highlight_loaded_instance(&target_project, &target_instance) {
+                self.select_current_item().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the restore instance selection to `instance`, or records an error if the
+    /// list did not load or does not contain it.
+    fn highlight_loaded_instance(&mut self, project: &str, instance: &str) -> bool {
+        if self.instance_load_error.is_some() {
+            return false;
+        }
+        let found = self
+            .restore_flow
+            .instances
+            .select_where(|i| i.name == instance);
+        if !found {
+            self.error = Some(format!(
+                "Instance {} was not found in {}. Press ESC to clear.",
+                instance, project
+            ));
+        }
+        found
+    }
+
+    /// Fetches a fresh access token ahead of expiry, e.g. before a long status poll.
+    pub async fn refresh_access_token(&mut self) -> Result<()> {
+        let client = self.gcp_client.clone();
+        self.dispatch(RequestKind::AccessToken, async move {
+            BackgroundResult::AccessTokenRefreshed(client.refresh_access_token().await)
+        })
+        .await;
+        Ok(())
+    }
+
+    fn apply_token_refresh(&mut self, result: Result<()>) {
+        match result {
+            Ok(()) => self.token_refreshed_at = Some(Utc::now()),
+            Err(e) => {
+                self.error = Some(format!(
+                    "Failed to refresh access token: {}. Press ESC to clear.",
+                    e
+                ));
+            }
+        }
+    }
+
     pub async fn load_projects(&mut self) -> Result<()> {
-        self.loading = false;
         self.start_manual_input("source_project");
         Ok(())
     }
 
+    /// Runs GCP requests made by key handlers and status ticks on background tasks from
+    /// now on, so a slow gcloud or API call does not freeze the TUI. Their results wait on
+    /// a channel until [`App::apply_background_results`] is called; the event loop does so
+    /// every tick. Until this is called, and in headless commands, requests are awaited.
+    pub fn enable_background_requests(&mut self) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.background_sender = Some(sender);
+        self.background_receiver = Some(receiver);
+    }
+
+    /// Applies the results that background tasks have sent since the last call, then
+    /// starts any request they made due. Returns how many results arrived, including
+    /// superseded ones that were dropped.
+    pub async fn apply_background_results(&mut self) -> usize {
+        let mut arrived = 0;
+        while let Some(completed) = self
+            .background_receiver
+            .as_mut()
+            .and_then(|receiver| receiver.try_recv().ok())
+        {
+            arrived += 1;
+            self.apply_completed(completed);
+        }
+        self.start_due_requests().await;
+        arrived
+    }
+
+    /// Applies a finished request, unless a newer one of the same kind was made since or
+    /// the user navigated away from it.
+    fn apply_completed(&mut self, completed: Completed) {
+        if self.awaited_requests.get(&completed.kind) != Some(&completed.sequence) {
+            return;
+        }
+        self.awaited_requests.remove(&completed.kind);
+        match completed.result {
+            BackgroundResult::Instances { mode, result } => self.apply_instances(mode, result),
+            BackgroundResult::Backups(result) => self.apply_backups(result),
+            BackgroundResult::ProjectBackups(result) => self.apply_project_backups(result),
+            BackgroundResult::InstanceState {
+                project,
+                instance,
+                result,
+            } => self.apply_instance_state(project, instance, result),
+            BackgroundResult::OperationStatus {
+                check,
+                operation_id,
+                result,
+            } => match check {
+                StatusCheck::Restore => self.apply_restore_status(&operation_id, result),
+                StatusCheck::Backup => self.apply_backup_status(&operation_id, result),
+                StatusCheck::BatchBackup => self.apply_batch_backup_status(&operation_id, result),
+                StatusCheck::Stored => self.apply_stored_status(&operation_id, result),
+                StatusCheck::Watch => self.apply_watch_status(&operation_id, result),
+            },
+            BackgroundResult::RestoreStarted { config, outcome } => {
+                self.apply_restore_started(&config, outcome)
+            }
+            BackgroundResult::BackupsStarted { batch, results } => {
+                self.apply_backups_started(batch, results)
+            }
+            BackgroundResult::RestoreMarked {
+                restore_operation_id,
+                result,
+            } => self.apply_restore_marked(&restore_operation_id, result),
+            BackgroundResult::AccessTokenRefreshed(result) => self.apply_token_refresh(result),
+            BackgroundResult::TypedBackup {
+                instance,
+                backup_id,
+                result,
+            } => self.apply_typed_backup(&instance, &backup_id, result),
+        }
+    }
+
+    /// Runs `request` on its own task once background requests are enabled; otherwise
+    /// awaits it and applies its result right away. It supersedes any earlier request of
+    /// the same `kind` that is still awaited.
+    async fn dispatch(
+        &mut self,
+        kind: RequestKind,
+        request: impl Future<Output = BackgroundResult> + Send + 'static,
+    ) {
+        self.request_sequence += 1;
+        let sequence = self.request_sequence;
+        self.awaited_requests.insert(kind.clone(), sequence);
+        let completed = async move {
+            Completed {
+                kind,
+                sequence,
+                result: request.await,
+            }
+        };
+        match self.background_sender.clone() {
+            Some(sender) => {
+                tokio::spawn(async move {
+                    let _ = sender.send(completed.await);
+                });
+            }
+            None => {
+                let completed = completed.await;
+                self.apply_completed(completed);
+            }
+        }
+    }
+
+    /// Starts the requests that applied results made due: the backup marking a restore
+    /// that has just finished.
+    async fn start_due_requests(&mut self) {
+        if std::mem::take(&mut self.restore_flow.marker_due) {
+            if let (Some(operation_id), Some(config)) = (
+                self.restore_flow.operation_id.clone(),
+                self.restore_flow.config.clone(),
+            ) {
+                self.mark_restore(operation_id, &config).await;
+            }
+        }
+    }
+
+    /// [`App::load_instances`], waiting for the result even with background requests, for
+    /// callers that go on to pick from the list.
+    async fn load_instances_now(&mut self, project_id: &str) -> Result<()> {
+        let sender = self.background_sender.take();
+        let result = self.load_instances(project_id).await;
+        self.background_sender = sender;
+        result
+    }
+
+    /// Abandons any list request still in flight, e.g. because the user pressed Esc.
+    /// Operations being started or checked are still applied when they finish.
+    pub fn invalidate_pending_requests(&mut self) {
+        for kind in [
+            RequestKind::Instances,
+            RequestKind::Backups,
+            RequestKind::InstanceState,
+            RequestKind::TypedBackup,
+        ] {
+            self.awaited_requests.remove(&kind);
+        }
+        self.loading.instances = false;
+        self.loading.backups = false;
+        self.loading.instance_state = false;
+    }
+
     pub async fn load_instances(&mut self, project_id: &str) -> Result<()> {
-        self.loading = true;
+        let Some(mode) = self.recover_operation_mode() else {
+            return Ok(());
+        };
+        self.loading.instances = true;
         self.error = None;
-        match self.gcp_client.list_sql_instances(project_id).await {
-            Ok(instances) => {
-                match self.operation_mode {
-                    Some(OperationMode::Restore) => {
-                        self.restore_flow.instances = instances;
-                        self.restore_flow.selected_instance_index = 0;
-                    }
-                    Some(OperationMode::CreateBackup) => {
-                        self.create_backup_flow.instances = instances;
-                        self.create_backup_flow.selected_instance_index = 0;
-                    }
-                    None => {}
+        self.instance_load_error = None;
+        let (client, project) = (self.gcp_client.clone(), project_id.to_string());
+        self.dispatch(RequestKind::Instances, async move {
+            let result = client.list_sql_instances(&project).await;
+            BackgroundResult::Instances { mode, result }
+        })
+        .await;
+        Ok(())
+    }
+
+    fn apply_instances(&mut self, mode: OperationMode, result: Result<Vec<SqlInstance>>) {
+        match result {
+            Ok(mut instances) => {
+                if let Some(engine) = &self.engine_filter {
+                    instances.retain(|instance| instance.matches_engine(engine));
                 }
-                self.loading = false;
+                let list = match mode {
+                    OperationMode::Restore => &mut self.restore_flow.instances,
+                    OperationMode::CreateBackup => &mut self.create_backup_flow.instances,
+                };
+                reload_instances(list, instances);
+                self.loading.instances = false;
             }
             Err(e) => {
-                self.loading = false;
+                self.loading.instances = false;
+                match mode {
+                    OperationMode::Restore => self.restore_flow.instances.clear(),
+                    OperationMode::CreateBackup => self.create_backup_flow.instances.clear(),
+                }
+                self.instance_load_error = Some(e.to_string());
                 self.error = Some(format!(
                     "Failed to load instances: {}. Press ESC to clear.",
                     e
                 ));
             }
         }
+    }
+
+    /// Reloads the list shown in the current state, if it has one.
+    pub async fn refresh_list(&mut self) -> Result<()> {
+        match self.state {
+            AppState::SelectingSourceInstance => {
+                if let Some(project) = &self.restore_flow.source_project.clone() {
+                    self.load_instances(project).await?;
+                }
+            }
+            AppState::SelectingTargetInstance => {
+                if let Some(project) = &self.restore_flow.target_project.clone() {
+                    self.load_instances(project).await?;
+                }
+            }
+            AppState::SelectingInstanceForBackup => {
+                if let Some(project) = &self.create_backup_flow.project.clone() {
+                    self.load_instances(project).await?;
+                }
+            }
+            AppState::SelectingBackup => {
+                if let (Some(project), Some(instance)) = (
+                    self.restore_flow.source_project.clone(),
+                    self.restore_flow.source_instance.clone(),
+                ) {
+                    self.load_backups(&project, &instance).await?;
+                }
+            }
+            AppState::BrowsingProjectBackups => {
+                if let Some(project) = &self.restore_flow.source_project.clone() {
+                    self.load_all_backups(project).await?;
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
 
+    /// Whether the list on screen can be reloaded by `--auto-refresh` without getting in
+    /// the user's way: a list is shown with nothing typed, loading, popped up or
+    /// awaiting confirmation on top of it.
+    pub fn idle_in_list(&self) -> bool {
+        matches!(
+            self.state,
+            AppState::SelectingSourceInstance
+                | AppState::SelectingTargetInstance
+                | AppState::SelectingInstanceForBackup
+                | AppState::SelectingBackup
+                | AppState::BrowsingProjectBackups
+        ) && !self.manual_input_active
+            && self.error.is_none()
+            && !self.show_help
+            && !self.restore_flow.show_comparison
+            && !self.restore_flow.show_retention
+            && !self.loading.instances
+            && !self.loading.backups
+    }
+
     pub async fn load_backups(&mut self, project_id: &str, instance_id: &str) -> Result<()> {
-        self.loading = true;
+        self.loading.backups = true;
         self.error = None;
-        match self.gcp_client.list_backups(project_id, instance_id).await {
+        let client = self.gcp_client.clone();
+        let (project, instance) = (project_id.to_string(), instance_id.to_string());
+        self.dispatch(RequestKind::Backups, async move {
+            BackgroundResult::Backups(client.list_backups(&project, &instance).await)
+        })
+        .await;
+        Ok(())
+    }
+
+    fn apply_backups(&mut self, result: Result<Vec<Backup>>) {
+        match result {
             Ok(backups) => {
-                self.restore_flow.backups = backups;
-                self.restore_flow.selected_backup_index = 0;
-                self.loading = false;
+                let flow = &mut self.restore_flow;
+                let selected = flow.backups.selected_item().map(|backup| backup.id.clone());
+                flow.backups.set_items(backups);
+                if let Some(id) = selected {
+                    flow.backups.select_where(|b| b.id == id);
+                }
+                let backups = flow.backups.items();
+                flow.compare_marks
+                    .retain(|mark| backups.iter().any(|backup| backup.id == *mark));
+                self.loading.backups = false;
             }
             Err(e) => {
-                self.loading = false;
+                self.loading.backups = false;
                 self.error = Some(format!(
                     "Failed to load backups: {}. Press ESC to clear.",
                     e
                 ));
             }
         }
+    }
+
+    /// Selects the typed backup `backup_id` if `instance` has it. Dropped if the user has
+    /// since closed the input or moved to another source instance.
+    fn apply_typed_backup(
+        &mut self,
+        instance: &str,
+        backup_id: &str,
+        result: Result<Vec<Backup>>,
+    ) {
+        self.loading.backups = false;
+        if !self.manual_input_active
+            || self.manual_input_type != "backup"
+            || self.restore_flow.source_instance.as_deref() != Some(instance)
+        {
+            return;
+        }
+        match result {
+            Ok(backups) => match backups.into_iter().find(|b| b.id == backup_id) {
+                Some(backup) => {
+                    self.manual_input_active = false;
+                    self.input_mode = InputMode::Normal;
+                    self.select_backup(backup);
+                }
+                None => {
+                    self.error = Some(format!(
+                        "Backup {} was not found on {}. Press ESC to clear.",
+                        backup_id, instance
+                    ));
+                }
+            },
+            Err(e) => {
+                self.error = Some(format!(
+                    "Could not verify backup {}: {}. Press ESC to clear.",
+                    backup_id, e
+                ));
+            }
+        }
+    }
+
+    pub async fn load_all_backups(&mut self, project_id: &str) -> Result<()> {
+        self.loading.backups = true;
+        self.error = None;
+        let (client, project) = (self.gcp_client.clone(), project_id.to_string());
+        self.dispatch(RequestKind::Backups, async move {
+            BackgroundResult::ProjectBackups(client.list_all_backups(&project).await)
+        })
+        .await;
         Ok(())
     }
 
+    fn apply_project_backups(&mut self, result: Result<Vec<(String, Backup)>>) {
+        match result {
+            Ok(backups) => {
+                let flow = &mut self.restore_flow;
+                let selected = flow
+                    .project_backups
+                    .selected_item()
+                    .map(|(instance, backup)| (instance.clone(), backup.id.clone()));
+                flow.project_backups.set_items(backups);
+                flow.sort_project_backups();
+                if let Some((instance, id)) = selected {
+                    flow.project_backups
+                        .select_where(|(i, backup)| *i == instance && backup.id == id);
+                }
+                self.loading.backups = false;
+            }
+            Err(e) => {
+                self.loading.backups = false;
+                self.error = Some(format!(
+                    "Failed to load project backups: {}. Press ESC to clear.",
+                    e
+                ));
+            }
+        }
+    }
+
+    pub fn toggle_project_backup_sort(&mut self) {
+        let flow = &mut self.restore_flow;
+        flow.project_backup_sort = flow.project_backup_sort.next();
+        flow.sort_project_backups();
+        flow.project_backups.select(0);
+    }
+
+    /// Fills in a restore of the newest backup of the source instance onto the target and
+    /// moves to [`AppState::ConfirmRestore`]. Returns the backup that was picked.
+    ///
+    /// The target is looked up like on the target instance step: a read replica is
+    /// refused, and [`RestoreFlow::newer_source_version`] tells whether the backup comes
+    /// from a newer engine version.
+    pub async fn prepare_latest_backup_restore(
+        &mut self,
+        source_project: &str,
+        source_instance: &str,
+        target_project: &str,
+        target_instance: &str,
+    ) -> Result<Backup> {
+        if self.restore_disabled {
+            return Err(FailureKind::Usage.error("Restore is disabled (--no-restore)"));
+        }
+        self.operation_mode = Some(OperationMode::Restore);
+        self.restore_flow = RestoreFlow::new();
+        self.restore_flow.source_project = Some(source_project.to_string());
+        self.restore_flow.source_instance = Some(source_instance.to_string());
+        let backups = self
+            .gcp_client
+            .list_backups(source_project, source_instance)
+            .await?;
+        self.restore_flow.backups.set_items(backups);
+        let backup = self.restore_flow.select_latest_backup().ok_or_else(|| {
+            FailureKind::NotFound.error(format!(
+                "No usable backups found for {}/{}",
+                source_project, source_instance
+            ))
+        })?;
+
+        let source_instances = self.gcp_client.list_sql_instances(source_project).await?;
+        self.restore_flow.source_version = source_instances
+            .iter()
+            .find(|instance| instance.name == source_instance)
+            .map(|instance| instance.database_version.clone());
+        let target_instances = if target_project == source_project {
+            source_instances
+        } else {
+            self.gcp_client.list_sql_instances(target_project).await?
+        };
+        self.restore_flow.instances.set_items(target_instances);
+        let target = self
+            .restore_flow
+            .instances
+            .items()
+            .iter()
+            .find(|instance| instance.name == target_instance)
+            .ok_or_else(|| {
+                FailureKind::NotFound.error(format!(
+                    "Target instance {}/{} not found",
+                    target_project, target_instance
+                ))
+            })?;
+        if target.is_replica() {
+            return Err(FailureKind::Usage.error(format!(
+                "{} is a read replica and cannot be a restore target. Pick a primary instance.",
+                target_instance
+            )));
+        }
+
+        self.restore_flow.target_project = Some(target_project.to_string());
+        self.restore_flow.target_instance = Some(target_instance.to_string());
+        self.create_restore_config();
+        self.state = AppState::ConfirmRestore;
+        Ok(backup)
+    }
+
+    /// Lists the instances of `project` and describes each one. An instance that cannot
+    /// be described is kept with its error, so one failure does not lose the rest.
+    pub async fn collect_inventory(&self, project: &str) -> Result<Inventory> {
+        let mut instances = Vec::new();
+        for instance in self.gcp_client.list_sql_instances(project).await? {
+            let described = self
+                .gcp_client
+                .describe_instance(project, &instance.name)
+                .await
+                .map_err(|e| e.to_string());
+            instances.push(InventoryEntry::new(instance, described));
+        }
+        Ok(Inventory {
+            project: project.to_string(),
+            exported_at: Utc::now(),
+            instances,
+        })
+    }
+
+    /// Polls operation `operation_id` of `project` every `interval` until it is done or
+    /// has failed, calling `on_change` each time its status line changes. Returns the
+    /// operation as last seen.
+    ///
+    /// A failed check is passed to `on_retry` and retried on the next poll, like the TUI
+    /// does, so a network blip does not end a long watch. Only an operation that does not
+    /// exist, or [`MAX_WATCH_FAILURES`] failed checks in a row, end it with an error.
+    pub async fn watch_operation(
+        &self,
+        project: &str,
+        operation_id: &str,
+        interval: Duration,
+        mut on_change: impl FnMut(&Operation),
+        mut on_retry: impl FnMut(&anyhow::Error),
+    ) -> Result<Operation> {
+        let mut last_line = None;
+        let mut failures = 0;
+        loop {
+            let operation = match self
+                .gcp_client
+                .get_operation_status(project, operation_id)
+                .await
+            {
+                Ok(operation) => operation,
+                Err(e) => {
+                    failures += 1;
+                    if failures >= MAX_WATCH_FAILURES
+                        || FailureKind::of(&e) == Some(FailureKind::NotFound)
+                    {
+                        return Err(e);
+                    }
+                    on_retry(&e);
+                    tokio::time::sleep(interval).await;
+                    continue;
+                }
+            };
+            failures = 0;
+            let line = operation.watch_line();
+            if last_line.as_ref() != Some(&line) {
+                on_change(&operation);
+                last_line = Some(line);
+            }
+            if matches!(operation.phase(), OperationPhase::Done | OperationPhase::Failed) {
+                return Ok(operation);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// The `(project, instance)` whose readiness matters at this step: the restore target
+    /// once chosen, or the instance being backed up.
+    pub fn relevant_instance(&self) -> Option<(String, String)> {
+        let restore_target = || {
+            self.restore_flow
+                .target_project
+                .clone()
+                .zip(self.restore_flow.target_instance.clone())
+        };
+        match self.state {
+            AppState::ConfirmRestore | AppState::PerformingRestore => restore_target(),
+            // A started restore returns to the target step while it runs.
+            _ if self.followed_operation() == Some(OperationMode::Restore) => restore_target(),
+            AppState::EnteringBackupName
+            | AppState::ConfirmCreateBackup
+            | AppState::PerformingCreateBackup => self
+                .create_backup_flow
+                .project
+                .clone()
+                .zip(self.create_backup_flow.instance.clone()),
+            _ => None,
+        }
+    }
+
+    /// Refreshes [`App::instance_state`] for the [relevant instance](App::relevant_instance).
+    /// Failures only clear the state, since this is informational. With background loads
+    /// the instance is described on its own task, like the lists.
+    pub async fn poll_instance_state(&mut self) {
+        let Some((project, instance)) = self.relevant_instance() else {
+            self.instance_state = None;
+            self.loading.instance_state = false;
+            return;
+        };
+        self.loading.instance_state = true;
+        let client = self.gcp_client.clone();
+        self.dispatch(RequestKind::InstanceState, async move {
+            let result = client.describe_instance(&project, &instance).await;
+            BackgroundResult::InstanceState {
+                project,
+                instance,
+                result,
+            }
+        })
+        .await;
+    }
+
+    fn apply_instance_state(
+        &mut self,
+        project: String,
+        instance: String,
+        result: Result<InstanceStatus>,
+    ) {
+        self.loading.instance_state = false;
+        let described = (project, instance);
+        if self.relevant_instance().as_ref() != Some(&described) {
+            return;
+        }
+        self.instance_state = result
+            .ok()
+            .filter(|status| !status.state.is_empty())
+            .map(|status| (described, status));
+    }
+
+    /// The polled status of `instance` in `project`, if it is the one last described.
+    fn polled_state(&self, project: &str, instance: &str) -> Option<&InstanceStatus> {
+        self.instance_state
+            .as_ref()
+            .filter(|((p, i), _)| p == project && i == instance)
+            .map(|(_, status)| status)
+    }
+
+    /// Whether the restore target is still being described for the first time, so it is
+    /// not yet known whether it is in use.
+    fn restore_target_unchecked(&self) -> bool {
+        let Some(config) = &self.restore_flow.config else {
+            return false;
+        };
+        self.loading.instance_state
+            && self
+                .polled_state(&config.target_project, &config.target_instance)
+                .is_none()
+    }
+
+    /// The polled state of `instance` in `project` if it is known and not `RUNNABLE`.
+    pub fn instance_not_ready(&self, project: &str, instance: &str) -> Option<&str> {
+        self.polled_state(project, instance)
+            .filter(|status| status.state != "RUNNABLE")
+            .map(|status| status.state.as_str())
+    }
+
+    /// A rough, advisory duration for `config`'s restore: the average of the finished
+    /// restores onto the same instance in the history, or else an estimate from the
+    /// target's disk size. `None` when neither is known.
+    pub fn restore_estimate(&self, config: &RestoreConfig) -> Option<String> {
+        let earlier: Vec<i64> = self
+            .operation_store
+            .history
+            .iter()
+            .filter(|op| {
+                op.mode == OperationMode::Restore
+                    && op.status == "DONE"
+                    && op.error.is_none()
+                    && op.project == config.target_project
+                    && op.instance.as_deref() == Some(config.target_instance.as_str())
+            })
+            .filter_map(|op| op.finished_at.map(|end| (end - op.started_at).num_seconds()))
+            .filter(|secs| *secs >= 0)
+            .collect();
+        if !earlier.is_empty() {
+            let average = earlier.iter().sum::<i64>() / earlier.len() as i64;
+            let minutes = (average as u64).div_ceil(60).max(1);
+            return Some(format!(
+                "~{} minutes (estimate from {} earlier restore{} onto this instance)",
+                minutes,
+                earlier.len(),
+                if earlier.len() == 1 { "" } else { "s" }
+            ));
+        }
+        let status = self.polled_state(&config.target_project, &config.target_instance)?;
+        let disk_size_gb = status.disk_size_gb?;
+        let minutes = RESTORE_OVERHEAD_MINUTES + disk_size_gb / RESTORE_GB_PER_MINUTE;
+        Some(format!(
+            "~{} minutes (estimate for the {} GB disk)",
+            minutes, disk_size_gb
+        ))
+    }
+
+    /// The maintenance window of `instance` in `project` if `now` is in it or about to be,
+    /// in which case Cloud SQL may hold an operation until maintenance is over.
+    pub fn maintenance_window_near(
+        &self,
+        project: &str,
+        instance: &str,
+        now: DateTime<Utc>,
+    ) -> Option<MaintenanceWindow> {
+        self.polled_state(project, instance)?
+            .maintenance_window
+            .filter(|window| window.is_near(now, MAINTENANCE_WARNING_LEAD))
+    }
+
+    /// Whether the configured restore target was last seen running with an activation
+    /// policy that keeps it up, i.e. it is probably serving traffic.
+    pub fn restore_target_in_use(&self) -> bool {
+        let Some(config) = &self.restore_flow.config else {
+            return false;
+        };
+        self.polled_state(&config.target_project, &config.target_instance)
+            .is_some_and(InstanceStatus::likely_in_use)
+    }
+
+    /// The operation this session started that has not reached a terminal status, if any.
+    /// Only one destructive operation is allowed at a time.
+    pub fn running_operation(&self) -> Option<String> {
+        let unfinished = |status: &Option<String>| {
+            !matches!(status.as_deref(), Some("DONE" | "FAILED" | "ERROR"))
+        };
+        if let Some(id) = &self.restore_flow.operation_id {
+            if unfinished(&self.restore_flow.status) {
+                return Some(format!("A restore (operation {})", id));
+            }
+        }
+        let backup = &self.create_backup_flow;
+        if let Some(id) = &backup.operation_id {
+            if unfinished(&backup.status) {
+                return Some(format!("A backup (operation {})", id));
+            }
+        }
+        if let Some(op) = backup
+            .batch_operations
+            .iter()
+            .find(|op| op.operation_id.is_some() && !op.is_finished())
+        {
+            return Some(format!("A backup of {}", op.instance));
+        }
+        self.detached_operations().first().map(|op| {
+            let kind = match op.mode {
+                OperationMode::Restore => "restore",
+                OperationMode::CreateBackup => "backup",
+            };
+            format!("A {} (operation {})", kind, op.operation_id)
+        })
+    }
+
+    /// Unfinished operations started this session that no flow follows any more, because
+    /// the flow was reset while they ran. They are polled from the store until they end.
+    fn detached_operations(&self) -> Vec<TrackedOperation> {
+        let backup = &self.create_backup_flow;
+        let followed = |id: &str| {
+            self.restore_flow.operation_id.as_deref() == Some(id)
+                || backup.operation_id.as_deref() == Some(id)
+                || backup
+                    .batch_operations
+                    .iter()
+                    .any(|op| op.operation_id.as_deref() == Some(id))
+        };
+        self.operation_store
+            .in_flight
+            .iter()
+            .filter(|op| self.session_operations.contains(&op.operation_id))
+            .filter(|op| !followed(&op.operation_id))
+            .cloned()
+            .collect()
+    }
+
+    fn reject_if_operation_running(&mut self, starting: &str) -> bool {
+        let Some(running) = self.running_operation() else {
+            return false;
+        };
+        self.error = Some(format!(
+            "{} is still running. Wait for it to finish before starting a {}. \
+             Press ESC to clear.",
+            running, starting
+        ));
+        true
+    }
+
     pub async fn perform_restore(&mut self) -> Result<()> {
+        if self.restore_disabled {
+            self.error =
+                Some("Restore is disabled (--no-restore). Press ESC to clear.".to_string());
+            return Ok(());
+        }
+        if self.reject_if_operation_running("restore") {
+            return Ok(());
+        }
+        if self.reason_missing() {
+            self.error = Some(REASON_REQUIRED.to_string());
+            return Ok(());
+        }
         if let Some(config) = self.restore_flow.config.clone() {
-            self.loading = true;
+            self.loading.operation_status = true;
             self.state = AppState::PerformingRestore;
 
-            let restore_request = RestoreRequest {
-                restore_backup_context: RestoreBackupContext {
-                    backup_run_id: config.backup_id.clone(),
-                    project: config.source_project.clone(),
-                    instance_id: config.source_instance.clone(),
-                },
-            };
+            let restore_request = RestoreRequest::from(&config);
 
             if self.dry_run_mode {
-                let mock_operation_id =
-                    format!("dry-run-operation-{}", chrono::Utc::now().timestamp());
+                self.dry_run_preview = Some(request_preview(
+                    &gcp::restore_backup_url(
+                        &self.api_endpoint,
+                        &config.target_project,
+                        &config.target_instance,
+                    ),
+                    &restore_request,
+                ));
+                let mock_operation_id = format!(
+                    "{}operation-{}",
+                    DRY_RUN_OPERATION_PREFIX,
+                    chrono::Utc::now().timestamp()
+                );
                 self.restore_flow.operation_id = Some(mock_operation_id);
                 self.restore_flow.status = Some("DONE".to_string());
-                self.loading = false;
+                self.loading.operation_status = false;
                 self.state = AppState::SelectingTargetInstance;
             } else {
-                match self
-                    .gcp_client
-                    .restore_backup(
-                        &restore_request,
-                        &config.target_project,
-                        &config.target_instance,
-                    )
-                    .await
-                {
-                    Ok(operation_id) => {
-                        self.restore_flow.operation_id = Some(operation_id.clone());
-                        self.restore_flow.status = Some("RUNNING".to_string());
-                        self.loading = false;
-                        self.state = AppState::SelectingTargetInstance;
-                    }
-                    Err(e) => {
-                        self.loading = false;
-                        self.error = Some(format!("Restore failed: {}. Press ESC to clear.", e));
-                        self.state = AppState::ConfirmRestore;
+                let client = self.gcp_client.clone();
+                self.dispatch(RequestKind::StartOperation, async move {
+                    let outcome = match missing_backup_error(client.as_ref(), &config).await {
+                        Some(message) => RestoreStart::BackupMissing(message),
+                        None => RestoreStart::Requested(
+                            client
+                                .restore_backup(
+                                    &restore_request,
+                                    &config.target_project,
+                                    &config.target_instance,
+                                )
+                                .await,
+                        ),
+                    };
+                    BackgroundResult::RestoreStarted { config, outcome }
+                })
+                .await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a started restore. The flow only takes it up if it is still waiting for
+    /// it, and only leaves the progress screen if the user has not already.
+    fn apply_restore_started(&mut self, config: &RestoreConfig, outcome: RestoreStart) {
+        if let RestoreStart::Requested(Ok(operation_id)) = &outcome {
+            let mut tracked = TrackedOperation::new(
+                OperationMode::Restore,
+                &config.target_project,
+                operation_id,
+            );
+            tracked.instance = Some(config.target_instance.clone());
+            tracked.source = Some(config.source());
+            tracked.backup_id = Some(config.backup_id.clone());
+            tracked.reason = config.reason.clone();
+            tracked.databases = config.intended_databases.clone();
+            self.track_operation(tracked);
+        }
+        if !self.loading.operation_status {
+            return;
+        }
+        self.loading.operation_status = false;
+        let performing = self.state == AppState::PerformingRestore;
+        match outcome {
+            RestoreStart::Requested(Ok(operation_id)) => {
+                self.restore_flow.operation_id = Some(operation_id);
+                self.restore_flow.last_operation = None;
+                self.restore_flow.status = Some("RUNNING".to_string());
+                self.restore_flow.retry_at = None;
+                self.restore_flow.retry_attempts = 0;
+                if performing {
+                    self.state = AppState::SelectingTargetInstance;
+                }
+            }
+            RestoreStart::Requested(Err(e))
+                if performing
+                    && gcp::is_instance_busy(&e)
+                    && self.restore_flow.retry_attempts < MAX_RESTORE_RETRIES =>
+            {
+                self.restore_flow.retry_attempts += 1;
+                self.restore_flow.retry_at = Some(Instant::now() + RESTORE_RETRY_DELAY);
+            }
+            RestoreStart::Requested(Err(e)) => {
+                self.restore_flow.retry_at = None;
+                self.error = Some(format!("Restore failed: {}. Press ESC to clear.", e));
+                if performing {
+                    self.state = AppState::ConfirmRestore;
+                }
+            }
+            RestoreStart::BackupMissing(message) => {
+                self.restore_flow.retry_at = None;
+                self.error = Some(message);
+                if performing {
+                    self.state = AppState::ConfirmRestore;
+                }
+            }
+        }
+    }
+
+    /// Re-issues a restore that was deferred because the target instance was busy.
+    pub async fn retry_restore_if_due(&mut self) -> Result<()> {
+        if let Some(retry_at) = self.restore_flow.retry_at {
+            if Instant::now() >= retry_at {
+                self.restore_flow.retry_at = None;
+                self.perform_restore().await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn perform_create_backup(&mut self) -> Result<()> {
+        if self.reject_if_operation_running("backup") {
+            return Ok(());
+        }
+        if !self.create_backup_flow.batch_configs.is_empty() {
+            return self.perform_batch_create_backup().await;
+        }
+        if let Some(config) = &self.create_backup_flow.config {
+            self.loading.operation_status = true;
+            self.state = AppState::PerformingCreateBackup;
+
+            if self.dry_run_mode {
+                self.dry_run_preview = Some(request_preview(
+                    &gcp::backup_runs_url(&self.api_endpoint, &config.project, &config.instance),
+                    &CreateBackupRequest::from(config),
+                ));
+                let mock_operation_id = format!(
+                    "{}backup-op-{}",
+                    DRY_RUN_OPERATION_PREFIX,
+                    chrono::Utc::now().timestamp()
+                );
+                self.create_backup_flow.operation_id = Some(mock_operation_id);
+                self.create_backup_flow.status = Some("DONE".to_string());
+                self.loading.operation_status = false;
+                self.state = AppState::PerformingCreateBackup;
+            } else {
+                let (client, config) = (self.gcp_client.clone(), config.clone());
+                self.dispatch(RequestKind::StartOperation, async move {
+                    let result = client.create_backup(&config).await;
+                    BackgroundResult::BackupsStarted {
+                        batch: false,
+                        results: vec![(config, result)],
                     }
+                })
+                .await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts one backup per configured instance. Failures are recorded per instance so
+    /// that one bad instance does not stop the rest of the batch.
+    pub async fn perform_batch_create_backup(&mut self) -> Result<()> {
+        if self.reject_if_operation_running("backup") {
+            return Ok(());
+        }
+        self.loading.operation_status = true;
+        self.state = AppState::PerformingCreateBackup;
+
+        let configs = self.create_backup_flow.batch_configs.clone();
+        if !self.dry_run_mode {
+            let client = self.gcp_client.clone();
+            self.dispatch(RequestKind::StartOperation, async move {
+                let mut results = Vec::with_capacity(configs.len());
+                for config in configs {
+                    let result = client.create_backup(&config).await;
+                    results.push((config, result));
                 }
+                BackgroundResult::BackupsStarted {
+                    batch: true,
+                    results,
+                }
+            })
+            .await;
+            return Ok(());
+        }
+
+        let mut operations = Vec::with_capacity(configs.len());
+        let mut previews = Vec::new();
+        for config in &configs {
+            previews.push(request_preview(
+                &gcp::backup_runs_url(&self.api_endpoint, &config.project, &config.instance),
+                &CreateBackupRequest::from(config),
+            ));
+            operations.push(BatchBackupOperation {
+                instance: config.instance.clone(),
+                operation_id: Some(format!(
+                    "{}backup-op-{}-{}",
+                    DRY_RUN_OPERATION_PREFIX,
+                    config.instance,
+                    chrono::Utc::now().timestamp()
+                )),
+                status: "DONE".to_string(),
+                error: None,
+            });
+        }
+        self.dry_run_preview = Some(previews.join("\n\n"));
+        self.create_backup_flow.batch_operations = operations;
+        self.loading.operation_status = false;
+        Ok(())
+    }
+
+    /// Records started backups, and hands them to the flow if it is still waiting for
+    /// them. A failed single backup returns to its confirmation.
+    fn apply_backups_started(
+        &mut self,
+        batch: bool,
+        results: Vec<(CreateBackupConfig, Result<String>)>,
+    ) {
+        for (config, result) in &results {
+            if let Ok(operation_id) = result {
+                let mut tracked = TrackedOperation::new(
+                    OperationMode::CreateBackup,
+                    &config.project,
+                    operation_id,
+                );
+                tracked.instance = Some(config.instance.clone());
+                tracked.location = config.location.clone();
+                self.track_operation(tracked);
+            }
+        }
+        if !self.loading.operation_status {
+            return;
+        }
+        self.loading.operation_status = false;
+        let flow = &mut self.create_backup_flow;
+        if batch {
+            flow.batch_operations = results
+                .into_iter()
+                .map(|(config, result)| match result {
+                    Ok(operation_id) => BatchBackupOperation {
+                        instance: config.instance,
+                        operation_id: Some(operation_id),
+                        status: "RUNNING".to_string(),
+                        error: None,
+                    },
+                    Err(e) => BatchBackupOperation {
+                        instance: config.instance,
+                        operation_id: None,
+                        status: "FAILED".to_string(),
+                        error: Some(e.to_string()),
+                    },
+                })
+                .collect();
+            return;
+        }
+        match results.into_iter().next().map(|(_, result)| result) {
+            Some(Ok(operation_id)) => {
+                flow.operation_id = Some(operation_id);
+                flow.last_operation = None;
+                flow.status = Some("RUNNING".to_string());
             }
+            Some(Err(e)) => {
+                self.error = Some(format!("Create backup failed: {}. Press ESC to clear.", e));
+                if self.state == AppState::PerformingCreateBackup {
+                    self.state = AppState::ConfirmCreateBackup;
+                }
+            }
+            None => {}
         }
+    }
+
+    /// Opens the list of stored operations and re-polls the unfinished ones.
+    pub async fn resume_stored_operations(&mut self) -> Result<()> {
+        self.resume_prompt = false;
+        self.state = AppState::MonitoringStoredOperations;
+        self.poll_stored_operations().await
+    }
+
+    pub async fn poll_stored_operations(&mut self) -> Result<()> {
+        self.poll_tracked(self.operation_store.in_flight.clone()).await;
         Ok(())
     }
 
-    pub async fn perform_create_backup(&mut self) -> Result<()> {
-        if let Some(config) = &self.create_backup_flow.config {
-            self.loading = true;
-            self.state = AppState::PerformingCreateBackup;
+    /// Keeps polling the operations a reset left behind, so they still finish in the
+    /// store and stop blocking new ones.
+    pub async fn poll_detached_operations(&mut self) {
+        self.poll_tracked(self.detached_operations()).await;
+    }
+
+    async fn poll_tracked(&mut self, operations: Vec<TrackedOperation>) {
+        for operation in operations {
+            if is_dry_run_operation(&operation.operation_id) {
+                let stored = self
+                    .operation_store
+                    .update_status(&operation.operation_id, "DONE", None, None);
+                self.record_store_result(stored);
+                continue;
+            }
+            let client = self.gcp_client.clone();
+            let kind =
+                RequestKind::OperationStatus(StatusCheck::Stored, operation.operation_id.clone());
+            self.dispatch(kind, async move {
+                let result = client
+                    .get_operation_status(&operation.project, &operation.operation_id)
+                    .await;
+                BackgroundResult::OperationStatus {
+                    check: StatusCheck::Stored,
+                    operation_id: operation.operation_id,
+                    result,
+                }
+            })
+            .await;
+        }
+    }
+
+    fn apply_stored_status(&mut self, operation_id: &str, result: Result<Operation>) {
+        let Some(operation) = self
+            .operation_store
+            .in_flight
+            .iter()
+            .find(|op| op.operation_id == operation_id)
+        else {
+            return;
+        };
+        let (status, error, end_time) = match result {
+            Ok(latest) => (latest.status, latest.error_message, latest.end_time),
+            Err(e) => (
+                operation.status.clone(),
+                Some(format!("Failed to check status: {}", e)),
+                None,
+            ),
+        };
+        let stored = self
+            .operation_store
+            .update_status(operation_id, &status, error, end_time);
+        self.record_store_result(stored);
+    }
+
+    /// Checks the status of the operation followed with `--watch`, until it has finished.
+    pub async fn poll_watched_operation(&mut self) {
+        let Some(flow) = self.watch_flow.as_ref().filter(|flow| !flow.is_finished()) else {
+            return;
+        };
+        let (client, project) = (self.gcp_client.clone(), flow.project.clone());
+        let operation_id = flow.operation_id.clone();
+        let kind = RequestKind::OperationStatus(StatusCheck::Watch, operation_id.clone());
+        self.dispatch(kind, async move {
+            let result = client.get_operation_status(&project, &operation_id).await;
+            BackgroundResult::OperationStatus {
+                check: StatusCheck::Watch,
+                operation_id,
+                result,
+            }
+        })
+        .await;
+    }
+
+    /// A failed check keeps the operation as last seen and is retried on the next poll.
+    fn apply_watch_status(&mut self, operation_id: &str, result: Result<Operation>) {
+        let Some(flow) = self
+            .watch_flow
+            .as_mut()
+            .filter(|flow| flow.operation_id == operation_id)
+        else {
+            return;
+        };
+        match result {
+            Ok(operation) => {
+                flow.operation = Some(operation);
+                flow.poll_error = None;
+            }
+            Err(e) => flow.poll_error = Some(format!("Failed to check status: {}", e)),
+        }
+    }
+
+    /// Persists a started operation and remembers it for the end-of-session summary.
+    fn track_operation(&mut self, tracked: TrackedOperation) {
+        self.session_operations.push(tracked.operation_id.clone());
+        let stored = self.operation_store.track_operation(tracked);
+        self.record_store_result(stored);
+    }
 
-            if self.dry_run_mode {
-                let mock_operation_id =
-                    format!("dry-run-backup-op-{}", chrono::Utc::now().timestamp());
-                self.create_backup_flow.operation_id = Some(mock_operation_id);
-                self.create_backup_flow.status = Some("DONE".to_string());
-                self.loading = false;
-                self.state = AppState::PerformingCreateBackup;
-            } else {
-                match self.gcp_client.create_backup(config).await {
-                    Ok(operation_id) => {
-                        self.create_backup_flow.operation_id = Some(operation_id);
-                        self.create_backup_flow.status = Some("RUNNING".to_string());
-                        self.loading = false;
-                        self.state = AppState::PerformingCreateBackup;
-                    }
-                    Err(e) => {
-                        self.loading = false;
-                        self.error =
-                            Some(format!("Create backup failed: {}. Press ESC to clear.", e));
-                        self.state = AppState::ConfirmCreateBackup;
-                    }
-                }
-            }
+    /// One line per operation started this session, with its latest known status, for
+    /// printing once the TUI has exited.
+    pub fn session_summary(&self) -> Vec<String> {
+        self.session_operations
+            .iter()
+            .filter_map(|id| self.operation_store.all().find(|op| op.operation_id == *id))
+            .map(|op| {
+                let target = match &op.instance {
+                    Some(instance) => format!("{}/{}", op.project, instance),
+                    None => op.project.clone(),
+                };
+                format!("{:?} of {} ({}): {}", op.mode, target, op.operation_id, op.status)
+            })
+            .collect()
+    }
+
+    fn record_store_result(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            self.error = Some(store_error(e));
         }
-        Ok(())
+    }
+
+    /// Whether `operation_id` has no real operation behind it: everything in dry-run
+    /// mode, and dry-run operations even after dry-run mode was turned off.
+    fn is_simulated(&self, operation_id: &str) -> bool {
+        self.dry_run_mode || is_dry_run_operation(operation_id)
     }
 
     pub async fn check_restore_status(&mut self) -> Result<()> {
         if let (Some(operation_id), Some(config)) = (
-            &self.restore_flow.operation_id.clone(),
-            &self.restore_flow.config.clone(),
+            self.restore_flow.operation_id.clone(),
+            &self.restore_flow.config,
         ) {
-            if self.dry_run_mode {
+            if self.is_simulated(&operation_id) {
                 self.restore_flow.status = Some("DONE".to_string());
                 return Ok(());
             }
-
-            match self
-                .gcp_client
-                .get_operation_status(&config.target_project, operation_id)
-                .await
-            {
-                Ok(operation) => {
-                    self.restore_flow.status = Some(operation.status.clone());
+            let (client, project) = (self.gcp_client.clone(), config.target_project.clone());
+            let kind = RequestKind::OperationStatus(StatusCheck::Restore, operation_id.clone());
+            self.dispatch(kind, async move {
+                let result = client.get_operation_status(&project, &operation_id).await;
+                BackgroundResult::OperationStatus {
+                    check: StatusCheck::Restore,
+                    operation_id,
+                    result,
                 }
-                Err(e) => {
-                    self.error = Some(format!("Failed to check restore status: {}", e));
+            })
+            .await;
+            self.start_due_requests().await;
+        }
+        Ok(())
+    }
+
+    fn apply_restore_status(&mut self, operation_id: &str, result: Result<Operation>) {
+        if self.restore_flow.operation_id.as_deref() != Some(operation_id) {
+            return;
+        }
+        match result {
+            Ok(operation) => {
+                let stored = self.operation_store.update_status(
+                    operation_id,
+                    &operation.status,
+                    operation.error_message.clone(),
+                    operation.end_time,
+                );
+                self.record_store_result(stored);
+                let finished_now = operation.status == "DONE"
+                    && operation.error_message.is_none()
+                    && self.restore_flow.status.as_deref() != Some("DONE");
+                self.restore_flow.operation_warning =
+                    operation.type_mismatch(Operation::RESTORE_TYPES, "restore");
+                self.restore_flow.operation_type = Some(operation.operation_type.clone());
+                self.restore_flow.status = Some(operation.status.clone());
+                self.restore_flow.last_operation = Some(operation);
+                if finished_now
+                    && self.mark_restores
+                    && self.operation_store.marker_of(operation_id).is_none()
+                {
+                    self.restore_flow.marker_due = true;
                 }
             }
+            Err(e) => {
+                self.error = Some(format!("Failed to check restore status: {}", e));
+            }
+        }
+    }
+
+    /// Creates an on-demand backup of the restored target whose description records the
+    /// restore: the source backup, where it came from and who ran it. Cloud SQL keeps no
+    /// such details for restores, so this leaves them where the Console shows them.
+    async fn mark_restore(&mut self, restore_operation_id: String, config: &RestoreConfig) {
+        let labels = BTreeMap::from([
+            ("restored-from".to_string(), config.backup_id.clone()),
+            ("source".to_string(), config.source()),
+            (
+                "by".to_string(),
+                self.authenticated_user
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+            ),
+        ]);
+        let name = format!("restore marker {}", Utc::now().format("%Y-%m-%d %H:%M UTC"));
+        let marker = CreateBackupConfig {
+            project: config.target_project.clone(),
+            instance: config.target_instance.clone(),
+            description: describe_with_labels(&name, &labels),
+            name,
+            location: None,
+        };
+        let client = self.gcp_client.clone();
+        self.dispatch(RequestKind::RestoreMarker, async move {
+            BackgroundResult::RestoreMarked {
+                restore_operation_id,
+                result: client.create_backup(&marker).await,
+            }
+        })
+        .await;
+    }
+
+    fn apply_restore_marked(&mut self, restore_operation_id: &str, result: Result<String>) {
+        match result {
+            Ok(operation_id) => {
+                let recorded = self
+                    .operation_store
+                    .record_marker(restore_operation_id, &operation_id);
+                self.record_store_result(recorded);
+                self.restore_flow.marker_operation_id = Some(operation_id);
+            }
+            Err(e) => {
+                self.error = Some(format!(
+                    "The restore finished, but the backup marking it could not be created: \
+                     {}. Press ESC to clear.",
+                    e
+                ));
+            }
         }
-        Ok(())
     }
 
     pub async fn check_backup_status(&mut self) -> Result<()> {
         if let (Some(operation_id), Some(config)) = (
-            &self.create_backup_flow.operation_id.clone(),
-            &self.create_backup_flow.config.clone(),
+            self.create_backup_flow.operation_id.clone(),
+            &self.create_backup_flow.config,
         ) {
-            if self.dry_run_mode {
+            if self.is_simulated(&operation_id) {
                 self.create_backup_flow.status = Some("DONE".to_string());
                 return Ok(());
             }
-
-            match self
-                .gcp_client
-                .get_operation_status(&config.project, operation_id)
-                .await
-            {
-                Ok(operation) => {
-                    self.create_backup_flow.status = Some(operation.status.clone());
-                }
-                Err(e) => {
-                    self.error = Some(format!("Failed to check backup status: {}", e));
+            let (client, project) = (self.gcp_client.clone(), config.project.clone());
+            let kind = RequestKind::OperationStatus(StatusCheck::Backup, operation_id.clone());
+            self.dispatch(kind, async move {
+                let result = client.get_operation_status(&project, &operation_id).await;
+                BackgroundResult::OperationStatus {
+                    check: StatusCheck::Backup,
+                    operation_id,
+                    result,
                 }
+            })
+            .await;
+        }
+        self.check_batch_backup_status().await
+    }
+
+    fn apply_backup_status(&mut self, operation_id: &str, result: Result<Operation>) {
+        if self.create_backup_flow.operation_id.as_deref() != Some(operation_id) {
+            return;
+        }
+        match result {
+            Ok(operation) => {
+                let stored = self.operation_store.update_status(
+                    operation_id,
+                    &operation.status,
+                    operation.error_message.clone(),
+                    operation.end_time,
+                );
+                self.record_store_result(stored);
+                let flow = &mut self.create_backup_flow;
+                flow.operation_warning = operation.type_mismatch(Operation::BACKUP_TYPES, "backup");
+                flow.operation_type = Some(operation.operation_type.clone());
+                flow.status = Some(operation.status.clone());
+                flow.last_operation = Some(operation);
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to check backup status: {}", e));
             }
         }
-        Ok(())
     }
 
-    pub fn move_selection_up(&mut self) {
-        match self.state {
-            AppState::SelectingOperation => {
-                if self.selected_operation_index > 0 {
-                    self.selected_operation_index -= 1;
-                }
+    async fn check_batch_backup_status(&mut self) -> Result<()> {
+        let Some(project) = self.create_backup_flow.project.clone() else {
+            return Ok(());
+        };
+
+        for index in 0..self.create_backup_flow.batch_operations.len() {
+            let entry = &self.create_backup_flow.batch_operations[index];
+            if entry.is_finished() {
+                continue;
             }
-            AppState::SelectingSourceProject
-            | AppState::SelectingTargetProject
-            | AppState::SelectingProjectForBackup => {}
-            AppState::SelectingSourceInstance | AppState::SelectingTargetInstance => {
-                if self.restore_flow.selected_instance_index > 0 {
-                    self.restore_flow.selected_instance_index -= 1;
-                }
+            let Some(operation_id) = entry.operation_id.clone() else {
+                continue;
+            };
+
+            if self.is_simulated(&operation_id) {
+                self.create_backup_flow.batch_operations[index].status = "DONE".to_string();
+                continue;
             }
-            AppState::SelectingInstanceForBackup => {
-                if self.create_backup_flow.selected_instance_index > 0 {
-                    self.create_backup_flow.selected_instance_index -= 1;
+
+            let (client, project) = (self.gcp_client.clone(), project.clone());
+            let kind =
+                RequestKind::OperationStatus(StatusCheck::BatchBackup, operation_id.clone());
+            self.dispatch(kind, async move {
+                let result = client.get_operation_status(&project, &operation_id).await;
+                BackgroundResult::OperationStatus {
+                    check: StatusCheck::BatchBackup,
+                    operation_id,
+                    result,
                 }
-            }
-            AppState::SelectingBackup => {
-                if self.restore_flow.selected_backup_index > 0 {
-                    self.restore_flow.selected_backup_index -= 1;
+            })
+            .await;
+        }
+        Ok(())
+    }
+
+    fn apply_batch_backup_status(&mut self, operation_id: &str, result: Result<Operation>) {
+        if let Ok(operation) = &result {
+            let stored = self.operation_store.update_status(
+                operation_id,
+                &operation.status,
+                operation.error_message.clone(),
+                operation.end_time,
+            );
+            self.record_store_result(stored);
+        }
+        let Some(entry) = self
+            .create_backup_flow
+            .batch_operations
+            .iter_mut()
+            .find(|entry| entry.operation_id.as_deref() == Some(operation_id))
+        else {
+            return;
+        };
+        match result {
+            Ok(operation) => {
+                if let Some(message) = operation.error_message.clone() {
+                    entry.status = "FAILED".to_string();
+                    entry.error = Some(message);
+                } else {
+                    entry.error = operation.type_mismatch(Operation::BACKUP_TYPES, "backup");
+                    entry.status = operation.status;
                 }
             }
-            _ => {}
+            Err(e) => {
+                entry.error = Some(format!("Failed to check backup status: {}", e));
+            }
+        }
+    }
+
+    /// Opens the read-only view of the loaded backups grouped by age.
+    pub fn open_retention_view(&mut self) {
+        if self.restore_flow.backups.items().is_empty() {
+            self.error = Some(
+                "There are no backups to group by age. Press ESC to clear.".to_string(),
+            );
+        } else {
+            self.restore_flow.show_retention = true;
+        }
+    }
+
+    /// Marks or unmarks the highlighted instance for a batch backup.
+    pub fn toggle_instance_mark(&mut self) {
+        let flow = &mut self.create_backup_flow;
+        if let Some(instance) = flow.instances.selected_item() {
+            if let Some(position) = flow.marked_instances.iter().position(|m| m == &instance.name) {
+                flow.marked_instances.remove(position);
+            } else {
+                flow.marked_instances.push(instance.name.clone());
+            }
+        }
+    }
+
+    /// Opens the side-by-side view of the two backups marked for comparison.
+    pub fn open_backup_comparison(&mut self) {
+        if self.restore_flow.compared_backups().is_some() {
+            self.restore_flow.show_comparison = true;
+        } else {
+            self.error = Some(
+                "Mark two backups with Space to compare them. Press ESC to clear.".to_string(),
+            );
         }
     }
 
+    pub fn move_selection_up(&mut self) {
+        self.move_selection(false);
+    }
+
     pub fn move_selection_down(&mut self) {
+        self.move_selection(true);
+    }
+
+    /// Moves the selection of the current list one row. Past either end it stays put, or
+    /// with [`App::wrap_selection`] continues from the other end.
+    fn move_selection(&mut self, down: bool) {
+        let wrap = self.wrap_selection;
         match self.state {
-            AppState::SelectingOperation => {
-                if self.selected_operation_index < 1 {
-                    self.selected_operation_index += 1;
-                }
-            }
-            AppState::SelectingSourceProject
-            | AppState::SelectingTargetProject
-            | AppState::SelectingProjectForBackup => {}
+            AppState::SelectingOperation => move_in(&mut self.operations, down, wrap),
             AppState::SelectingSourceInstance | AppState::SelectingTargetInstance => {
-                if self.restore_flow.selected_instance_index
-                    < self.restore_flow.instances.len().saturating_sub(1)
-                {
-                    self.restore_flow.selected_instance_index += 1;
-                }
+                move_in(&mut self.restore_flow.instances, down, wrap)
             }
             AppState::SelectingInstanceForBackup => {
-                if self.create_backup_flow.selected_instance_index
-                    < self.create_backup_flow.instances.len().saturating_sub(1)
-                {
-                    self.create_backup_flow.selected_instance_index += 1;
-                }
+                move_in(&mut self.create_backup_flow.instances, down, wrap)
             }
-            AppState::SelectingBackup => {
-                if self.restore_flow.selected_backup_index
-                    < self.restore_flow.backups.len().saturating_sub(1)
-                {
-                    self.restore_flow.selected_backup_index += 1;
-                }
+            AppState::SelectingBackup => move_in(&mut self.restore_flow.backups, down, wrap),
+            AppState::BrowsingProjectBackups => {
+                move_in(&mut self.restore_flow.project_backups, down, wrap)
             }
             _ => {}
         }
@@ -326,10 +1807,8 @@ impl App {
     pub async fn select_current_item(&mut self) -> Result<()> {
         match self.state {
             AppState::SelectingOperation => {
-                let selected_mode = if self.selected_operation_index == 0 {
-                    OperationMode::Restore
-                } else {
-                    OperationMode::CreateBackup
+                let Some(selected_mode) = self.operations.selected_item().copied() else {
+                    return Ok(());
                 };
                 self.operation_mode = Some(selected_mode);
                 match selected_mode {
@@ -345,21 +1824,30 @@ impl App {
                 if let Some(instance) = self
                     .restore_flow
                     .instances
-                    .get(self.restore_flow.selected_instance_index)
+                    .selected_item()
                     .cloned()
                 {
                     self.restore_flow.source_instance = Some(instance.name.clone());
+                    self.restore_flow.clear_date_filter();
+                    self.restore_flow.source_version = self
+                        .restore_flow
+                        .instance_version(&instance.name)
+                        .map(str::to_string);
                     if let Some(project) = &self.restore_flow.source_project.clone() {
                         self.state = AppState::SelectingBackup;
                         self.load_backups(project, &instance.name).await?;
                     }
                 }
             }
+            AppState::SelectingInstanceForBackup if self.create_backup_flow.is_batch() => {
+                self.state = AppState::EnteringBackupName;
+                self.start_manual_input("backup_name");
+            }
             AppState::SelectingInstanceForBackup => {
                 if let Some(instance) = self
                     .create_backup_flow
                     .instances
-                    .get(self.create_backup_flow.selected_instance_index)
+                    .selected_item()
                     .cloned()
                 {
                     self.create_backup_flow.instance = Some(instance.name.clone());
@@ -367,17 +1855,44 @@ impl App {
                     self.start_manual_input("backup_name");
                 }
             }
+            AppState::SelectingBackup if self.no_backups_to_restore() => {
+                self.create_backup_of_source();
+            }
             AppState::SelectingBackup => {
-                if let Some(backup) = self
-                    .restore_flow
-                    .backups
-                    .get(self.restore_flow.selected_backup_index)
-                    .cloned()
-                {
+                if let Some(backup) = self.restore_flow.backups.selected_item().cloned() {
+                    if let Some(message) = unrestorable_backup_error(&backup) {
+                        self.error = Some(message);
+                        return Ok(());
+                    }
                     self.restore_flow.selected_backup = Some(backup.id.clone());
                     self.state = AppState::SelectingTargetProject;
                 }
             }
+            AppState::BrowsingProjectBackups => {
+                let flow = &mut self.restore_flow;
+                if let Some((instance, backup)) = flow.project_backups.selected_item().cloned() {
+                    if let Some(message) = unrestorable_backup_error(&backup) {
+                        self.error = Some(message);
+                        return Ok(());
+                    }
+                    // Keep the instance's own backups so Esc from the target step lands
+                    // on a normal backup list for the chosen instance.
+                    let backups = flow
+                        .project_backups
+                        .items()
+                        .iter()
+                        .filter(|(name, _)| *name == instance)
+                        .map(|(_, backup)| backup.clone())
+                        .collect();
+                    flow.backups.set_items(backups);
+                    flow.clear_date_filter();
+                    flow.backups.select_where(|b| b.id == backup.id);
+                    flow.source_version = flow.instance_version(&instance).map(str::to_string);
+                    flow.source_instance = Some(instance);
+                    flow.selected_backup = Some(backup.id);
+                    self.state = AppState::SelectingTargetProject;
+                }
+            }
             AppState::SelectingTargetProject => {
                 self.start_manual_input("target_project");
             }
@@ -385,22 +1900,210 @@ impl App {
                 if let Some(instance) = self
                     .restore_flow
                     .instances
-                    .get(self.restore_flow.selected_instance_index)
+                    .selected_item()
                     .cloned()
                 {
+                    if instance.is_replica() {
+                        self.error = Some(format!(
+                            "{} is a read replica and cannot be a restore target. \
+                             Pick a primary instance. Press ESC to clear.",
+                            instance.name
+                        ));
+                        return Ok(());
+                    }
                     self.restore_flow.target_instance = Some(instance.name.clone());
                     self.create_restore_config();
                     self.state = AppState::ConfirmRestore;
+                    self.poll_instance_state().await;
+                    self.edit_restore_reason();
+                }
+            }
+            AppState::ConfirmRestore => {
+                if self.reason_missing() {
+                    self.edit_restore_reason();
+                    return Ok(());
                 }
+                if self.restore_target_unchecked() {
+                    self.error = Some(
+                        "Still checking whether the restore target is in use. Try again in a \
+                         moment. Press ESC to clear."
+                            .to_string(),
+                    );
+                    return Ok(());
+                }
+                if self.restore_target_protected() || self.restore_target_in_use() {
+                    self.restore_flow.confirm_started_at = Some(Instant::now());
+                    self.start_manual_input("confirm_restore");
+                    return Ok(());
+                }
+                self.restore_flow.retry_attempts = 0;
+                self.perform_restore().await?;
             }
             AppState::ConfirmCreateBackup => {
                 self.perform_create_backup().await?;
             }
+            AppState::PerformingRestore | AppState::PerformingCreateBackup => {
+                self.show_operation_details();
+            }
             _ => {}
         }
         Ok(())
     }
 
+    /// Whether the configured restore target matches `protect_pattern`, by project or
+    /// instance name.
+    pub fn restore_target_protected(&self) -> bool {
+        self.restore_flow.config.as_ref().is_some_and(|config| {
+            self.protect_pattern.is_match(&config.target_project)
+                || self.protect_pattern.is_match(&config.target_instance)
+        })
+    }
+
+    /// Time left before a protected restore can be confirmed.
+    pub fn protected_confirm_remaining(&self) -> Duration {
+        self.restore_flow
+            .confirm_started_at
+            .map_or(PROTECTED_CONFIRM_DELAY, |started| {
+                PROTECTED_CONFIRM_DELAY.saturating_sub(started.elapsed())
+            })
+    }
+
+    /// The flow of the current step, if this session started its restore or backup(s).
+    /// A started restore returns to the target step, so the `Performing*` states alone do
+    /// not tell whether an operation is being followed.
+    pub fn followed_operation(&self) -> Option<OperationMode> {
+        let mode = self.state.operation_mode()?;
+        let started = match mode {
+            OperationMode::Restore => self.restore_flow.operation_id.is_some(),
+            OperationMode::CreateBackup => self.create_backup_flow.has_operations(),
+        };
+        started.then_some(mode)
+    }
+
+    /// Whether the followed operation is a single one that the details popup can show.
+    /// Batches show each backup in their table instead.
+    pub fn has_operation_details(&self) -> bool {
+        match self.followed_operation() {
+            Some(OperationMode::Restore) => true,
+            Some(OperationMode::CreateBackup) => self.create_backup_flow.operation_id.is_some(),
+            None => false,
+        }
+    }
+
+    /// The followed restore or backup as last fetched, for the details popup.
+    pub fn operation_details(&self) -> Option<&Operation> {
+        match self.followed_operation() {
+            Some(OperationMode::Restore) => self.restore_flow.last_operation.as_ref(),
+            Some(OperationMode::CreateBackup) => self.create_backup_flow.last_operation.as_ref(),
+            None => None,
+        }
+    }
+
+    /// Opens the details popup of the followed restore or backup, if there is one.
+    pub fn show_operation_details(&mut self) {
+        if self.has_operation_details() {
+            self.show_operation_details = true;
+        }
+    }
+
+    /// The flow whose restore or backup has a `gcloud` command to show: the one being
+    /// confirmed, or the one followed after it was started.
+    pub fn gcloud_command_mode(&self) -> Option<OperationMode> {
+        match self.state {
+            AppState::ConfirmRestore => Some(OperationMode::Restore),
+            AppState::ConfirmCreateBackup => Some(OperationMode::CreateBackup),
+            _ => self.followed_operation(),
+        }
+    }
+
+    /// Shows the `gcloud` command that does what the current restore or backup does.
+    pub fn show_gcloud_command(&mut self) {
+        let commands: Vec<String> = match self.gcloud_command_mode() {
+            Some(OperationMode::Restore) => self
+                .restore_flow
+                .config
+                .iter()
+                .map(gcp::gcloud_restore_command)
+                .collect(),
+            Some(OperationMode::CreateBackup) => {
+                let flow = &self.create_backup_flow;
+                flow.config
+                    .iter()
+                    .chain(&flow.batch_configs)
+                    .map(gcp::gcloud_backup_command)
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        if commands.is_empty() {
+            self.error = Some(
+                "There is no configured operation to show a gcloud command for. \
+                 Press ESC to clear."
+                    .to_string(),
+            );
+        } else {
+            self.gcloud_preview = Some(commands.join("\n"));
+        }
+    }
+
+    /// Steps back from the backup list to the source instances, reloading them if the
+    /// target step replaced them, with the source instance still selected.
+    pub async fn back_to_source_instances(&mut self) -> Result<()> {
+        let flow = &mut self.restore_flow;
+        let source_instance = flow.source_instance.take();
+        flow.source_version = None;
+        flow.backups.clear();
+        flow.compare_marks.clear();
+        self.state = AppState::SelectingSourceInstance;
+        if self.restore_flow.instances.is_empty() {
+            if let Some(project) = self.restore_flow.source_project.clone() {
+                self.load_instances_now(&project).await?;
+            }
+        }
+        if let Some(name) = source_instance {
+            self.restore_flow.instances.select_where(|i| i.name == name);
+        }
+        Ok(())
+    }
+
+    /// Shortcut for restoring within the source project: makes it the target project and
+    /// moves on to picking the target instance.
+    pub async fn use_source_project_as_target(&mut self) -> Result<()> {
+        let Some(project) = self.restore_flow.source_project.clone() else {
+            return Ok(());
+        };
+        self.restore_flow.target_project = Some(project.clone());
+        self.state = AppState::SelectingTargetInstance;
+        self.load_instances(&project).await
+    }
+
+    /// Selects the source instance as the target, for restoring an instance from its own
+    /// backup. Only offered when source and target are in the same project.
+    pub async fn use_source_instance_as_target(&mut self) -> Result<()> {
+        let flow = &self.restore_flow;
+        let Some(source_instance) = flow.source_instance.clone() else {
+            return Ok(());
+        };
+        if flow.source_project != flow.target_project {
+            self.error = Some(
+                "The source instance is in another project. Press ESC to clear.".to_string(),
+            );
+            return Ok(());
+        }
+        if self
+            .restore_flow
+            .instances
+            .select_where(|i| i.name == source_instance)
+        {
+            return self.select_current_item().await;
+        }
+        self.error = Some(format!(
+            "{} is not in the target instance list. Press ESC to clear.",
+            source_instance
+        ));
+        Ok(())
+    }
+
     pub fn create_restore_config(&mut self) {
         if let (
             Some(backup_id),
@@ -421,6 +2124,8 @@ impl App {
                 source_instance: source_instance.clone(),
                 target_project: target_project.clone(),
                 target_instance: target_instance.clone(),
+                intended_databases: self.restore_flow.intended_databases.clone(),
+                reason: self.restore_flow.reason.clone(),
             });
         }
     }
@@ -433,14 +2138,173 @@ impl App {
             self.create_backup_flow.config = Some(CreateBackupConfig {
                 project: project.clone(),
                 instance: instance.clone(),
-                name: backup_name.clone(),
-                description: backup_name,
+                description: describe_with_labels(&backup_name, &self.create_backup_flow.labels),
+                name: backup_name,
+                location: self.create_backup_flow.backup_location(instance),
             });
         }
     }
 
+    /// Builds one config per marked instance. `{instance}` and `{date}` in the name
+    /// template are replaced with the instance name and today's date.
+    pub fn create_batch_backup_configs(&mut self, name_template: &str) {
+        if let Some(project) = self.create_backup_flow.project.clone() {
+            let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+            self.create_backup_flow.batch_configs = self
+                .create_backup_flow
+                .marked_instances
+                .iter()
+                .map(|instance| {
+                    let name = name_template
+                        .replace("{instance}", instance)
+                        .replace("{date}", &date);
+                    CreateBackupConfig {
+                        project: project.clone(),
+                        instance: instance.clone(),
+                        description: describe_with_labels(&name, &self.create_backup_flow.labels),
+                        name,
+                        location: self.create_backup_flow.backup_location(instance),
+                    }
+                })
+                .collect();
+        }
+    }
+
+    /// Pins the location of the pending backup(s); `None` goes back to each instance's region.
+    pub fn set_backup_location(&mut self, location: Option<String>) {
+        let flow = &mut self.create_backup_flow;
+        flow.location = location;
+        let locations: Vec<Option<String>> = flow
+            .config
+            .iter()
+            .chain(flow.batch_configs.iter())
+            .map(|config| flow.backup_location(&config.instance))
+            .collect();
+        for (config, location) in flow
+            .config
+            .iter_mut()
+            .chain(flow.batch_configs.iter_mut())
+            .zip(locations)
+        {
+            config.location = location;
+        }
+    }
+
+    /// Stores labels for the pending backup(s) and re-encodes their descriptions.
+    pub fn set_backup_labels(&mut self, labels: BTreeMap<String, String>) {
+        let flow = &mut self.create_backup_flow;
+        for config in flow.config.iter_mut().chain(flow.batch_configs.iter_mut()) {
+            config.description = describe_with_labels(&config.name, &labels);
+        }
+        flow.labels = labels;
+    }
+
+    /// Returns to operation selection with no flow state left over from the previous
+    /// operation. Session-wide data (user, remembered projects, caches) is kept.
+    pub fn reset_to_operation_selection(&mut self) {
+        self.invalidate_pending_requests();
+        self.state = AppState::SelectingOperation;
+        self.operation_mode = None;
+        self.restore_flow = RestoreFlow::new();
+        self.create_backup_flow = CreateBackupFlow::new();
+        self.operations.select(0);
+        self.loading = LoadingState::default();
+        self.error = None;
+        self.error_scroll = 0;
+        self.instance_load_error = None;
+        self.dry_run_preview = None;
+        self.gcloud_preview = None;
+        self.show_operation_details = false;
+        self.polling_paused = false;
+        self.show_help = false;
+        self.help_scroll = 0;
+        self.cancel_manual_input();
+        self.manual_input_type.clear();
+        self.input_buffer.clear();
+    }
+
+    /// Whether the source instance's backups have loaded and there are none, so the only
+    /// way forward is to take one first.
+    pub fn no_backups_to_restore(&self) -> bool {
+        matches!(self.state, AppState::SelectingBackup)
+            && self.restore_flow.backups.items().is_empty()
+            && !self.loading.backups
+            && self.error.is_none()
+    }
+
+    /// Leaves a restore whose source instance has no backups for creating a backup of that
+    /// instance, starting at the backup name.
+    pub fn create_backup_of_source(&mut self) {
+        let flow = &self.restore_flow;
+        let (Some(project), Some(instance)) =
+            (flow.source_project.clone(), flow.source_instance.clone())
+        else {
+            return;
+        };
+        let instances = std::mem::take(&mut self.restore_flow.instances);
+        self.reset_to_operation_selection();
+        self.operation_mode = Some(OperationMode::CreateBackup);
+        let flow = &mut self.create_backup_flow;
+        flow.instances = instances;
+        flow.instances.select_where(|i| i.name == instance);
+        flow.project = Some(project);
+        flow.instance = Some(instance);
+        self.state = AppState::EnteringBackupName;
+        self.start_manual_input("backup_name");
+    }
+
+    /// The current operation. Every step of a flow needs one; if it was lost anyway, the
+    /// app goes back to operation selection with an error instead of staying on a step
+    /// that would silently ignore input.
+    pub fn recover_operation_mode(&mut self) -> Option<OperationMode> {
+        let state_mode = self.state.operation_mode();
+        debug_assert!(
+            self.operation_mode.is_none()
+                || state_mode.is_none()
+                || self.operation_mode == state_mode,
+            "{:?} does not belong to {:?}",
+            self.state,
+            self.operation_mode
+        );
+        if self.operation_mode.is_none() && state_mode.is_some() {
+            self.reset_to_operation_selection();
+            self.error = Some(
+                "Lost track of the current operation, please start it again. \
+                 Press ESC to clear."
+                    .to_string(),
+            );
+        }
+        self.operation_mode
+    }
+
+    /// Whether status is polled periodically: that of a started restore or backup, on
+    /// whatever step the flow is, or of the operations on the current screen.
+    pub fn polls_status(&self) -> bool {
+        self.restore_flow.operation_id.is_some()
+            || self.create_backup_flow.has_operations()
+            || !self.detached_operations().is_empty()
+            || matches!(
+                self.state,
+                AppState::MonitoringStoredOperations | AppState::WatchingOperation
+            )
+    }
+
+    pub fn toggle_polling(&mut self) {
+        self.polling_paused = !self.polling_paused;
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    /// Moves `project` to the front of the recent projects, replacing any entry that
+    /// differs only in case, and drops the least recently used beyond the cap.
+    pub fn remember_project(&mut self, project: &str) {
+        self.remembered_projects
+            .retain(|known| !known.eq_ignore_ascii_case(project));
+        self.remembered_projects.insert(0, project.to_string());
+        self.remembered_projects.truncate(MAX_REMEMBERED_PROJECTS);
     }
 
     pub fn start_manual_input(&mut self, input_type: &str) {
@@ -450,14 +2314,47 @@ impl App {
         self.input_mode = InputMode::Editing;
     }
 
+    /// Opens the optional reason input shown before the restore confirmation, pre-filled
+    /// with the reason given so far.
+    pub fn edit_restore_reason(&mut self) {
+        self.start_manual_input("reason");
+        if let Some(reason) = &self.restore_flow.reason {
+            self.manual_input_buffer = reason.clone();
+        }
+    }
+
+    /// Records why the restore is being done; blank reasons count as none.
+    pub fn set_restore_reason(&mut self, reason: Option<String>) {
+        self.restore_flow.reason = reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+        self.create_restore_config();
+    }
+
+    /// Whether `--require-reason` is set and the configured restore has no reason.
+    pub fn reason_missing(&self) -> bool {
+        self.require_reason
+            && self
+                .restore_flow
+                .config
+                .as_ref()
+                .is_some_and(|config| config.reason.is_none())
+    }
+
+    /// Opens the backup name input, pre-filled with the name entered last.
+    pub fn edit_backup_name(&mut self) {
+        self.start_manual_input("backup_name");
+        if let Some(name) = &self.create_backup_flow.last_backup_name {
+            self.manual_input_buffer = name.clone();
+        }
+    }
+
     pub async fn finish_manual_input(&mut self) -> Result<()> {
         let input_value = self.manual_input_buffer.trim().to_string();
         if !input_value.is_empty() {
             match self.manual_input_type.as_str() {
                 "source_project" => {
-                    if !self.remembered_projects.contains(&input_value) {
-                        self.remembered_projects.push(input_value.clone());
-                    }
+                    self.remember_project(&input_value);
                     self.manual_input_active = false;
                     self.input_mode = InputMode::Normal;
                     match self.operation_mode {
@@ -469,14 +2366,15 @@ impl App {
                             self.create_backup_flow.project = Some(input_value.clone());
                             self.state = AppState::SelectingInstanceForBackup
                         }
-                        None => {}
+                        None => {
+                            self.recover_operation_mode();
+                            return Ok(());
+                        }
                     }
                     self.load_instances(&input_value).await?;
                 }
                 "target_project" => {
-                    if !self.remembered_projects.contains(&input_value) {
-                        self.remembered_projects.push(input_value.clone());
-                    }
+                    self.remember_project(&input_value);
                     self.restore_flow.target_project = Some(input_value.clone());
                     self.manual_input_active = false;
                     self.input_mode = InputMode::Normal;
@@ -487,54 +2385,272 @@ impl App {
                     if !self.remembered_instances.contains(&input_value) {
                         self.remembered_instances.push(input_value.clone());
                     }
-                    let instance = SqlInstance {
-                        name: input_value.clone(),
-                        database_version: "Manual".to_string(),
-                        region: "Manual".to_string(),
-                        tier: "Manual".to_string(),
-                    };
+                    let instance = SqlInstance::manual(&input_value);
                     match self.operation_mode {
                         Some(OperationMode::Restore) => {
                             self.restore_flow.instances.push(instance);
-                            self.restore_flow.selected_instance_index =
-                                self.restore_flow.instances.len() - 1;
                         }
                         Some(OperationMode::CreateBackup) => {
                             self.create_backup_flow.instances.push(instance);
-                            self.create_backup_flow.selected_instance_index =
-                                self.create_backup_flow.instances.len() - 1;
                         }
-                        None => {}
+                        None => {
+                            self.recover_operation_mode();
+                        }
                     }
                 }
-                "backup" => {
-                    let backup = Backup {
-                        id: input_value.clone(),
-                        start_time: None,
-                        backup_type: "Manual".to_string(),
-                        status: "Manual".to_string(),
-                    };
-                    self.restore_flow.backups.push(backup);
-                    self.restore_flow.selected_backup_index = self.restore_flow.backups.len() - 1;
+                // Cloud SQL backup ids are numeric; anything else is a typo.
+                "backup" if !input_value.chars().all(|c| c.is_ascii_digit()) => {
+                    self.error = Some(format!(
+                        "Invalid backup id '{}': backup ids are numeric. Press ESC to clear.",
+                        input_value
+                    ));
+                }
+                "backup" => match (
+                    self.restore_flow.source_project.clone(),
+                    self.restore_flow.source_instance.clone(),
+                ) {
+                    // With a known source, check the id exists and skip straight to the target.
+                    (Some(project), Some(instance)) => {
+                        self.loading.backups = true;
+                        let client = self.gcp_client.clone();
+                        self.dispatch(RequestKind::TypedBackup, async move {
+                            let result = client.list_backups(&project, &instance).await;
+                            BackgroundResult::TypedBackup {
+                                instance,
+                                backup_id: input_value,
+                                result,
+                            }
+                        })
+                        .await;
+                    }
+                    _ => {
+                        let backup = Backup {
+                            id: input_value.clone(),
+                            start_time: None,
+                            backup_type: Backup::MANUAL.to_string(),
+                            status: Backup::MANUAL.to_string(),
+                            description: String::new(),
+                        };
+                        // Manual backups have no timestamp, so drop the filter to keep it visible.
+                        let flow = &mut self.restore_flow;
+                        flow.clear_date_filter();
+                        if !flow.backups.select_where(|b| b.id == backup.id) {
+                            flow.backups.push(backup);
+                        }
+                    }
+                },
+                "backup_date_filter" => match DateRange::parse(&input_value) {
+                    Ok(range) => {
+                        self.manual_input_active = false;
+                        self.input_mode = InputMode::Normal;
+                        let zone = self.display_zone;
+                        self.restore_flow.set_date_filter(range, zone);
+                        self.restore_flow.backups.select(0);
+                    }
+                    Err(message) => {
+                        self.error = Some(message);
+                    }
+                },
+                "databases" => {
+                    self.manual_input_active = false;
+                    self.input_mode = InputMode::Normal;
+                    self.restore_flow.intended_databases = input_value
+                        .split(',')
+                        .map(|db| db.trim().to_string())
+                        .filter(|db| !db.is_empty())
+                        .collect();
+                    self.create_restore_config();
+                }
+                "backup_labels" => match parse_label_input(&input_value) {
+                    Ok(labels) => {
+                        self.manual_input_active = false;
+                        self.input_mode = InputMode::Normal;
+                        self.set_backup_labels(labels);
+                    }
+                    Err(message) => {
+                        self.error = Some(message);
+                    }
+                },
+                "backup_location" => {
+                    let location = input_value.to_lowercase();
+                    if location
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+                    {
+                        self.manual_input_active = false;
+                        self.input_mode = InputMode::Normal;
+                        self.set_backup_location(Some(location));
+                    } else {
+                        self.error = Some(format!(
+                            "Invalid location '{}': expected a region such as europe-west1 \
+                             or a multi-region such as eu. Press ESC to clear.",
+                            input_value
+                        ));
+                    }
+                }
+                "confirm_restore" => {
+                    let target = self
+                        .restore_flow
+                        .config
+                        .as_ref()
+                        .map(|config| config.target_instance.clone())
+                        .unwrap_or_default();
+                    let remaining = self.protected_confirm_remaining();
+                    if input_value != target {
+                        self.error = Some(format!(
+                            "Type {} exactly to confirm the restore. Press ESC to clear.",
+                            target
+                        ));
+                    } else if !remaining.is_zero() {
+                        self.error = Some(format!(
+                            "{} needs a deliberate confirmation. Wait {}s before \
+                             confirming. Press ESC to clear.",
+                            target,
+                            remaining.as_secs() + 1
+                        ));
+                    } else {
+                        self.manual_input_active = false;
+                        self.input_mode = InputMode::Normal;
+                        self.restore_flow.retry_attempts = 0;
+                        self.perform_restore().await?;
+                    }
+                }
+                "reason" => {
+                    self.manual_input_active = false;
+                    self.input_mode = InputMode::Normal;
+                    self.set_restore_reason(Some(input_value));
                 }
                 "backup_name" => {
+                    self.create_backup_flow.last_backup_name = Some(input_value.clone());
                     self.manual_input_active = false;
                     self.input_mode = InputMode::Normal;
-                    self.create_backup_config(input_value);
+                    if self.create_backup_flow.is_batch() {
+                        self.create_batch_backup_configs(&input_value);
+                    } else {
+                        self.create_backup_config(input_value);
+                    }
                     self.state = AppState::ConfirmCreateBackup;
                 }
                 _ => {}
             }
         } else {
+            if self.manual_input_type == "reason" {
+                if self.require_reason {
+                    self.error = Some(REASON_REQUIRED.to_string());
+                    return Ok(());
+                }
+                self.set_restore_reason(None);
+            }
+            if self.manual_input_type == "backup_date_filter" {
+                self.restore_flow.clear_date_filter();
+                self.restore_flow.backups.select(0);
+            }
+            if self.manual_input_type == "backup_location" {
+                self.set_backup_location(None);
+            }
             self.manual_input_active = false;
             self.input_mode = InputMode::Normal;
         }
         Ok(())
     }
 
+    /// Selects `backup` as the restore source and moves on to choosing the target, unless
+    /// it cannot be restored from.
+    fn select_backup(&mut self, backup: Backup) {
+        if let Some(message) = unrestorable_backup_error(&backup) {
+            self.error = Some(message);
+            return;
+        }
+        let flow = &mut self.restore_flow;
+        flow.clear_date_filter();
+        if !flow.backups.select_where(|b| b.id == backup.id) {
+            flow.backups.push(backup.clone());
+        }
+        flow.selected_backup = Some(backup.id);
+        self.state = AppState::SelectingTargetProject;
+    }
+
     pub fn cancel_manual_input(&mut self) {
         self.manual_input_active = false;
         self.manual_input_buffer.clear();
         self.input_mode = InputMode::Normal;
     }
 }
+
+/// Why `backup` cannot be restored from: it failed or has not finished. Typed-in backups,
+/// whose status is unknown, are let through and flagged on the confirmation instead.
+fn unrestorable_backup_error(backup: &Backup) -> Option<String> {
+    (!backup.completed() && !backup.status_unknown()).then(|| {
+        format!(
+            "Backup {} has status {} and cannot be restored from. Pick a SUCCESSFUL backup. \
+             Press ESC to clear.",
+            backup.id, backup.status
+        )
+    })
+}
+
+/// Whether `operation_id` was made up by a dry run rather than returned by the API.
+pub fn is_dry_run_operation(operation_id: &str) -> bool {
+    operation_id.starts_with(DRY_RUN_OPERATION_PREFIX)
+}
+
+/// Re-checks that the backup still exists right before restoring, since a retention
+/// policy can delete it between selection and confirmation.
+async fn missing_backup_error(
+    client: &dyn GcpClientTrait,
+    config: &RestoreConfig,
+) -> Option<String> {
+    match client
+        .list_backups(&config.source_project, &config.source_instance)
+        .await
+    {
+        Ok(backups) if backups.iter().any(|b| b.id == config.backup_id) => None,
+        Ok(_) => Some(format!(
+            "Backup {} no longer exists on {}. It may have been removed by a retention \
+             policy. Press ESC to clear.",
+            config.backup_id, config.source_instance
+        )),
+        Err(e) => Some(format!(
+            "Could not verify that backup {} still exists: {}. Press ESC to clear.",
+            config.backup_id, e
+        )),
+    }
+}
+
+/// Replaces the instances in `list`, keeping the selected one selected if it is still
+/// there, so a reloaded list keeps the user's place.
+fn reload_instances(list: &mut SelectableList<SqlInstance>, instances: Vec<SqlInstance>) {
+    let selected = list.selected_item().map(|instance| instance.name.clone());
+    list.set_items(instances);
+    if let Some(name) = selected {
+        list.select_where(|instance| instance.name == name);
+    }
+}
+
+/// Operations offered on the first screen, in display order.
+fn offered_operations(restore_disabled: bool) -> Vec<OperationMode> {
+    if restore_disabled {
+        vec![OperationMode::CreateBackup]
+    } else {
+        vec![OperationMode::Restore, OperationMode::CreateBackup]
+    }
+}
+
+fn move_in<T>(list: &mut SelectableList<T>, down: bool, wrap: bool) {
+    if down {
+        list.move_down(wrap);
+    } else {
+        list.move_up(wrap);
+    }
+}
+
+fn store_error(error: anyhow::Error) -> String {
+    format!("Failed to save operation history: {}. Press ESC to clear.", error)
+}
+
+/// Renders the request that would be POSTed, for display in dry-run mode.
+fn request_preview<T: Serialize>(url: &str, body: &T) -> String {
+    let body = serde_json::to_string_pretty(body)
+        .unwrap_or_else(|e| format!("<failed to serialize request: {}>", e));
+    format!("POST {}\n\n{}", url, body)
+}
@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+/// `$XDG_CONFIG_HOME/gcp-snap-crab`, falling back to `~/.config/gcp-snap-crab`.
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("gcp-snap-crab"))
+}
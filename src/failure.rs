@@ -0,0 +1,110 @@
+use reqwest::StatusCode;
+use std::fmt;
+
+/// Broad class of a failure. Headless commands exit with a code per class so scripts can
+/// react to, say, an expired login differently from a failed restore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// Invalid or missing command-line arguments.
+    Usage,
+    /// gcloud is missing, not logged in or lacks permission, or the API is disabled.
+    Prerequisites,
+    /// A project, instance, backup or operation does not exist.
+    NotFound,
+    /// Cloud SQL rejected or failed the operation.
+    OperationFailed,
+    /// A request or operation did not finish in time.
+    Timeout,
+}
+
+/// Help text listing the exit codes of headless commands.
+pub const EXIT_CODES_HELP: &str = "Exit codes of headless commands:
+  0  success
+  1  other error
+  2  invalid arguments
+  3  gcloud missing, not authenticated, permission denied or API disabled
+  4  project, instance, backup or operation not found
+  5  the operation was rejected or failed
+  6  timed out";
+
+impl FailureKind {
+    /// The process exit code for this class, as listed in [`EXIT_CODES_HELP`].
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Usage => 2,
+            Self::Prerequisites => 3,
+            Self::NotFound => 4,
+            Self::OperationFailed => 5,
+            Self::Timeout => 6,
+        }
+    }
+
+    /// An error of this class with the given message.
+    pub fn error(self, message: impl Into<String>) -> anyhow::Error {
+        anyhow::Error::new(Failure {
+            kind: self,
+            message: message.into(),
+        })
+    }
+
+    /// The class of a failed Cloud SQL Admin API response.
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Prerequisites,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => Self::Timeout,
+            _ => Self::OperationFailed,
+        }
+    }
+
+    /// The class of a failed gcloud command, judged from what it printed to stderr.
+    pub fn from_gcloud_stderr(stderr: &str) -> Option<Self> {
+        let stderr = stderr.to_lowercase();
+        if ["permission_denied", "not authorized", "httperror 403", "auth login"]
+            .iter()
+            .any(|needle| stderr.contains(needle))
+        {
+            Some(Self::Prerequisites)
+        } else if ["not found", "httperror 404", "does not exist"]
+            .iter()
+            .any(|needle| stderr.contains(needle))
+        {
+            Some(Self::NotFound)
+        } else {
+            None
+        }
+    }
+
+    /// The class of `error`, which may have context added since it was created. Request
+    /// timeouts are recognised without being classified where they happened.
+    pub fn of(error: &anyhow::Error) -> Option<Self> {
+        if let Some(failure) = error.downcast_ref::<Failure>() {
+            return Some(failure.kind);
+        }
+        error
+            .chain()
+            .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+            .any(reqwest::Error::is_timeout)
+            .then_some(Self::Timeout)
+    }
+
+    /// The exit code for `error`: its class's code, or 1 if it has none.
+    pub fn exit_code_for(error: &anyhow::Error) -> i32 {
+        Self::of(error).map_or(1, Self::exit_code)
+    }
+}
+
+/// An error tagged with its [`FailureKind`]. It displays as just the message.
+#[derive(Debug)]
+pub struct Failure {
+    pub kind: FailureKind,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Failure {}
@@ -1,20 +1,353 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::{Client, NoProxy, Proxy, StatusCode};
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::process::{Output, Stdio};
+use std::time::{Duration, Instant};
 use tokio::process::Command as AsyncCommand;
+use tokio::sync::Mutex;
 
+use crate::failure::{Failure, FailureKind};
 use crate::types::{
-    Backup, CreateBackupConfig, GcpApiResponse, Operation, RestoreRequest, SqlInstance,
+    Backup, CreateBackupConfig, CreateBackupRequest, GcpApiResponse, InstanceStatus, Operation,
+    RestoreConfig, RestoreRequest, SqlInstance,
 };
 
+/// The Cloud SQL Admin API. `--api-endpoint` or `SQLADMIN_ENDPOINT` point elsewhere, e.g.
+/// at an emulator or a mock server.
+pub const DEFAULT_API_ENDPOINT: &str = "https://sqladmin.googleapis.com/v1";
+/// Sent on every Cloud SQL Admin API request so admins can attribute the traffic in audit logs.
+pub const USER_AGENT: &str = concat!("gcp-snap-crab/", env!("CARGO_PKG_VERSION"));
+/// Cached access tokens are refreshed this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+/// Assumed when gcloud does not report an expiry. User and service-account tokens last an hour.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// An error for a failed gcloud command, classified by its message where possible.
+fn gcloud_error(message: &str) -> anyhow::Error {
+    match FailureKind::from_gcloud_stderr(message) {
+        Some(kind) => kind.error(message),
+        None => anyhow!(message.to_string()),
+    }
+}
+
+/// How long a gcloud command may run before it is given up on. With stdin closed, gcloud
+/// fails rather than prompting, but a command stuck on anything else must not hang the TUI.
+pub const GCLOUD_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long fetching an access token may take. It only reads local credentials, or
+/// refreshes them in one request, so a slower fetch is almost always stuck on reauth.
+pub const TOKEN_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How the gcloud CLI is run: as which account, and which executable.
+#[derive(Debug, Clone)]
+pub struct Gcloud {
+    program: String,
+    account: Option<String>,
+}
+
+impl Default for Gcloud {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl Gcloud {
+    /// Runs every command as `account` (`--account`); without one, as gcloud's active
+    /// account.
+    pub fn new(account: Option<String>) -> Self {
+        Self {
+            program: "gcloud".to_string(),
+            account,
+        }
+    }
+
+    /// Runs `program` instead of `gcloud` from the `PATH`, e.g. a stand-in script in tests.
+    pub fn with_program(mut self, program: &str) -> Self {
+        self.program = program.to_string();
+        self
+    }
+
+    pub fn account(&self) -> Option<&str> {
+        self.account.as_deref()
+    }
+
+    /// Runs gcloud with `args`, adding `--account` when one was chosen. Its stdin is
+    /// closed: the TUI owns the terminal, so a prompt (for example to reauthenticate)
+    /// could never be answered and would hang forever. It is given [`GCLOUD_TIMEOUT`].
+    pub async fn run(&self, args: &[&str]) -> Result<Output> {
+        self.run_with_timeout(args, GCLOUD_TIMEOUT).await
+    }
+
+    /// Like [`Gcloud::run`], but gives up after `timeout`.
+    pub async fn run_with_timeout(&self, args: &[&str], timeout: Duration) -> Result<Output> {
+        let mut command = AsyncCommand::new(&self.program);
+        command.args(args);
+        if let Some(account) = &self.account {
+            command.arg(format!("--account={}", account));
+        }
+        self.run_unscoped(command, args, timeout).await
+    }
+
+    /// Runs `command` (gcloud with `args`) with stdin closed, for at most `timeout`.
+    async fn run_unscoped(
+        &self,
+        mut command: AsyncCommand,
+        args: &[&str],
+        timeout: Duration,
+    ) -> Result<Output> {
+        let output = command.stdin(Stdio::null()).kill_on_drop(true).output();
+        match tokio::time::timeout(timeout, output).await {
+            Ok(output) => Ok(output?),
+            Err(_) => {
+                let name: Vec<&str> = args
+                    .iter()
+                    .copied()
+                    .take_while(|arg| !arg.starts_with('-'))
+                    .collect();
+                Err(FailureKind::Timeout.error(format!(
+                    "gcloud {} did not finish within {}s. If it is waiting to reauthenticate, \
+                     run `gcloud auth login` and try again",
+                    name.join(" "),
+                    timeout.as_secs_f32()
+                )))
+            }
+        }
+    }
+
+    /// Lists the active accounts. Unlike [`Gcloud::run`] it does not pass `--account`,
+    /// which would report the chosen account as active instead of checking that it is.
+    async fn active_accounts(&self) -> Result<Output> {
+        let args = ["auth", "list", "--filter=status:ACTIVE", "--format=value(account)"];
+        let mut command = AsyncCommand::new(&self.program);
+        command.args(args);
+        self.run_unscoped(command, &args, GCLOUD_TIMEOUT).await
+    }
+}
+
+/// Whether `error` is the gcloud program failing to start because it does not exist.
+fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<std::io::Error>()
+        .is_some_and(|error| error.kind() == std::io::ErrorKind::NotFound)
+}
+
+/// The error for a failed `gcloud auth print-access-token`, given its stderr. Expired
+/// sessions get an instruction instead of gcloud's interactive wording.
+pub fn gcloud_token_error(stderr: &str) -> anyhow::Error {
+    let lower = stderr.to_lowercase();
+    if lower.contains("reauth") || lower.contains("auth login") {
+        return FailureKind::Prerequisites
+            .error("Reauthentication required: run `gcloud auth login` and try again");
+    }
+    match stderr.trim() {
+        "" => FailureKind::Prerequisites.error("Failed to get access token"),
+        stderr => {
+            FailureKind::Prerequisites.error(format!("Failed to get access token: {}", stderr))
+        }
+    }
+}
+
+/// Returns true if a failed Cloud SQL response says another operation on the instance is
+/// in progress, so the request can be retried: HTTP 409 or the `operationInProgress`
+/// reason in the error body.
+pub fn is_instance_busy_error(status: StatusCode, body: &str) -> bool {
+    status == StatusCode::CONFLICT
+        || serde_json::from_str::<Value>(body).is_ok_and(|body| {
+            body["error"]["errors"].as_array().is_some_and(|errors| {
+                errors.iter().any(|error| error["reason"] == "operationInProgress")
+            })
+        })
+}
+
+/// Marks an error as Cloud SQL refusing a request because the instance is busy.
+#[derive(Debug)]
+pub struct InstanceBusy;
+
+impl std::fmt::Display for InstanceBusy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("another operation is in progress on the instance")
+    }
+}
+
+impl std::error::Error for InstanceBusy {}
+
+/// A failed operation that [`is_instance_busy`] recognises, displayed as `message`.
+pub fn instance_busy_error(message: impl Into<String>) -> anyhow::Error {
+    anyhow::Error::new(InstanceBusy).context(Failure {
+        kind: FailureKind::OperationFailed,
+        message: message.into(),
+    })
+}
+
+/// Whether `error` came from [`instance_busy_error`] and the request can be retried.
+pub fn is_instance_busy(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<InstanceBusy>().is_some()
+}
+
+/// Recognizes the 403 Cloud SQL returns when the Admin API is not enabled on a project and
+/// turns it into an instruction. Other failures yield `None`.
+pub fn api_disabled_error(status: StatusCode, body: &str, project_id: &str) -> Option<String> {
+    let disabled = status == StatusCode::FORBIDDEN
+        && ["SERVICE_DISABLED", "accessNotConfigured", "has not been used in project"]
+            .iter()
+            .any(|needle| body.contains(needle));
+    disabled.then(|| {
+        format!(
+            "Enable the Cloud SQL Admin API on project {}: \
+             gcloud services enable sqladmin.googleapis.com --project={}",
+            project_id, project_id
+        )
+    })
+}
+
+/// How much of an unparseable response body is quoted in the error.
+const BODY_SNIPPET_CHARS: usize = 200;
+
+/// Parses a JSON response body. If the body is not the expected JSON (for example an HTML
+/// error page from a proxy), the error names the HTTP status and quotes the start of it.
+pub fn parse_response_body<T: DeserializeOwned>(
+    status: StatusCode,
+    body: &str,
+    what: &str,
+) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| {
+        let trimmed = body.trim();
+        let mut snippet: String = trimmed.chars().take(BODY_SNIPPET_CHARS).collect();
+        if trimmed.chars().count() > BODY_SNIPPET_CHARS {
+            snippet.push('…');
+        }
+        anyhow!(
+            "Unexpected {} response (HTTP {}): {}. Body: {}",
+            what,
+            status,
+            e,
+            snippet
+        )
+    })
+}
+
+/// Parses one line of `gcloud sql instances list --format=value(...)` output. gcloud may
+/// drop the tabs of empty trailing fields, so missing fields are read as empty rather than
+/// discarding the instance. Lines without a name yield `None`.
+pub fn parse_instance_line(line: &str) -> Option<SqlInstance> {
+    let mut parts = line.split('\t').map(str::trim);
+    let name = parts.next().filter(|name| !name.is_empty())?;
+    let mut next_field = || parts.next().unwrap_or_default().to_string();
+    Some(SqlInstance {
+        name: name.to_string(),
+        database_version: next_field(),
+        region: next_field(),
+        tier: next_field(),
+        instance_type: next_field(),
+    })
+}
+
+fn proxy_from_env(name: &str) -> Option<String> {
+    std::env::var(name)
+        .or_else(|_| std::env::var(name.to_lowercase()))
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+pub fn operation_url(endpoint: &str, project_id: &str, operation_id: &str) -> String {
+    format!(
+        "{}/projects/{}/operations/{}",
+        endpoint, project_id, operation_id
+    )
+}
+
+pub fn restore_backup_url(
+    endpoint: &str,
+    target_project: &str,
+    target_instance: &str,
+) -> String {
+    format!(
+        "{}/projects/{}/instances/{}/restoreBackup",
+        endpoint, target_project, target_instance
+    )
+}
+
+pub fn backup_runs_url(endpoint: &str, project_id: &str, instance_id: &str) -> String {
+    format!(
+        "{}/projects/{}/instances/{}/backupRuns",
+        endpoint, project_id, instance_id
+    )
+}
+
+/// The `gcloud` command equivalent to the restore described by `config`.
+pub fn gcloud_restore_command(config: &RestoreConfig) -> String {
+    format!(
+        "gcloud sql backups restore {} --restore-instance={} --project={} \
+         --backup-instance={} --backup-project={}",
+        shell_quote(&config.backup_id),
+        shell_quote(&config.target_instance),
+        shell_quote(&config.target_project),
+        shell_quote(&config.source_instance),
+        shell_quote(&config.source_project)
+    )
+}
+
+/// The `gcloud` command equivalent to the on-demand backup described by `config`.
+pub fn gcloud_backup_command(config: &CreateBackupConfig) -> String {
+    let mut command = format!(
+        "gcloud sql backups create --instance={} --project={} --description={}",
+        shell_quote(&config.instance),
+        shell_quote(&config.project),
+        shell_quote(&config.description)
+    );
+    if let Some(location) = &config.location {
+        command.push_str(&format!(" --location={}", shell_quote(location)));
+    }
+    command
+}
+
+/// Quotes `value` for a POSIX shell unless it only has characters that need no quoting.
+fn shell_quote(value: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_.:/@=,".contains(c);
+    if !value.is_empty() && value.chars().all(plain) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Picks the account to use from `gcloud auth list --filter=status:ACTIVE` output, one
+/// account per line. With `requested`, it must be one of them; otherwise the first is used.
+pub fn pick_active_account(output: &str, requested: Option<&str>) -> Result<String> {
+    let accounts: Vec<&str> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    match (requested, accounts.first()) {
+        (_, None) => Err(FailureKind::Prerequisites.error("Not authenticated with gcloud")),
+        (Some(requested), Some(_)) if accounts.contains(&requested) => Ok(requested.to_string()),
+        (Some(requested), Some(_)) => Err(FailureKind::Prerequisites.error(format!(
+            "Account {} is not active in gcloud (active: {}). Run `gcloud auth login {}`.",
+            requested,
+            accounts.join(", "),
+            requested
+        ))),
+        (None, Some(first)) => Ok(first.to_string()),
+    }
+}
+
 #[mockall::automock]
 #[async_trait]
 pub trait GcpClientTrait: Send + Sync {
     async fn check_prerequisites(&self) -> Result<String>;
     async fn list_sql_instances(&self, project_id: &str) -> Result<Vec<SqlInstance>>;
     async fn list_backups(&self, project_id: &str, instance_id: &str) -> Result<Vec<Backup>>;
+    /// Backups of every instance in the project, paired with the owning instance name.
+    async fn list_all_backups(&self, project_id: &str) -> Result<Vec<(String, Backup)>>;
+    /// The instance's current state and activation policy.
+    async fn describe_instance(
+        &self,
+        project_id: &str,
+        instance_id: &str,
+    ) -> Result<InstanceStatus>;
     async fn get_operation_status(&self, project_id: &str, operation_id: &str) -> Result<Operation>;
     async fn restore_backup(
         &self,
@@ -23,30 +356,187 @@ pub trait GcpClientTrait: Send + Sync {
         target_instance: &str,
     ) -> Result<String>;
     async fn create_backup(&self, backup_config: &CreateBackupConfig) -> Result<String>;
+    /// Discards any cached access token and fetches a new one from gcloud.
+    async fn refresh_access_token(&self) -> Result<()>;
+}
+
+/// Where access tokens for the REST calls come from: gcloud in normal use, something
+/// fixed in tests.
+#[async_trait]
+pub trait TokenSource: Send + Sync {
+    /// A new token and how long it stays valid. `gcloud` runs commands as the client's
+    /// account.
+    async fn fetch_token(&self, gcloud: &Gcloud) -> Result<(String, Duration)>;
+}
+
+/// Fetches tokens from the gcloud CLI.
+pub struct GcloudTokenSource;
+
+#[async_trait]
+impl TokenSource for GcloudTokenSource {
+    /// Asks gcloud for a token together with its expiry. `config-helper` reports the
+    /// expiry for both user and service-account credentials; if it is unavailable, fall
+    /// back to `print-access-token` and assume the default lifetime.
+    async fn fetch_token(&self, gcloud: &Gcloud) -> Result<(String, Duration)> {
+        let output = gcloud
+            .run_with_timeout(&["config", "config-helper", "--format=json"], TOKEN_COMMAND_TIMEOUT)
+            .await?;
+
+        if output.status.success() {
+            let json: Value = serde_json::from_slice(&output.stdout)?;
+            let credential = &json["credential"];
+            if let Some(token) = credential["access_token"].as_str() {
+                let lifetime = credential["token_expiry"]
+                    .as_str()
+                    .and_then(|expiry| DateTime::parse_from_rfc3339(expiry).ok())
+                    .and_then(|expiry| (expiry.with_timezone(&Utc) - Utc::now()).to_std().ok())
+                    .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+                return Ok((token.to_string(), lifetime));
+            }
+        }
+
+        let output = gcloud
+            .run_with_timeout(&["auth", "print-access-token"], TOKEN_COMMAND_TIMEOUT)
+            .await?;
+
+        if !output.status.success() {
+            return Err(gcloud_token_error(&String::from_utf8_lossy(&output.stderr)));
+        }
+
+        Ok((
+            String::from_utf8(output.stdout)?.trim().to_string(),
+            DEFAULT_TOKEN_LIFETIME,
+        ))
+    }
+}
+
+struct CachedToken {
+    token: String,
+    fetched_at: Instant,
+    lifetime: Duration,
+}
+
+impl CachedToken {
+    /// Whether the token is not yet due for a refresh. Short-lived tokens are refreshed
+    /// halfway through instead, so the margin never swallows their whole lifetime.
+    fn is_fresh(&self) -> bool {
+        let margin = TOKEN_REFRESH_MARGIN.min(self.lifetime / 2);
+        self.fetched_at.elapsed() + margin < self.lifetime
+    }
 }
 
 pub struct GcpClient {
     client: Client,
+    proxy: Option<String>,
+    /// Runs gcloud as the `--account` account; without one, as the first active account.
+    gcloud: Gcloud,
+    /// Base URL of the Admin API, without a trailing slash.
+    endpoint: String,
+    token_source: Box<dyn TokenSource>,
+    token: Mutex<Option<CachedToken>>,
 }
 
 impl GcpClient {
-    pub fn new() -> Self {
-        Self {
-            client: Client::new(),
+    /// Builds a client that uses the proxy from the environment, if any. Fails if that
+    /// proxy is malformed rather than connecting directly.
+    pub fn new() -> Result<Self> {
+        Self::with_proxy(None)
+    }
+
+    /// Builds a client that sends REST calls through `proxy`. Without an explicit proxy,
+    /// `HTTPS_PROXY`/`HTTP_PROXY` (and `NO_PROXY`) are read from the environment.
+    pub fn with_proxy(proxy: Option<&str>) -> Result<Self> {
+        let mut builder = Client::builder().user_agent(USER_AGENT).no_proxy();
+        let mut active_proxy = None;
+
+        if let Some(url) = proxy {
+            let proxy =
+                Proxy::all(url).map_err(|e| anyhow!("Invalid proxy URL '{}': {}", url, e))?;
+            builder = builder.proxy(proxy.no_proxy(NoProxy::from_env()));
+            active_proxy = Some(url.to_string());
+        } else {
+            if let Some(url) = proxy_from_env("HTTPS_PROXY") {
+                let proxy = Proxy::https(&url)
+                    .map_err(|e| anyhow!("Invalid HTTPS_PROXY '{}': {}", url, e))?;
+                builder = builder.proxy(proxy.no_proxy(NoProxy::from_env()));
+                active_proxy = Some(url);
+            }
+            if let Some(url) = proxy_from_env("HTTP_PROXY") {
+                let proxy = Proxy::http(&url)
+                    .map_err(|e| anyhow!("Invalid HTTP_PROXY '{}': {}", url, e))?;
+                builder = builder.proxy(proxy.no_proxy(NoProxy::from_env()));
+                active_proxy.get_or_insert(url);
+            }
         }
+
+        Ok(Self {
+            client: builder.build()?,
+            proxy: active_proxy,
+            gcloud: Gcloud::default(),
+            endpoint: DEFAULT_API_ENDPOINT.to_string(),
+            token_source: Box::new(GcloudTokenSource),
+            token: Mutex::new(None),
+        })
     }
 
-    async fn get_access_token(&self) -> Result<String> {
-        let output = AsyncCommand::new("gcloud")
-            .args(&["auth", "print-access-token"])
-            .output()
-            .await?;
+    /// Sends Admin API requests to `endpoint` instead of [`DEFAULT_API_ENDPOINT`].
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_string();
+        self
+    }
 
-        if !output.status.success() {
-            return Err(anyhow!("Failed to get access token"));
+    /// Takes access tokens from `source` instead of gcloud.
+    pub fn with_token_source(mut self, source: impl TokenSource + 'static) -> Self {
+        self.token_source = Box::new(source);
+        self
+    }
+
+    /// Requires `account` to be among the active gcloud accounts at startup, and runs
+    /// every gcloud command, the access token included, as that account.
+    pub fn with_account(mut self, account: Option<String>) -> Self {
+        self.gcloud = Gcloud::new(account);
+        self
+    }
+
+    /// Runs `program` instead of `gcloud`, e.g. a stand-in script in tests.
+    pub fn with_gcloud_program(mut self, program: &str) -> Self {
+        self.gcloud = self.gcloud.with_program(program);
+        self
+    }
+
+    /// Reports connection failures through a proxy separately, since they otherwise look
+    /// like the Cloud SQL API itself being unreachable.
+    fn request_error(&self, error: reqwest::Error) -> anyhow::Error {
+        match &self.proxy {
+            Some(proxy) if error.is_connect() => {
+                anyhow!("Could not connect through proxy {}: {}", proxy, error)
+            }
+            _ => error.into(),
         }
+    }
 
-        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+    /// Returns the cached access token, fetching a new one when it is missing or close
+    /// to expiry.
+    async fn get_access_token(&self) -> Result<String> {
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| token.is_fresh()) {
+            return Ok(token.token.clone());
+        }
+
+        let token = self.fetch_access_token().await?;
+        let value = token.token.clone();
+        *cached = Some(token);
+        Ok(value)
+    }
+
+    async fn fetch_access_token(&self) -> Result<CachedToken> {
+        let fetched_at = Instant::now();
+        let (token, lifetime) = self.token_source.fetch_token(&self.gcloud).await?;
+        Ok(CachedToken {
+            token,
+            fetched_at,
+            lifetime,
+        })
     }
 }
 
@@ -54,78 +544,59 @@ impl GcpClient {
 impl GcpClientTrait for GcpClient {
     async fn check_prerequisites(&self) -> Result<String> {
         // Check if gcloud is installed
-        let output = AsyncCommand::new("which")
-            .arg("gcloud")
-            .output()
-            .await?;
-
-        if !output.status.success() {
-            return Err(anyhow!("gcloud CLI is not installed"));
+        let installed = match self.gcloud.run(&["--version"]).await {
+            Ok(output) => output.status.success(),
+            Err(e) if is_not_found(&e) => false,
+            Err(e) => return Err(e),
+        };
+
+        if !installed {
+            return Err(FailureKind::Prerequisites.error("gcloud CLI is not installed"));
         }
 
         // Check authentication
-        let output = AsyncCommand::new("gcloud")
-            .args(&["auth", "list", "--filter=status:ACTIVE", "--format=value(account)"])
-            .output()
-            .await?;
+        let output = self.gcloud.active_accounts().await?;
 
-        if !output.status.success() || output.stdout.is_empty() {
-            return Err(anyhow!("Not authenticated with gcloud"));
+        if !output.status.success() {
+            return Err(FailureKind::Prerequisites.error("Not authenticated with gcloud"));
         }
 
-        let account = String::from_utf8(output.stdout)?.trim().to_string();
-        Ok(account)
+        pick_active_account(&String::from_utf8(output.stdout)?, self.gcloud.account())
     }
 
     async fn list_sql_instances(&self, project_id: &str) -> Result<Vec<SqlInstance>> {
-        let output = AsyncCommand::new("gcloud")
-            .args(&[
-                "sql",
-                "instances",
-                "list",
-                &format!("--project={}", project_id),
-                "--format=value(name,databaseVersion,region,settings.tier)",
-            ])
-            .output()
-            .await?;
+        let output = self.gcloud.run(&[
+            "sql",
+            "instances",
+            "list",
+            &format!("--project={}", project_id),
+            "--format=value(name,databaseVersion,region,settings.tier,instanceType)",
+        ])
+        .await?;
 
         if !output.status.success() {
-            return Err(anyhow!("Failed to list SQL instances"));
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(gcloud_error(&format!("Failed to list SQL instances: {}", stderr.trim())));
         }
 
         let stdout = String::from_utf8(output.stdout)?;
-        let mut instances = Vec::new();
-
-        for line in stdout.lines() {
-            let parts: Vec<&str> = line.split('\t').collect();
-            if parts.len() >= 4 {
-                instances.push(SqlInstance {
-                    name: parts[0].to_string(),
-                    database_version: parts[1].to_string(),
-                    region: parts[2].to_string(),
-                    tier: parts[3].to_string(),
-                });
-            }
-        }
-
-        Ok(instances)
+        Ok(stdout.lines().filter_map(parse_instance_line).collect())
     }
 
     async fn list_backups(&self, project_id: &str, instance_id: &str) -> Result<Vec<Backup>> {
-        let output = AsyncCommand::new("gcloud")
-            .args(&[
-                "sql",
-                "backups",
-                "list",
-                &format!("--instance={}", instance_id),
-                &format!("--project={}", project_id),
-                "--format=value(id,startTime,type,status)",
-            ])
-            .output()
-            .await?;
+        let output = self.gcloud.run(&[
+            "sql",
+            "backups",
+            "list",
+            &format!("--instance={}", instance_id),
+            &format!("--project={}", project_id),
+            "--format=value(id,startTime,type,status,description)",
+        ])
+        .await?;
 
         if !output.status.success() {
-            return Ok(Vec::new()); // Return empty if no backups or error
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(gcloud_error(&format!("Failed to list backups: {}", stderr.trim())));
         }
 
         let stdout = String::from_utf8(output.stdout)?;
@@ -145,6 +616,7 @@ impl GcpClientTrait for GcpClient {
                     start_time,
                     backup_type: parts[2].to_string(),
                     status: parts[3].to_string(),
+                    description: parts.get(4).unwrap_or(&"").to_string(),
                 });
             }
         }
@@ -152,46 +624,84 @@ impl GcpClientTrait for GcpClient {
         Ok(backups)
     }
 
+    /// Instances whose backups cannot be listed are skipped; an error is returned only
+    /// if every instance fails.
+    async fn list_all_backups(&self, project_id: &str) -> Result<Vec<(String, Backup)>> {
+        let instances = self.list_sql_instances(project_id).await?;
+        let mut all_backups = Vec::new();
+        let mut last_error = None;
+
+        for instance in &instances {
+            match self.list_backups(project_id, &instance.name).await {
+                Ok(backups) => all_backups.extend(
+                    backups
+                        .into_iter()
+                        .map(|backup| (instance.name.clone(), backup)),
+                ),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match last_error {
+            Some(e) if all_backups.is_empty() => {
+                Err(anyhow!("Failed to list backups for any instance: {}", e))
+            }
+            _ => Ok(all_backups),
+        }
+    }
+
+    async fn describe_instance(
+        &self,
+        project_id: &str,
+        instance_id: &str,
+    ) -> Result<InstanceStatus> {
+        let output = self.gcloud.run(&[
+            "sql",
+            "instances",
+            "describe",
+            instance_id,
+            &format!("--project={}", project_id),
+            &format!("--format={}", InstanceStatus::GCLOUD_FORMAT),
+        ])
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(gcloud_error(&format!("Failed to describe instance: {}", stderr.trim())));
+        }
+
+        Ok(InstanceStatus::parse(&String::from_utf8(output.stdout)?))
+    }
+
     async fn get_operation_status(
         &self,
         project_id: &str,
         operation_id: &str,
     ) -> Result<Operation> {
         let token = self.get_access_token().await?;
-        let url = format!(
-            "https://sqladmin.googleapis.com/v1/projects/{}/operations/{}",
-            project_id, operation_id
-        );
+        let url = operation_url(&self.endpoint, project_id, operation_id);
 
         let response = self
             .client
             .get(&url)
             .bearer_auth(&token)
             .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(anyhow!(
-                "Failed to get operation status: {}",
-                response.status()
-            ));
+            .await
+            .map_err(|e| self.request_error(e))?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            if let Some(message) = api_disabled_error(status, &body, project_id) {
+                return Err(FailureKind::Prerequisites.error(message));
+            }
+            return Err(FailureKind::from_status(status)
+                .error(format!("Failed to get operation status: {}", status)));
         }
 
-        let api_response: GcpApiResponse = response.json().await?;
-
-        Ok(Operation {
-            id: operation_id.to_string(),
-            operation_type: api_response
-                .operation_type
-                .unwrap_or_else(|| "Unknown".to_string()),
-            status: api_response.status.unwrap_or_else(|| "Unknown".to_string()),
-            target_id: api_response
-                .target_id
-                .unwrap_or_else(|| "Unknown".to_string()),
-            start_time: api_response.start_time.and_then(|s| s.parse().ok()),
-            end_time: api_response.end_time.and_then(|s| s.parse().ok()),
-            error_message: api_response.error.map(|e| e.message),
-        })
+        let api_response: GcpApiResponse = parse_response_body(status, &body, "operation status")?;
+
+        Ok((operation_id.to_string(), api_response).into())
     }
 
     async fn restore_backup(
@@ -201,10 +711,7 @@ impl GcpClientTrait for GcpClient {
         target_instance: &str,
     ) -> Result<String> {
         let token = self.get_access_token().await?;
-        let url = format!(
-            "https://sqladmin.googleapis.com/v1/projects/{}/instances/{}/restoreBackup",
-            target_project, target_instance
-        );
+        let url = restore_backup_url(&self.endpoint, target_project, target_instance);
 
         let response = self
             .client
@@ -212,18 +719,27 @@ impl GcpClientTrait for GcpClient {
             .bearer_auth(&token)
             .json(restore_request)
             .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Restore operation failed: {}", error_text));
+            .await
+            .map_err(|e| self.request_error(e))?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            if let Some(message) = api_disabled_error(status, &body, target_project) {
+                return Err(FailureKind::Prerequisites.error(message));
+            }
+            let message = format!("Restore operation failed: {}", body);
+            if is_instance_busy_error(status, &body) {
+                return Err(instance_busy_error(message));
+            }
+            return Err(FailureKind::from_status(status).error(message));
         }
 
-        let result: Value = response.json().await?;
+        let result: Value = parse_response_body(status, &body, "restore")?;
 
         if let Some(name) = result.get("name").and_then(|n| n.as_str()) {
             // Extract operation ID from the full operation name
-            let operation_id = name.split('/').last().unwrap_or(name);
+            let operation_id = name.rsplit('/').next().unwrap_or(name);
             Ok(operation_id.to_string())
         } else {
             Err(anyhow!("No operation ID returned from restore request"))
@@ -232,14 +748,12 @@ impl GcpClientTrait for GcpClient {
 
     async fn create_backup(&self, backup_config: &CreateBackupConfig) -> Result<String> {
         let token = self.get_access_token().await?;
-        let url = format!(
-            "https://sqladmin.googleapis.com/v1/projects/{}/instances/{}/backupRuns",
-            backup_config.project, backup_config.instance
+        let url = backup_runs_url(
+            &self.endpoint,
+            &backup_config.project,
+            &backup_config.instance,
         );
-
-        let request_body = serde_json::json!({
-            "description": &backup_config.name
-        });
+        let request_body = CreateBackupRequest::from(backup_config);
 
         let response = self
             .client
@@ -247,17 +761,23 @@ impl GcpClientTrait for GcpClient {
             .bearer_auth(&token)
             .json(&request_body)
             .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Create backup operation failed: {}", error_text));
+            .await
+            .map_err(|e| self.request_error(e))?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            if let Some(message) = api_disabled_error(status, &body, &backup_config.project) {
+                return Err(FailureKind::Prerequisites.error(message));
+            }
+            return Err(FailureKind::from_status(status)
+                .error(format!("Create backup operation failed: {}", body)));
         }
 
-        let result: Value = response.json().await?;
+        let result: Value = parse_response_body(status, &body, "create backup")?;
 
         if let Some(name) = result.get("name").and_then(|n| n.as_str()) {
-            let operation_id = name.split('/').last().unwrap_or(name);
+            let operation_id = name.rsplit('/').next().unwrap_or(name);
             Ok(operation_id.to_string())
         } else {
             Err(anyhow!(
@@ -265,4 +785,10 @@ impl GcpClientTrait for GcpClient {
             ))
         }
     }
+
+    async fn refresh_access_token(&self) -> Result<()> {
+        let token = self.fetch_access_token().await?;
+        *self.token.lock().await = Some(token);
+        Ok(())
+    }
 }
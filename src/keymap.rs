@@ -0,0 +1,330 @@
+use anyhow::{anyhow, Context, Result};
+use crossterm::event::KeyCode;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+
+/// Logical actions that can be rebound in `keymap.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Select,
+    Back,
+    Help,
+    Refresh,
+    New,
+    Manual,
+    Quit,
+    // Keys that only act on some steps. They may share a key with each other, as long
+    // as the steps differ, and are only checked when no global action matches.
+    AccessToken,
+    PastOperations,
+    PausePolling,
+    CompactFooter,
+    ProjectBackups,
+    Sort,
+    SameAsSource,
+    Mark,
+    Compare,
+    DateFilter,
+    Retention,
+    Labels,
+    Location,
+    Details,
+    Databases,
+    GcloudCommand,
+}
+
+impl Action {
+    /// The global actions, in the order bindings are matched, so that overlapping keys
+    /// resolve predictably. Step actions are checked with [`KeyMap::binds`] instead.
+    pub const ALL: [Action; 9] = [
+        Action::Quit,
+        Action::Back,
+        Action::Help,
+        Action::Up,
+        Action::Down,
+        Action::Select,
+        Action::Manual,
+        Action::Refresh,
+        Action::New,
+    ];
+}
+
+/// Maps each logical action to the keys that trigger it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    pub up: Vec<KeyCode>,
+    pub down: Vec<KeyCode>,
+    pub select: Vec<KeyCode>,
+    pub back: Vec<KeyCode>,
+    pub help: Vec<KeyCode>,
+    pub refresh: Vec<KeyCode>,
+    pub new: Vec<KeyCode>,
+    pub manual: Vec<KeyCode>,
+    pub quit: Vec<KeyCode>,
+    pub access_token: Vec<KeyCode>,
+    pub past_operations: Vec<KeyCode>,
+    pub pause_polling: Vec<KeyCode>,
+    pub compact_footer: Vec<KeyCode>,
+    pub project_backups: Vec<KeyCode>,
+    pub sort: Vec<KeyCode>,
+    pub same_as_source: Vec<KeyCode>,
+    pub mark: Vec<KeyCode>,
+    pub compare: Vec<KeyCode>,
+    pub date_filter: Vec<KeyCode>,
+    pub retention: Vec<KeyCode>,
+    pub labels: Vec<KeyCode>,
+    pub location: Vec<KeyCode>,
+    pub details: Vec<KeyCode>,
+    pub databases: Vec<KeyCode>,
+    pub gcloud_command: Vec<KeyCode>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            up: vec![KeyCode::Up],
+            down: vec![KeyCode::Down],
+            select: vec![KeyCode::Enter],
+            back: vec![KeyCode::Esc],
+            help: vec![KeyCode::Char('h')],
+            refresh: vec![KeyCode::Char('r')],
+            new: vec![KeyCode::Char('n')],
+            manual: vec![KeyCode::Char('m')],
+            quit: vec![KeyCode::Char('q')],
+            access_token: vec![KeyCode::Char('a')],
+            past_operations: vec![KeyCode::Char('o')],
+            pause_polling: vec![KeyCode::Char('p')],
+            compact_footer: vec![KeyCode::Char('v')],
+            project_backups: vec![KeyCode::Char('p')],
+            sort: vec![KeyCode::Char('s')],
+            same_as_source: vec![KeyCode::Char('s')],
+            mark: vec![KeyCode::Char(' ')],
+            compare: vec![KeyCode::Char('c')],
+            date_filter: vec![KeyCode::Char('f')],
+            retention: vec![KeyCode::Char('R')],
+            labels: vec![KeyCode::Char('l')],
+            location: vec![KeyCode::Char('g')],
+            details: vec![KeyCode::Char('i')],
+            databases: vec![KeyCode::Char('d')],
+            gcloud_command: vec![KeyCode::Char('c')],
+        }
+    }
+}
+
+/// A binding in `keymap.toml`: either one key or a list of keys.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The on-disk format. Actions that are left out keep their default keys.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct KeyMapFile {
+    up: Option<KeyList>,
+    down: Option<KeyList>,
+    select: Option<KeyList>,
+    back: Option<KeyList>,
+    help: Option<KeyList>,
+    refresh: Option<KeyList>,
+    new: Option<KeyList>,
+    manual: Option<KeyList>,
+    quit: Option<KeyList>,
+    access_token: Option<KeyList>,
+    past_operations: Option<KeyList>,
+    pause_polling: Option<KeyList>,
+    compact_footer: Option<KeyList>,
+    project_backups: Option<KeyList>,
+    sort: Option<KeyList>,
+    same_as_source: Option<KeyList>,
+    mark: Option<KeyList>,
+    compare: Option<KeyList>,
+    date_filter: Option<KeyList>,
+    retention: Option<KeyList>,
+    labels: Option<KeyList>,
+    location: Option<KeyList>,
+    details: Option<KeyList>,
+    databases: Option<KeyList>,
+    gcloud_command: Option<KeyList>,
+}
+
+impl KeyMap {
+    /// `keymap.toml` in the [config directory](config::config_dir).
+    pub fn default_path() -> Option<PathBuf> {
+        config::config_dir().map(|dir| dir.join("keymap.toml"))
+    }
+
+    /// Loads overrides from `path`, or from the default location if none is given.
+    /// A missing default file is not an error; a missing explicit file is.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        if !required && !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read keymap {}", path.display()))?;
+        Self::from_toml(&contents).with_context(|| format!("Invalid keymap {}", path.display()))
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let file: KeyMapFile = toml::from_str(contents)?;
+        let mut keymap = Self::default();
+        let overrides = [
+            (file.up, &mut keymap.up),
+            (file.down, &mut keymap.down),
+            (file.select, &mut keymap.select),
+            (file.back, &mut keymap.back),
+            (file.help, &mut keymap.help),
+            (file.refresh, &mut keymap.refresh),
+            (file.new, &mut keymap.new),
+            (file.manual, &mut keymap.manual),
+            (file.quit, &mut keymap.quit),
+            (file.access_token, &mut keymap.access_token),
+            (file.past_operations, &mut keymap.past_operations),
+            (file.pause_polling, &mut keymap.pause_polling),
+            (file.compact_footer, &mut keymap.compact_footer),
+            (file.project_backups, &mut keymap.project_backups),
+            (file.sort, &mut keymap.sort),
+            (file.same_as_source, &mut keymap.same_as_source),
+            (file.mark, &mut keymap.mark),
+            (file.compare, &mut keymap.compare),
+            (file.date_filter, &mut keymap.date_filter),
+            (file.retention, &mut keymap.retention),
+            (file.labels, &mut keymap.labels),
+            (file.location, &mut keymap.location),
+            (file.details, &mut keymap.details),
+            (file.databases, &mut keymap.databases),
+            (file.gcloud_command, &mut keymap.gcloud_command),
+        ];
+        for (list, keys) in overrides {
+            if let Some(list) = list {
+                let names = match list {
+                    KeyList::One(name) => vec![name],
+                    KeyList::Many(names) => names,
+                };
+                *keys = names
+                    .iter()
+                    .map(|name| parse_key(name))
+                    .collect::<Result<_>>()?;
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        match action {
+            Action::Up => &self.up,
+            Action::Down => &self.down,
+            Action::Select => &self.select,
+            Action::Back => &self.back,
+            Action::Help => &self.help,
+            Action::Refresh => &self.refresh,
+            Action::New => &self.new,
+            Action::Manual => &self.manual,
+            Action::Quit => &self.quit,
+            Action::AccessToken => &self.access_token,
+            Action::PastOperations => &self.past_operations,
+            Action::PausePolling => &self.pause_polling,
+            Action::CompactFooter => &self.compact_footer,
+            Action::ProjectBackups => &self.project_backups,
+            Action::Sort => &self.sort,
+            Action::SameAsSource => &self.same_as_source,
+            Action::Mark => &self.mark,
+            Action::Compare => &self.compare,
+            Action::DateFilter => &self.date_filter,
+            Action::Retention => &self.retention,
+            Action::Labels => &self.labels,
+            Action::Location => &self.location,
+            Action::Details => &self.details,
+            Action::Databases => &self.databases,
+            Action::GcloudCommand => &self.gcloud_command,
+        }
+    }
+
+    /// The global action bound to `key`, if any.
+    pub fn action_for(&self, key: KeyCode) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|action| self.keys(*action).contains(&key))
+    }
+
+    /// Whether `key` triggers `action`, for step actions that only apply on some steps.
+    pub fn binds(&self, action: Action, key: KeyCode) -> bool {
+        self.keys(action).contains(&key)
+    }
+
+    /// Whether `a` and `b` have a key in common, so they cannot both apply on one step.
+    pub fn overlaps(&self, a: Action, b: Action) -> bool {
+        self.keys(a).iter().any(|key| self.keys(b).contains(key))
+    }
+
+    /// The keys bound to `action` as shown in the footer and help, e.g. `↑/k`.
+    pub fn label(&self, action: Action) -> String {
+        self.keys(action)
+            .iter()
+            .map(|key| key_name(*key))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn parse_key(name: &str) -> Result<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+    match name.to_lowercase().as_str() {
+        "up" => Ok(KeyCode::Up),
+        "down" => Ok(KeyCode::Down),
+        "left" => Ok(KeyCode::Left),
+        "right" => Ok(KeyCode::Right),
+        "enter" | "return" => Ok(KeyCode::Enter),
+        "esc" | "escape" => Ok(KeyCode::Esc),
+        "tab" => Ok(KeyCode::Tab),
+        "backspace" => Ok(KeyCode::Backspace),
+        "space" => Ok(KeyCode::Char(' ')),
+        "home" => Ok(KeyCode::Home),
+        "end" => Ok(KeyCode::End),
+        "pageup" => Ok(KeyCode::PageUp),
+        "pagedown" => Ok(KeyCode::PageDown),
+        other => other
+            .strip_prefix('f')
+            .and_then(|n| n.parse::<u8>().ok())
+            .map(KeyCode::F)
+            .ok_or_else(|| anyhow!("Unknown key '{}'", name)),
+    }
+}
+
+fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        other => format!("{:?}", other),
+    }
+}
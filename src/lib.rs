@@ -1,5 +1,11 @@
 pub mod app;
+pub mod config;
+pub mod failure;
 pub mod gcp;
+pub mod keymap;
+pub mod operation_store;
+pub mod selectable_list;
+pub mod session;
 pub mod state;
 pub mod ui;
 pub mod types;
\ No newline at end of file
@@ -1,40 +1,736 @@
-use anyhow::Result;
-use clap::{Arg, Command};
+use anyhow::{anyhow, Context, Result};
+use chrono::{Local, Utc};
+use clap::{Arg, ArgMatches, Command};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use gcp_snap_crab::{
-    app::App,
-    gcp::GcpClient,
+    app::{App, WATCH_INTERVAL},
+    failure::{FailureKind, EXIT_CODES_HELP},
+    gcp::{GcpClient, GcpClientTrait, DEFAULT_API_ENDPOINT},
+    keymap::KeyMap,
+    operation_store::{self, OperationStore, TrackedOperation},
+    selectable_list::SelectableList,
+    session::SavedSession,
+    state::{create_backup_flow::CreateBackupFlow, watch_flow::WatchFlow},
+    types::{has_backup_on, CreateBackupConfig, DisplayZone, OperationPhase, ProtectPattern},
     ui::run_app,
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
+    if let Err(error) = run().await {
+        eprintln!("Error: {:?}", error);
+        std::process::exit(FailureKind::exit_code_for(&error));
+    }
+}
+
+async fn run() -> Result<()> {
     let matches = Command::new("GCP SQL Backup Restore")
-        .version("2.0.0")
+        .version(env!("CARGO_PKG_VERSION"))
         .about("Interactive GCP SQL Instance Backup Restore Tool")
+        .after_help(EXIT_CODES_HELP)
         .arg(
             Arg::new("dry-run")
                 .long("dry-run")
                 .help("Run in dry-run mode (simulate operations without executing)")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("engine")
+                .long("engine")
+                .value_name("ENGINE")
+                .help("Only show instances running the given database engine")
+                .value_parser(["MYSQL", "POSTGRES", "SQLSERVER"])
+                .ignore_case(true),
+        )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("URL")
+                .help("Send Cloud SQL Admin API requests through this proxy")
+                .long_help(
+                    "Send Cloud SQL Admin API requests through this proxy. \
+                     Overrides HTTPS_PROXY/HTTP_PROXY; NO_PROXY is still honored.",
+                ),
+        )
+        .arg(
+            Arg::new("api-endpoint")
+                .long("api-endpoint")
+                .value_name("URL")
+                .help("Send Cloud SQL Admin API requests to this base URL")
+                .long_help(
+                    "Send Cloud SQL Admin API requests to this base URL instead of \
+                     https://sqladmin.googleapis.com/v1, e.g. an emulator or mock server. \
+                     Defaults to SQLADMIN_ENDPOINT if it is set.",
+                ),
+        )
+        .arg(
+            Arg::new("account")
+                .long("account")
+                .value_name("EMAIL")
+                .help("Run every gcloud command as this active gcloud account"),
+        )
+        .arg(
+            Arg::new("no-restore")
+                .long("no-restore")
+                .help("Safe mode: disable restores and only allow creating backups")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .help("Use the single-column layout even on wide terminals")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("reduce-motion")
+                .long("reduce-motion")
+                .visible_alias("no-animations")
+                .help("Show warnings without blinking or flashing colors")
+                .long_help(
+                    "Show warnings without blinking or flashing colors. \
+                     Also enabled when NO_COLOR is set.",
+                )
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("wrap-selection")
+                .long("wrap-selection")
+                .help("Wrap around from the top of a list to the bottom and back")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("local-time")
+                .long("local-time")
+                .help("Show backup and operation times in the local time zone instead of UTC")
+                .long_help(
+                    "Show backup and operation times in the local time zone, as set by TZ or \
+                     the system, instead of UTC. Titles and column headers say which zone is \
+                     shown. Saved history and --export-history stay in UTC.",
+                )
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("auto-refresh")
+                .long("auto-refresh")
+                .value_name("SECS")
+                .help("Reload the instance or backup list every SECS seconds while idle")
+                .value_parser(clap::value_parser!(u64).range(1..)),
+        )
+        .arg(
+            Arg::new("timeout-prereq")
+                .long("timeout-prereq")
+                .value_name("SECS")
+                .help("Give up checking gcloud and its login at startup after SECS seconds (30)")
+                .value_parser(clap::value_parser!(u64).range(1..)),
+        )
+        .arg(
+            Arg::new("protect-pattern")
+                .long("protect-pattern")
+                .value_name("REGEX")
+                .default_value(ProtectPattern::DEFAULT)
+                .help("Restore targets matching this need the instance name typed to confirm")
+                .long_help(
+                    "Restore targets whose project or instance name matches this pattern \
+                     need the target instance name typed out, and a short wait, to confirm. \
+                     The pattern is a regular expression, matched case-insensitively \
+                     anywhere in the name. An empty pattern protects nothing.",
+                ),
+        )
+        .arg(
+            Arg::new("keymap")
+                .long("keymap")
+                .value_name("FILE")
+                .help("Load key bindings from this TOML file")
+                .long_help(
+                    "Load key bindings from this TOML file. Defaults to \
+                     $XDG_CONFIG_HOME/gcp-snap-crab/keymap.toml (or ~/.config/...) if it exists.",
+                ),
+        )
+        .arg(
+            Arg::new("list-instances")
+                .long("list-instances")
+                .help("Print the SQL instances of --project and exit")
+                .action(clap::ArgAction::SetTrue)
+                .requires("project")
+                .conflicts_with("list-backups"),
+        )
+        .arg(
+            Arg::new("list-backups")
+                .long("list-backups")
+                .help("Print the backups of --instance in --project and exit")
+                .action(clap::ArgAction::SetTrue)
+                .requires_all(["project", "instance"]),
+        )
+        .arg(
+            Arg::new("create-backup")
+                .long("create-backup")
+                .help("Create an on-demand backup of --instance in --project and exit")
+                .action(clap::ArgAction::SetTrue)
+                .requires_all(["project", "instance"])
+                .conflicts_with_all(["list-instances", "list-backups"]),
+        )
+        .arg(
+            Arg::new("backup-name")
+                .long("backup-name")
+                .value_name("NAME")
+                .help("Name for --create-backup; {date} is replaced with today's date")
+                .default_value("scheduled-{date}"),
+        )
+        .arg(
+            Arg::new("backup-only-if-none-today")
+                .long("backup-only-if-none-today")
+                .help("With --create-backup, skip if the instance already has a backup today")
+                .action(clap::ArgAction::SetTrue)
+                .requires("create-backup"),
+        )
+        .arg(
+            Arg::new("timezone")
+                .long("timezone")
+                .value_name("TZ")
+                .help(
+                    "Time zone that decides what \"today\" is for --backup-only-if-none-today \
+                     [default: local with --local-time, else utc]",
+                )
+                .value_parser(["utc", "local"])
+                .ignore_case(true),
+        )
+        .arg(
+            Arg::new("restore-latest")
+                .long("restore-latest")
+                .help("Restore the newest backup of --instance onto --target-instance and exit")
+                .action(clap::ArgAction::SetTrue)
+                .requires_all(["project", "instance", "target-project", "target-instance"])
+                .conflicts_with_all(["list-instances", "list-backups", "create-backup"]),
+        )
+        .arg(
+            Arg::new("export-inventory")
+                .long("export-inventory")
+                .value_name("FILE")
+                .help("Write a JSON inventory of every instance in --project to FILE and exit")
+                .long_help(
+                    "Write a JSON inventory of every instance in --project to FILE and exit: \
+                     name, database version, region, tier, state, disk size, high \
+                     availability and maintenance window. An instance that cannot be \
+                     described is listed with its error instead of aborting the export.",
+                )
+                .requires("project")
+                .conflicts_with_all([
+                    "list-instances",
+                    "list-backups",
+                    "create-backup",
+                    "restore-latest",
+                    "watch",
+                ]),
+        )
+        .arg(
+            Arg::new("watch")
+                .long("watch")
+                .value_names(["PROJECT", "OPERATION"])
+                .num_args(2)
+                .help("Follow an operation already running in PROJECT until it finishes and exit")
+                .long_help(
+                    "Follow an operation already running in PROJECT, such as one started by \
+                     someone else, on a screen showing its status, type, times and error \
+                     until it finishes. Failed status checks are retried. With --quiet, or \
+                     when stdout is not a terminal, it runs without the TUI instead: \
+                     printing the status whenever it changes and exiting once the operation \
+                     finishes, with 0 when it succeeded, otherwise with the exit code for a \
+                     failed operation.",
+                )
+                .conflicts_with_all([
+                    "list-instances",
+                    "list-backups",
+                    "create-backup",
+                    "restore-latest",
+                    "export-inventory",
+                ]),
+        )
+        .arg(
+            Arg::new("target-project")
+                .long("target-project")
+                .value_name("PROJECT")
+                .help("Project of the instance --restore-latest restores onto"),
+        )
+        .arg(
+            Arg::new("target-instance")
+                .long("target-instance")
+                .value_name("INSTANCE")
+                .help("Instance --restore-latest restores onto"),
+        )
+        .arg(
+            Arg::new("yes")
+                .long("yes")
+                .help("Do not ask for confirmation before --restore-latest overwrites the target")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("reason")
+                .long("reason")
+                .value_name("TEXT")
+                .help("Why --restore-latest is restoring; kept in the operation history"),
+        )
+        .arg(
+            Arg::new("require-reason")
+                .long("require-reason")
+                .help("Refuse to start a restore without a reason")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("mark-restores")
+                .long("mark-restores")
+                .help("After a restore finishes, back up the target with a note of the restore")
+                .long_help(
+                    "After a restore watched in the TUI finishes, create an on-demand backup \
+                     of the target whose description records the source backup, the source \
+                     instance and the gcloud account that ran it, so the restore shows up in \
+                     the Console's backup list.",
+                )
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("save-session")
+                .long("save-session")
+                .value_name("FILE")
+                .help("On exit, save the restore selections made so far to this file"),
+        )
+        .arg(
+            Arg::new("resume")
+                .long("resume")
+                .value_name("FILE")
+                .help("Start from the restore selections saved with --save-session")
+                .conflicts_with("project"),
+        )
+        .arg(
+            Arg::new("export-history")
+                .long("export-history")
+                .help("Print the history of operations started by this tool and exit")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("audit-format")
+                .long("audit-format")
+                .value_name("FORMAT")
+                .help("Format for --export-history")
+                .value_parser(["json", "csv"])
+                .default_value("json"),
+        )
+        .arg(
+            Arg::new("project")
+                .long("project")
+                .value_name("PROJECT")
+                .help("Project for --list-instances, --list-backups and --create-backup")
+                .long_help(
+                    "Project for --list-instances, --list-backups, --create-backup and \
+                     --export-inventory. Without those, the TUI starts a restore from this \
+                     project's instances.",
+                ),
+        )
+        .arg(
+            Arg::new("instance")
+                .long("instance")
+                .value_name("INSTANCE")
+                .help("Instance for --list-backups and --create-backup")
+                .long_help(
+                    "Instance for --list-backups and --create-backup. Together with \
+                     --project, the TUI starts a restore at this instance's backups.",
+                ),
+        )
+        .arg(
+            Arg::new("quiet")
+                .long("quiet")
+                .help("Print only the operation id or final status of headless commands")
+                .long_help(
+                    "Print only the operation id or final status of --create-backup, \
+                     --restore-latest and --watch: the operation id when one was started, \
+                     SKIPPED or DRY_RUN when none was, and the final status for --watch. \
+                     --export-inventory prints the number of instances written. \
+                     Confirmation prompts go to stderr.",
+                )
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_name("FORMAT")
+                .help("Output format for --list-instances and --list-backups")
+                .value_parser(["text", "json"])
+                .default_value("text"),
+        )
         .get_matches();
 
     let dry_run_mode = matches.get_flag("dry-run");
+    let engine_filter = matches
+        .get_one::<String>("engine")
+        .map(|engine| engine.to_uppercase());
+
+    let protect_pattern = ProtectPattern::parse(
+        matches
+            .get_one::<String>("protect-pattern")
+            .map_or(ProtectPattern::DEFAULT, String::as_str),
+    )
+    .map_err(|e| FailureKind::Usage.error(e))?;
+    let keymap = KeyMap::load(matches.get_one::<String>("keymap").map(Path::new))?;
+
+    let api_endpoint = matches
+        .get_one::<String>("api-endpoint")
+        .cloned()
+        .or_else(|| std::env::var("SQLADMIN_ENDPOINT").ok().filter(|url| !url.is_empty()))
+        .unwrap_or_else(|| DEFAULT_API_ENDPOINT.to_string());
+
+    let gcp_client = GcpClient::with_proxy(matches.get_one::<String>("proxy").map(String::as_str))?
+        .with_account(matches.get_one::<String>("account").cloned())
+        .with_endpoint(&api_endpoint);
+
+    if matches.get_flag("list-instances") || matches.get_flag("list-backups") {
+        return run_list_command(&gcp_client, &matches, engine_filter.as_deref()).await;
+    }
+    if matches.get_flag("create-backup") {
+        return run_create_backup_command(&gcp_client, &matches, dry_run_mode).await;
+    }
+
+    let mut app = App::new(Box::new(gcp_client), dry_run_mode);
+    app.engine_filter = engine_filter;
+    app.api_endpoint = api_endpoint.trim_end_matches('/').to_string();
+    app.set_restore_disabled(matches.get_flag("no-restore"));
+    app.compact_layout = matches.get_flag("compact");
+    app.reduce_motion = matches.get_flag("reduce-motion")
+        || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    app.wrap_selection = matches.get_flag("wrap-selection");
+    if matches.get_flag("local-time") {
+        app.display_zone = DisplayZone::Local;
+    }
+    app.auto_refresh = matches
+        .get_one::<u64>("auto-refresh")
+        .map(|secs| Duration::from_secs(*secs));
+    if let Some(secs) = matches.get_one::<u64>("timeout-prereq") {
+        app.prereq_timeout = Duration::from_secs(*secs);
+    }
+    app.protect_pattern = protect_pattern;
+    app.require_reason = matches.get_flag("require-reason");
+    app.mark_restores = matches.get_flag("mark-restores");
+    app.start_project = matches.get_one::<String>("project").cloned();
+    app.start_instance = matches.get_one::<String>("instance").cloned();
+    if let Some(path) = matches.get_one::<String>("resume") {
+        app.resumed_session = Some(SavedSession::load(Path::new(path))?);
+    }
+    app.keymap = keymap;
+    if let Some(path) = OperationStore::default_path() {
+        app.operation_store = OperationStore::load(&path)?;
+    }
+
+    if matches.get_flag("export-history") {
+        return export_history(&app.operation_store, &matches);
+    }
+    if matches.get_flag("restore-latest") {
+        return run_restore_latest_command(app, &matches).await;
+    }
+    if let Some(path) = matches.get_one::<String>("export-inventory") {
+        return export_inventory(&app, &matches, Path::new(path)).await;
+    }
+    if let Some(watch) = matches.get_many::<String>("watch") {
+        let watch: Vec<&String> = watch.collect();
+        if matches.get_flag("quiet") || !io::stdout().is_terminal() {
+            return run_watch_command(&app, &matches, watch[0], watch[1]).await;
+        }
+        app.watch_flow = Some(WatchFlow::new(watch[0], watch[1]));
+    }
+
+    run_tui_app(app, matches.get_one::<String>("save-session").map(Path::new)).await?;
 
-    // Run the application in restore mode (with or without dry-run)
-    run_tui_app(dry_run_mode).await?;
+    Ok(())
+}
 
+/// Prints instances or backups for use from scripts, without starting the TUI.
+async fn run_list_command(
+    gcp_client: &GcpClient,
+    matches: &ArgMatches,
+    engine_filter: Option<&str>,
+) -> Result<()> {
+    let project = matches
+        .get_one::<String>("project")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let json = matches.get_one::<String>("output").map(String::as_str) == Some("json");
+
+    if matches.get_flag("list-instances") {
+        let mut instances = gcp_client.list_sql_instances(project).await?;
+        if let Some(engine) = engine_filter {
+            instances.retain(|instance| instance.matches_engine(engine));
+        }
+        if json {
+            println!("{}", serde_json::to_string_pretty(&instances)?);
+        } else {
+            for instance in &instances {
+                println!("{}", instance.name);
+            }
+        }
+    } else {
+        let instance = matches
+            .get_one::<String>("instance")
+            .map(String::as_str)
+            .unwrap_or_default();
+        let backups = gcp_client.list_backups(project, instance).await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&backups)?);
+        } else {
+            for backup in &backups {
+                let start_time = backup
+                    .start_time
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_default();
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    backup.id, start_time, backup.backup_type, backup.status
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates one backup without starting the TUI, for scheduled runs (e.g. from cron).
+async fn run_create_backup_command(
+    gcp_client: &GcpClient,
+    matches: &ArgMatches,
+    dry_run: bool,
+) -> Result<()> {
+    let arg = |name: &str| {
+        matches
+            .get_one::<String>(name)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let (project, instance) = (arg("project"), arg("instance"));
+    // Without --timezone, "today" is the day in the zone times are shown in.
+    let local = match matches.get_one::<String>("timezone") {
+        Some(timezone) => timezone.eq_ignore_ascii_case("local"),
+        None => matches.get_flag("local-time"),
+    };
+    let today = if local {
+        Local::now().date_naive()
+    } else {
+        Utc::now().date_naive()
+    };
+
+    if matches.get_flag("backup-only-if-none-today") {
+        let backups = gcp_client.list_backups(&project, &instance).await?;
+        let exists = if local {
+            has_backup_on(&backups, today, &Local)
+        } else {
+            has_backup_on(&backups, today, &Utc)
+        };
+        if exists {
+            report(
+                matches,
+                "SKIPPED",
+                &format!("Backup already exists today for {}/{}, skipping.", project, instance),
+            );
+            return Ok(());
+        }
+    }
+
+    // Like the TUI, keep the backup in the instance's region.
+    let flow = CreateBackupFlow {
+        instances: SelectableList::new(
+            gcp_client
+                .list_sql_instances(&project)
+                .await
+                .context("Failed to look up the instance region")?,
+        ),
+        ..CreateBackupFlow::new()
+    };
+    let location = flow.backup_location(&instance);
+    let name = arg("backup-name").replace("{date}", &today.format("%Y-%m-%d").to_string());
+    let config = CreateBackupConfig {
+        project,
+        instance,
+        description: name.clone(),
+        name,
+        location,
+    };
+    if dry_run {
+        report(matches, "DRY_RUN", &format!("Dry run: would create backup {}.", config));
+        return Ok(());
+    }
+
+    let operation_id = gcp_client.create_backup(&config).await?;
+    report(
+        matches,
+        &operation_id,
+        &format!("Started backup {} (operation {}).", config, operation_id),
+    );
+    Ok(())
+}
+
+/// Prints the outcome of a headless command: just `quiet` under `--quiet`, for scripts,
+/// and `message` otherwise.
+fn report(matches: &ArgMatches, quiet: &str, message: &str) {
+    if matches.get_flag("quiet") {
+        println!("{}", quiet);
+    } else {
+        println!("{}", message);
+    }
+}
+
+fn export_history(store: &OperationStore, matches: &ArgMatches) -> Result<()> {
+    let operations: Vec<&TrackedOperation> = store.all().collect();
+    if matches.get_one::<String>("audit-format").map(String::as_str) == Some("csv") {
+        println!("{}", operation_store::CSV_HEADER);
+        for operation in operations {
+            println!("{}", operation.to_csv_row());
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&operations)?);
+    }
+    Ok(())
+}
+
+/// Restores the newest backup of the source instance onto the target without the TUI.
+async fn run_restore_latest_command(mut app: App, matches: &ArgMatches) -> Result<()> {
+    let arg = |name: &str| {
+        matches
+            .get_one::<String>(name)
+            .map(String::as_str)
+            .unwrap_or_default()
+    };
+    let backup = app
+        .prepare_latest_backup_restore(
+            arg("project"),
+            arg("instance"),
+            arg("target-project"),
+            arg("target-instance"),
+        )
+        .await?;
+    app.set_restore_reason(matches.get_one::<String>("reason").cloned());
+    if app.reason_missing() {
+        return Err(FailureKind::Usage.error("--require-reason is set; give one with --reason"));
+    }
+    let quiet = matches.get_flag("quiet");
+    if !quiet {
+        println!(
+            "Auto-selected latest backup {} of {}/{}.",
+            backup,
+            arg("project"),
+            arg("instance")
+        );
+    }
+    if let Some((source, target)) = app.restore_flow.newer_source_version() {
+        eprintln!(
+            "Warning: the backup is from {}, newer than the {} the target runs; the restore \
+             will fail.",
+            source, target
+        );
+    }
+
+    if !matches.get_flag("yes") && !app.dry_run_mode {
+        let prompt = format!(
+            "This will overwrite ALL data on {}/{}. Type the instance name to continue: ",
+            arg("target-project"),
+            arg("target-instance")
+        );
+        if quiet {
+            eprint!("{}", prompt);
+            io::stderr().flush()?;
+        } else {
+            print!("{}", prompt);
+            io::stdout().flush()?;
+        }
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        if answer.trim() != arg("target-instance") {
+            return Err(anyhow!("Restore cancelled"));
+        }
+    }
+
+    app.perform_restore().await?;
+    if let Some(error) = app.error {
+        let message = error.trim_end_matches(" Press ESC to clear.").to_string();
+        return Err(FailureKind::OperationFailed.error(message));
+    }
+    if let Some(preview) = app.dry_run_preview {
+        report(matches, "DRY_RUN", &preview);
+    } else if app.restore_flow.retry_at.is_some() {
+        return Err(FailureKind::OperationFailed
+            .error("Target instance is busy with another operation; try again later"));
+    } else if let Some(operation_id) = app.restore_flow.operation_id {
+        report(
+            matches,
+            &operation_id,
+            &format!("Started restore (operation {}).", operation_id),
+        );
+    }
+    Ok(())
+}
+
+/// Writes the inventory of `--project` to `path` without the TUI.
+async fn export_inventory(app: &App, matches: &ArgMatches, path: &Path) -> Result<()> {
+    let project = matches
+        .get_one::<String>("project")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let inventory = app.collect_inventory(project).await?;
+    std::fs::write(path, serde_json::to_string_pretty(&inventory)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    let failures = inventory.failures();
+    let mut message = format!(
+        "Wrote {} instances of {} to {}.",
+        inventory.instances.len(),
+        project,
+        path.display()
+    );
+    if failures > 0 {
+        message.push_str(&format!(" {} could not be described; see their error.", failures));
+    }
+    report(matches, &inventory.instances.len().to_string(), &message);
+    Ok(())
+}
+
+/// Follows an operation until it finishes without the TUI, failing if it failed.
+async fn run_watch_command(
+    app: &App,
+    matches: &ArgMatches,
+    project: &str,
+    operation_id: &str,
+) -> Result<()> {
+    let quiet = matches.get_flag("quiet");
+    let operation = app
+        .watch_operation(
+            project,
+            operation_id,
+            WATCH_INTERVAL,
+            |operation| {
+                if !quiet {
+                    println!("{}", operation.watch_line());
+                }
+            },
+            |error| eprintln!("Failed to check status, retrying: {}", error),
+        )
+        .await?;
+    if operation.phase() == OperationPhase::Failed {
+        return Err(FailureKind::OperationFailed.error(format!(
+            "Operation {} failed: {}",
+            operation_id,
+            operation.error_message.as_deref().unwrap_or(&operation.status)
+        )));
+    }
+    report(
+        matches,
+        &operation.status,
+        &format!("Operation {} finished.", operation_id),
+    );
     Ok(())
 }
 
-async fn run_tui_app(dry_run_mode: bool) -> Result<()> {
+async fn run_tui_app(app: App, save_session: Option<&Path>) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -42,9 +738,7 @@ async fn run_tui_app(dry_run_mode: bool) -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run it
-    let gcp_client = GcpClient::new();
-    let app = App::new(Box::new(gcp_client), dry_run_mode);
+    // Run the app
     let res = run_app(&mut terminal, app).await;
 
     // Restore terminal
@@ -56,8 +750,24 @@ async fn run_tui_app(dry_run_mode: bool) -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
-    if let Err(err) = res {
-        println!("{err:?}");
+    match res {
+        Ok(app) => {
+            if let Some(operation) = app.watch_flow.as_ref().and_then(|f| f.operation.as_ref()) {
+                println!("{}", operation.watch_line());
+            }
+            if let Some(path) = save_session {
+                SavedSession::from_flow(&app.restore_flow).save(path)?;
+                println!("Saved restore selections to {}.", path.display());
+            }
+            let summary = app.session_summary();
+            if !summary.is_empty() {
+                println!("Operations this session:");
+                for line in summary {
+                    println!("  {}", line);
+                }
+            }
+        }
+        Err(err) => println!("{err:?}"),
     }
 
     Ok(())
@@ -0,0 +1,229 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::types::OperationMode;
+
+/// Column names of [`TrackedOperation::to_csv_row`].
+pub const CSV_HEADER: &str =
+    "started_at,mode,source,project,instance,location,backup_id,operation_id,status,\
+     duration_secs,error,reason,databases";
+
+/// A restore or backup operation started by this tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedOperation {
+    /// The project the operation runs in: the target of a restore.
+    pub project: String,
+    pub operation_id: String,
+    pub mode: OperationMode,
+    pub started_at: DateTime<Utc>,
+    pub status: String,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// The instance the operation runs on: the target of a restore.
+    #[serde(default)]
+    pub instance: Option<String>,
+    /// For restores, the `project/instance` the backup was taken from.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// For backups, where the backup is stored, if it was pinned.
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub backup_id: Option<String>,
+    #[serde(default)]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// For restores, the reason given for doing it.
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// For restores, the databases the user said they cared about. The whole instance is
+    /// restored regardless.
+    #[serde(default)]
+    pub databases: Vec<String>,
+    /// For restores, the backup with which `--mark-restores` recorded it, so a restore
+    /// watched again after a restart is not marked twice.
+    #[serde(default)]
+    pub marker_operation_id: Option<String>,
+}
+
+impl TrackedOperation {
+    pub fn new(mode: OperationMode, project: &str, operation_id: &str) -> Self {
+        Self {
+            project: project.to_string(),
+            operation_id: operation_id.to_string(),
+            mode,
+            started_at: Utc::now(),
+            status: "RUNNING".to_string(),
+            error: None,
+            instance: None,
+            source: None,
+            location: None,
+            backup_id: None,
+            finished_at: None,
+            reason: None,
+            databases: Vec::new(),
+            marker_operation_id: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "DONE" | "FAILED" | "ERROR")
+    }
+
+    /// One line of the CSV export, in [`CSV_HEADER`] order. The duration is empty until
+    /// the operation has finished, and databases are separated by semicolons.
+    pub fn to_csv_row(&self) -> String {
+        let duration = self
+            .finished_at
+            .map(|end| (end - self.started_at).num_seconds().to_string())
+            .unwrap_or_default();
+        let mode = format!("{:?}", self.mode);
+        let databases = self.databases.join(";");
+        let started_at = self.started_at.to_rfc3339();
+        [
+            started_at.as_str(),
+            mode.as_str(),
+            self.source.as_deref().unwrap_or_default(),
+            &self.project,
+            self.instance.as_deref().unwrap_or_default(),
+            self.location.as_deref().unwrap_or_default(),
+            self.backup_id.as_deref().unwrap_or_default(),
+            &self.operation_id,
+            &self.status,
+            &duration,
+            self.error.as_deref().unwrap_or_default(),
+            self.reason.as_deref().unwrap_or_default(),
+            &databases,
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Operations that were still running when the tool last saved, plus every finished one,
+/// persisted so that quitting does not orphan a long-running restore. The history is
+/// never trimmed, as it is the audit trail `--export-history` prints.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OperationStore {
+    #[serde(default)]
+    pub in_flight: Vec<TrackedOperation>,
+    #[serde(default)]
+    pub history: Vec<TrackedOperation>,
+    /// Where the store is saved. Without a path, changes are kept in memory only.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl OperationStore {
+    /// `operations.json` in the [config directory](config::config_dir).
+    pub fn default_path() -> Option<PathBuf> {
+        config::config_dir().map(|dir| dir.join("operations.json"))
+    }
+
+    /// Loads the store from `path`. A missing file yields an empty store.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut store = if path.exists() {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&contents)
+                .with_context(|| format!("Invalid operation store {}", path.display()))?
+        } else {
+            Self::default()
+        };
+        store.path = Some(path.to_path_buf());
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn track(&mut self, mode: OperationMode, project: &str, operation_id: &str) -> Result<()> {
+        self.track_operation(TrackedOperation::new(mode, project, operation_id))
+    }
+
+    pub fn track_operation(&mut self, operation: TrackedOperation) -> Result<()> {
+        self.in_flight.push(operation);
+        self.save()
+    }
+
+    /// The operation id of the backup that marks restore `operation_id`, if one was made.
+    pub fn marker_of(&self, operation_id: &str) -> Option<&str> {
+        self.all()
+            .find(|op| op.operation_id == operation_id)
+            .and_then(|op| op.marker_operation_id.as_deref())
+    }
+
+    /// Records that `marker_operation_id` marks restore `operation_id`. Unknown operation
+    /// ids are ignored.
+    pub fn record_marker(&mut self, operation_id: &str, marker_operation_id: &str) -> Result<()> {
+        let Some(operation) = self
+            .in_flight
+            .iter_mut()
+            .chain(self.history.iter_mut())
+            .find(|op| op.operation_id == operation_id)
+        else {
+            return Ok(());
+        };
+        operation.marker_operation_id = Some(marker_operation_id.to_string());
+        self.save()
+    }
+
+    /// Every tracked operation, newest first within in-flight and then finished ones.
+    pub fn all(&self) -> impl Iterator<Item = &TrackedOperation> {
+        self.in_flight.iter().rev().chain(self.history.iter())
+    }
+
+    /// Records the latest status of a tracked operation, moving it to the history once
+    /// it has finished. `end_time` is when the API says it finished; without one, the
+    /// time it was seen finished is used. Unknown operation ids are ignored.
+    pub fn update_status(
+        &mut self,
+        operation_id: &str,
+        status: &str,
+        error: Option<String>,
+        end_time: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let Some(index) = self
+            .in_flight
+            .iter()
+            .position(|op| op.operation_id == operation_id)
+        else {
+            return Ok(());
+        };
+
+        let operation = &mut self.in_flight[index];
+        if operation.status == status && operation.error == error {
+            return Ok(());
+        }
+        operation.status = status.to_string();
+        operation.error = error;
+        if operation.is_finished() {
+            operation.finished_at = Some(end_time.unwrap_or_else(Utc::now));
+            let finished = self.in_flight.remove(index);
+            self.history.insert(0, finished);
+        }
+        self.save()
+    }
+}
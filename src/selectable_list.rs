@@ -0,0 +1,206 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    widgets::{Block, List, ListItem, ListState},
+    Frame,
+};
+
+/// Decides which items of a [`SelectableList`] are shown.
+pub type ListFilter<T> = Box<dyn Fn(&T) -> bool>;
+
+/// A list the user picks from: its items, an optional filter, the selection and how far
+/// it is scrolled. The selected index counts the items that pass the filter, as shown on
+/// screen. The filter runs when it or the items change, not on every lookup.
+pub struct SelectableList<T> {
+    items: Vec<T>,
+    filter: Option<ListFilter<T>>,
+    /// Indices into `items` of the items that pass the filter, in order.
+    shown: Vec<usize>,
+    selected: usize,
+    /// The first shown item drawn, kept between frames so the view only scrolls when the
+    /// selection leaves it.
+    offset: usize,
+}
+
+impl<T> Default for SelectableList<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> SelectableList<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        let shown = (0..items.len()).collect();
+        Self {
+            items,
+            filter: None,
+            shown,
+            selected: 0,
+            offset: 0,
+        }
+    }
+
+    /// All items, whether or not they pass the filter.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Replaces the items, keeping the filter, and moves the selection to the top.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.selected = 0;
+        self.offset = 0;
+        self.refilter();
+    }
+
+    pub fn clear(&mut self) {
+        self.set_items(Vec::new());
+    }
+
+    /// Adds an item at the end and selects it if it passes the filter.
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+        self.refilter();
+        let added = self.items.len() - 1;
+        if let Some(position) = self.shown.iter().position(|&index| index == added) {
+            self.selected = position;
+        }
+    }
+
+    /// Sorts the items, keeping the selection on the same row number.
+    pub fn sort_by(&mut self, compare: impl FnMut(&T, &T) -> Ordering) {
+        self.items.sort_by(compare);
+        self.refilter();
+    }
+
+    /// Shows only the items `filter` accepts, until it is replaced.
+    pub fn set_filter(&mut self, filter: impl Fn(&T) -> bool + 'static) {
+        self.filter = Some(Box::new(filter));
+        self.refilter();
+    }
+
+    /// Shows every item again.
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+        self.refilter();
+    }
+
+    fn refilter(&mut self) {
+        self.shown = match &self.filter {
+            Some(filter) => (0..self.items.len())
+                .filter(|&index| filter(&self.items[index]))
+                .collect(),
+            None => (0..self.items.len()).collect(),
+        };
+    }
+
+    /// The items that pass the filter, in order.
+    pub fn filtered_items(&self) -> Vec<&T> {
+        self.shown.iter().map(|&index| &self.items[index]).collect()
+    }
+
+    /// How many items pass the filter.
+    pub fn len(&self) -> usize {
+        self.shown.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty()
+    }
+
+    /// Index of the selection among the filtered items, kept within the list.
+    pub fn selected(&self) -> usize {
+        self.selected.min(self.shown.len().saturating_sub(1))
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+    }
+
+    /// Selects the first filtered item `matches` accepts. Returns whether there was one;
+    /// without one, the selection stays where it was.
+    pub fn select_where(&mut self, matches: impl Fn(&T) -> bool) -> bool {
+        let found = self
+            .shown
+            .iter()
+            .position(|&index| matches(&self.items[index]));
+        if let Some(position) = found {
+            self.selected = position;
+        }
+        found.is_some()
+    }
+
+    pub fn selected_item(&self) -> Option<&T> {
+        self.shown
+            .get(self.selected())
+            .map(|&index| &self.items[index])
+    }
+
+    /// Moves the selection one row up. Past the top it stays put, or with `wrap`
+    /// continues from the bottom.
+    pub fn move_up(&mut self, wrap: bool) {
+        let last = self.shown.len().saturating_sub(1);
+        self.selected = match self.selected() {
+            0 if wrap => last,
+            index => index.saturating_sub(1),
+        };
+    }
+
+    /// Moves the selection one row down. Past the bottom it stays put, or with `wrap`
+    /// continues from the top.
+    pub fn move_down(&mut self, wrap: bool) {
+        let last = self.shown.len().saturating_sub(1);
+        self.selected = match self.selected() {
+            index if index >= last && wrap => 0,
+            index => (index + 1).min(last),
+        };
+    }
+
+    /// The filtered items that fit in `height` rows. The view stays where it was last
+    /// drawn unless the selection has left it, and then scrolls just far enough to bring
+    /// the selection back.
+    pub fn visible_rows(&mut self, height: usize) -> Range<usize> {
+        let height = height.max(1);
+        let len = self.shown.len();
+        let selected = self.selected();
+        self.offset = self.offset.min(len.saturating_sub(height));
+        if selected < self.offset {
+            self.offset = selected;
+        } else if selected >= self.offset + height {
+            self.offset = selected + 1 - height;
+        }
+        self.offset..(self.offset + height).min(len)
+    }
+
+    /// Renders the list inside `block`, building items with `to_item` (given the filtered
+    /// index) only for the rows that are shown, so long lists stay cheap to draw.
+    pub fn render(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        block: Block,
+        highlight: Style,
+        to_item: impl Fn(usize, &T) -> ListItem<'static>,
+    ) {
+        let rows = self.visible_rows(usize::from(area.height.saturating_sub(2)));
+        let first_row = rows.start;
+        let items: Vec<ListItem> = self.shown[rows]
+            .iter()
+            .enumerate()
+            .map(|(offset, &index)| to_item(first_row + offset, &self.items[index]))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(highlight)
+            .highlight_symbol("► ");
+
+        let mut state = ListState::default();
+        state.select(Some(self.selected().saturating_sub(first_row)));
+
+        f.render_stateful_widget(list, area, &mut state);
+    }
+}
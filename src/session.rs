@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::state::restore_flow::RestoreFlow;
+
+/// The choices made so far in a restore, saved with `--save-session` so that
+/// `--resume` can pick the flow up again later. Operation ids are deliberately left out:
+/// a resumed session only ever starts from the selections.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSession {
+    #[serde(default)]
+    pub source_project: Option<String>,
+    #[serde(default)]
+    pub source_instance: Option<String>,
+    #[serde(default)]
+    pub backup_id: Option<String>,
+    #[serde(default)]
+    pub target_project: Option<String>,
+    #[serde(default)]
+    pub target_instance: Option<String>,
+}
+
+impl SavedSession {
+    pub fn from_flow(flow: &RestoreFlow) -> Self {
+        Self {
+            source_project: flow.source_project.clone(),
+            source_instance: flow.source_instance.clone(),
+            backup_id: flow.selected_backup.clone(),
+            target_project: flow.target_project.clone(),
+            target_instance: flow.target_instance.clone(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid session file {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
@@ -1,18 +1,87 @@
-use crate::types::{CreateBackupConfig, SqlInstance};
+use std::collections::BTreeMap;
+
+use crate::selectable_list::SelectableList;
+use crate::types::{CreateBackupConfig, Operation, SqlInstance};
+
+/// Progress of one instance's backup within a batch backup.
+#[derive(Debug, Clone)]
+pub struct BatchBackupOperation {
+    pub instance: String,
+    pub operation_id: Option<String>,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+impl BatchBackupOperation {
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "DONE" | "FAILED" | "ERROR")
+    }
+}
 
 #[derive(Default)]
 pub struct CreateBackupFlow {
     pub project: Option<String>,
     pub instance: Option<String>,
     pub config: Option<CreateBackupConfig>,
+    /// Name (or batch name template) last entered, to edit when going back to naming.
+    pub last_backup_name: Option<String>,
     pub operation_id: Option<String>,
     pub status: Option<String>,
-    pub instances: Vec<SqlInstance>,
-    pub selected_instance_index: usize,
+    pub operation_warning: Option<String>,
+    /// Cloud SQL's type for the operation, once its status has been fetched.
+    pub operation_type: Option<String>,
+    /// The operation as last fetched, for the details popup.
+    pub last_operation: Option<Operation>,
+    pub instances: SelectableList<SqlInstance>,
+    pub labels: BTreeMap<String, String>,
+    /// Location chosen for the backup; without one, each instance's region is used.
+    pub location: Option<String>,
+    pub marked_instances: Vec<String>,
+    pub batch_configs: Vec<CreateBackupConfig>,
+    pub batch_operations: Vec<BatchBackupOperation>,
 }
 
 impl CreateBackupFlow {
     pub fn new() -> Self {
         Self::default()
     }
-}
\ No newline at end of file
+
+    /// The chosen location, or else the region of `instance` if it is known.
+    pub fn backup_location(&self, instance: &str) -> Option<String> {
+        self.location.clone().or_else(|| {
+            self.instances
+                .items()
+                .iter()
+                .find(|i| i.name == instance)
+                .and_then(|i| i.known_region())
+                .map(str::to_string)
+        })
+    }
+
+    pub fn is_batch(&self) -> bool {
+        !self.marked_instances.is_empty()
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.config.is_some() || !self.batch_configs.is_empty()
+    }
+
+    pub fn has_operations(&self) -> bool {
+        self.operation_id.is_some() || !self.batch_operations.is_empty()
+    }
+
+    /// Returns `(done, failed, total)` for the current batch.
+    pub fn batch_progress(&self) -> (usize, usize, usize) {
+        let failed = self
+            .batch_operations
+            .iter()
+            .filter(|op| matches!(op.status.as_str(), "FAILED" | "ERROR"))
+            .count();
+        let done = self
+            .batch_operations
+            .iter()
+            .filter(|op| op.status == "DONE")
+            .count();
+        (done, failed, self.batch_operations.len())
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+
+use crate::types::{
+    Backup, CreateBackupConfig, InstanceStatus, Operation, OperationMode, RestoreConfig,
+    SqlInstance,
+};
+
+/// Tracks which panels are waiting on a GCP call, so each can show its own spinner.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoadingState {
+    pub instances: bool,
+    pub backups: bool,
+    pub operation_status: bool,
+    /// The relevant instance is being described.
+    pub instance_state: bool,
+}
+
+impl LoadingState {
+    pub fn any(&self) -> bool {
+        self.instances || self.backups || self.operation_status || self.instance_state
+    }
+}
+
+/// What a background request is for. Only the newest request of each kind is awaited,
+/// so the result of an older one that arrives late is dropped instead of overwriting it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RequestKind {
+    Instances,
+    /// The backup list of an instance or of the whole project.
+    Backups,
+    InstanceState,
+    /// Starting the confirmed restore or backup(s).
+    StartOperation,
+    RestoreMarker,
+    AccessToken,
+    /// Checking that a typed backup id exists on the source instance.
+    TypedBackup,
+    /// A status check of the operation with this id.
+    OperationStatus(StatusCheck, String),
+}
+
+/// The screen a status check was made for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusCheck {
+    Restore,
+    Backup,
+    BatchBackup,
+    Stored,
+    Watch,
+}
+
+/// How a request to start a restore ended.
+pub enum RestoreStart {
+    /// The backup was gone, or could not be checked, right before restoring.
+    BackupMissing(String),
+    /// The restore was requested; the result is its operation id.
+    Requested(Result<String>),
+}
+
+/// The result of a GCP request that ran on a background task.
+pub enum BackgroundResult {
+    Instances {
+        mode: OperationMode,
+        result: Result<Vec<SqlInstance>>,
+    },
+    Backups(Result<Vec<Backup>>),
+    ProjectBackups(Result<Vec<(String, Backup)>>),
+    /// The described `(project, instance)`; applied only if it is still the relevant one.
+    InstanceState {
+        project: String,
+        instance: String,
+        result: Result<InstanceStatus>,
+    },
+    OperationStatus {
+        check: StatusCheck,
+        operation_id: String,
+        result: Result<Operation>,
+    },
+    RestoreStarted {
+        config: RestoreConfig,
+        outcome: RestoreStart,
+    },
+    /// The operation id (or error) of each backup started, in order.
+    BackupsStarted {
+        batch: bool,
+        results: Vec<(CreateBackupConfig, Result<String>)>,
+    },
+    /// The marker backup's operation id (or error) for restore `restore_operation_id`.
+    RestoreMarked {
+        restore_operation_id: String,
+        result: Result<String>,
+    },
+    AccessTokenRefreshed(Result<()>),
+    /// The backup list of `instance`, searched for the typed `backup_id`.
+    TypedBackup {
+        instance: String,
+        backup_id: String,
+        result: Result<Vec<Backup>>,
+    },
+}
+
+/// A finished background request. The task sends it over the app's channel and the event
+/// loop applies it on its next tick, unless a newer request of the same kind was made.
+pub struct Completed {
+    pub kind: RequestKind,
+    pub sequence: u64,
+    pub result: BackgroundResult,
+}
@@ -1,2 +1,4 @@
 pub mod create_backup_flow;
-pub mod restore_flow;
\ No newline at end of file
+pub mod loading;
+pub mod restore_flow;
+pub mod watch_flow;
@@ -1,23 +1,175 @@
-use crate::types::{RestoreConfig, SqlInstance, Backup};
+use std::time::Instant;
+
+use crate::selectable_list::SelectableList;
+use crate::types::{
+    is_newer_version, RestoreConfig, SqlInstance, Backup, DateRange, DisplayZone, Operation,
+};
+
+/// Ordering of the project-wide backup list.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectBackupSort {
+    #[default]
+    Newest,
+    Instance,
+}
+
+impl ProjectBackupSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Newest => Self::Instance,
+            Self::Instance => Self::Newest,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Newest => "newest first",
+            Self::Instance => "by instance",
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct RestoreFlow {
     pub source_project: Option<String>,
     pub source_instance: Option<String>,
+    /// Database version of the source instance, kept because the instance list is
+    /// replaced by the target project's.
+    pub source_version: Option<String>,
     pub target_project: Option<String>,
     pub target_instance: Option<String>,
     pub selected_backup: Option<String>,
+    /// Set when `selected_backup` was picked by [`RestoreFlow::select_latest_backup`]
+    /// rather than by the user.
+    pub auto_selected_backup: bool,
+    pub intended_databases: Vec<String>,
+    /// Why the restore is being done; kept here so rebuilding the config keeps it.
+    pub reason: Option<String>,
     pub config: Option<RestoreConfig>,
+    /// When the type-to-confirm prompt for a protected target was opened.
+    pub confirm_started_at: Option<Instant>,
     pub operation_id: Option<String>,
     pub status: Option<String>,
-    pub instances: Vec<SqlInstance>,
-    pub backups: Vec<Backup>,
-    pub selected_instance_index: usize,
-    pub selected_backup_index: usize,
+    pub operation_warning: Option<String>,
+    /// Cloud SQL's type for the operation, once its status has been fetched.
+    pub operation_type: Option<String>,
+    /// The operation as last fetched, for the details popup.
+    pub last_operation: Option<Operation>,
+    /// Operation of the backup that records the finished restore on the target
+    /// (`--mark-restores`).
+    pub marker_operation_id: Option<String>,
+    /// The restore has just finished and its marker backup is yet to be requested.
+    pub marker_due: bool,
+    /// When a restore hit a busy target, the time at which it will be re-issued.
+    pub retry_at: Option<Instant>,
+    pub retry_attempts: u32,
+    pub instances: SelectableList<SqlInstance>,
+    /// Backups of the source instance, filtered by [`RestoreFlow::date_filter`].
+    pub backups: SelectableList<Backup>,
+    date_filter: Option<DateRange>,
+    /// Backups across every instance of the source project, as `(instance, backup)`.
+    pub project_backups: SelectableList<(String, Backup)>,
+    pub project_backup_sort: ProjectBackupSort,
+    /// Ids of up to two backups marked for comparison, oldest mark first.
+    pub compare_marks: Vec<String>,
+    pub show_comparison: bool,
+    /// Whether the backups-by-age view of the retention popup is open.
+    pub show_retention: bool,
 }
 
 impl RestoreFlow {
     pub fn new() -> Self {
         Self::default()
     }
-}
\ No newline at end of file
+
+    pub fn date_filter(&self) -> Option<DateRange> {
+        self.date_filter
+    }
+
+    /// Shows only the backups taken on a day in `range`, as seen in `zone`. Backups without
+    /// a start time are hidden while the filter is active.
+    pub fn set_date_filter(&mut self, range: DateRange, zone: DisplayZone) {
+        self.date_filter = Some(range);
+        self.backups.set_filter(move |backup: &Backup| {
+            backup
+                .start_time
+                .is_some_and(|time| range.contains(time, zone))
+        });
+    }
+
+    pub fn clear_date_filter(&mut self) {
+        self.date_filter = None;
+        self.backups.clear_filter();
+    }
+
+    /// Database version of `instance` in the loaded list, unless it is unknown.
+    pub fn instance_version(&self, instance: &str) -> Option<&str> {
+        self.instances
+            .items()
+            .iter()
+            .find(|i| i.name == instance && !i.is_manual())
+            .map(|i| i.database_version.as_str())
+    }
+
+    /// The source and target versions, if the backup comes from a newer version than
+    /// the chosen target runs.
+    pub fn newer_source_version(&self) -> Option<(&str, &str)> {
+        let source = self.source_version.as_deref()?;
+        let target = self.instance_version(self.target_instance.as_deref()?)?;
+        is_newer_version(source, target).then_some((source, target))
+    }
+
+    /// Marks or unmarks the selected backup for comparison. Marking a third backup
+    /// replaces the oldest mark.
+    pub fn toggle_compare_mark(&mut self) {
+        let Some(id) = self.backups.selected_item().map(|backup| backup.id.clone()) else {
+            return;
+        };
+        match self.compare_marks.iter().position(|mark| *mark == id) {
+            Some(position) => {
+                self.compare_marks.remove(position);
+            }
+            None => {
+                self.compare_marks.push(id);
+                if self.compare_marks.len() > 2 {
+                    self.compare_marks.remove(0);
+                }
+            }
+        }
+    }
+
+    /// The two marked backups, in the order they were marked.
+    pub fn compared_backups(&self) -> Option<(&Backup, &Backup)> {
+        let find = |id: &String| self.backups.items().iter().find(|backup| backup.id == *id);
+        match self.compare_marks.as_slice() {
+            [first, second] => Some((find(first)?, find(second)?)),
+            _ => None,
+        }
+    }
+
+    /// Sorts `backups` newest first and selects the newest one that completed.
+    pub fn select_latest_backup(&mut self) -> Option<Backup> {
+        self.backups
+            .sort_by(|a, b| b.start_time.cmp(&a.start_time));
+        self.clear_date_filter();
+        if !self.backups.select_where(Backup::completed) {
+            return None;
+        }
+        let backup = self.backups.selected_item()?.clone();
+        self.selected_backup = Some(backup.id.clone());
+        self.auto_selected_backup = true;
+        Some(backup)
+    }
+
+    pub fn sort_project_backups(&mut self) {
+        let newest_first = |a: &Backup, b: &Backup| b.start_time.cmp(&a.start_time);
+        match self.project_backup_sort {
+            ProjectBackupSort::Newest => self
+                .project_backups
+                .sort_by(|(_, a), (_, b)| newest_first(a, b)),
+            ProjectBackupSort::Instance => self
+                .project_backups
+                .sort_by(|(ia, a), (ib, b)| ia.cmp(ib).then_with(|| newest_first(a, b))),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::types::{Operation, OperationPhase};
+
+/// An operation followed with `--watch`, which may have been started by someone else.
+pub struct WatchFlow {
+    pub project: String,
+    pub operation_id: String,
+    /// The operation as last fetched.
+    pub operation: Option<Operation>,
+    /// Why the last status check failed. Checks are retried, so this clears once one
+    /// succeeds again.
+    pub poll_error: Option<String>,
+}
+
+impl WatchFlow {
+    pub fn new(project: &str, operation_id: &str) -> Self {
+        Self {
+            project: project.to_string(),
+            operation_id: operation_id.to_string(),
+            operation: None,
+            poll_error: None,
+        }
+    }
+
+    /// Whether the operation is done or has failed, so there is nothing left to check.
+    pub fn is_finished(&self) -> bool {
+        self.operation.as_ref().is_some_and(|operation| {
+            matches!(operation.phase(), OperationPhase::Done | OperationPhase::Failed)
+        })
+    }
+}
@@ -1,20 +1,585 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeDelta, TimeZone, Utc, Weekday};
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SqlInstance {
     pub name: String,
     pub database_version: String,
     pub region: String,
     pub tier: String,
+    /// `CLOUD_SQL_INSTANCE`, `READ_REPLICA_INSTANCE` or `ON_PREMISES_INSTANCE`.
+    pub instance_type: String,
 }
 
-#[derive(Debug, Clone)]
+impl SqlInstance {
+    /// Placeholder for the fields of a manually entered instance, which are unknown.
+    pub const MANUAL: &'static str = "Manual";
+
+    /// An instance the user typed in rather than picked from `gcloud sql instances list`.
+    pub fn manual(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            database_version: Self::MANUAL.to_string(),
+            region: Self::MANUAL.to_string(),
+            tier: Self::MANUAL.to_string(),
+            instance_type: Self::MANUAL.to_string(),
+        }
+    }
+
+    /// Manual instances have no known engine, region, tier or type, so checks on those
+    /// fields must let them through rather than compare against the placeholder.
+    pub fn is_manual(&self) -> bool {
+        self.database_version == Self::MANUAL
+    }
+
+    /// The region, unless it is unknown.
+    pub fn known_region(&self) -> Option<&str> {
+        Some(self.region.as_str()).filter(|region| !self.is_manual() && !region.is_empty())
+    }
+
+    /// Read replicas cannot be restored onto.
+    pub fn is_replica(&self) -> bool {
+        self.instance_type == "READ_REPLICA_INSTANCE"
+    }
+
+    /// Returns true if the instance runs the given engine (`MYSQL`, `POSTGRES`, `SQLSERVER`).
+    /// Manually entered instances have an unknown engine and always match.
+    pub fn matches_engine(&self, engine: &str) -> bool {
+        self.is_manual()
+            || self
+                .database_version
+                .to_uppercase()
+                .starts_with(&engine.to_uppercase())
+    }
+
+    /// The name followed by engine, region and tier, skipping empty fields. Manual
+    /// instances have none of these, which is said instead.
+    pub fn summary(&self) -> String {
+        if self.is_manual() {
+            return format!("{} (details unknown)", self.name);
+        }
+        let details: Vec<&str> = [&self.database_version, &self.region, &self.tier]
+            .into_iter()
+            .map(String::as_str)
+            .filter(|field| !field.is_empty())
+            .collect();
+        let mut summary = if details.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({})", self.name, details.join(", "))
+        };
+        if self.is_replica() {
+            summary.push_str(" (replica)");
+        }
+        summary
+    }
+}
+
+impl fmt::Display for SqlInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// What `gcloud sql instances describe` says about an instance's readiness.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstanceStatus {
+    /// e.g. `RUNNABLE`, `PENDING_CREATE` or `MAINTENANCE`.
+    pub state: String,
+    /// `ALWAYS` for a started instance, `NEVER` for a stopped one.
+    pub activation_policy: String,
+    pub maintenance_window: Option<MaintenanceWindow>,
+    /// Size of the data disk, when reported.
+    pub disk_size_gb: Option<u64>,
+    /// `REGIONAL` for a high-availability instance, `ZONAL` otherwise.
+    pub availability_type: String,
+}
+
+impl InstanceStatus {
+    /// The `gcloud --format` that [`InstanceStatus::parse`] reads.
+    pub const GCLOUD_FORMAT: &'static str = "value(state,settings.activationPolicy,\
+        settings.maintenanceWindow.day,settings.maintenanceWindow.hour,\
+        settings.dataDiskSizeGb,settings.availabilityType)";
+
+    /// Parses the tab-separated [`InstanceStatus::GCLOUD_FORMAT`] output.
+    pub fn parse(line: &str) -> Self {
+        let mut fields = line.trim().splitn(6, '\t').map(str::trim);
+        let mut next = || fields.next().unwrap_or_default();
+        let state = next().to_string();
+        let activation_policy = next().to_string();
+        let (day, hour) = (next(), next());
+        let disk_size_gb = next().parse().ok();
+        Self {
+            state,
+            activation_policy,
+            maintenance_window: MaintenanceWindow::parse(day, hour),
+            disk_size_gb,
+            availability_type: next().to_string(),
+        }
+    }
+
+    /// Whether the instance has a standby in another zone.
+    pub fn high_availability(&self) -> bool {
+        self.availability_type == "REGIONAL"
+    }
+
+    /// Whether the instance is up and set to stay up, so it is likely serving clients.
+    pub fn likely_in_use(&self) -> bool {
+        self.state == "RUNNABLE" && self.activation_policy != "NEVER"
+    }
+}
+
+/// One instance of an `--export-inventory` snapshot. When describing the instance failed,
+/// the fields that come from its description are empty and `error` says why.
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryEntry {
+    pub name: String,
+    pub database_version: String,
+    pub region: String,
+    pub tier: String,
+    pub instance_type: String,
+    pub state: Option<String>,
+    pub disk_size_gb: Option<u64>,
+    pub high_availability: Option<bool>,
+    pub maintenance_window: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl InventoryEntry {
+    /// The entry for `instance`, given what describing it returned.
+    pub fn new(instance: SqlInstance, described: Result<InstanceStatus, String>) -> Self {
+        let (status, error) = match described {
+            Ok(status) => (Some(status), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            state: status.as_ref().map(|s| s.state.clone()),
+            disk_size_gb: status.as_ref().and_then(|s| s.disk_size_gb),
+            high_availability: status.as_ref().map(InstanceStatus::high_availability),
+            maintenance_window: status
+                .as_ref()
+                .and_then(|s| s.maintenance_window)
+                .map(|window| window.to_string()),
+            error,
+            name: instance.name,
+            database_version: instance.database_version,
+            region: instance.region,
+            tier: instance.tier,
+            instance_type: instance.instance_type,
+        }
+    }
+}
+
+/// A snapshot of a project's Cloud SQL instances, written by `--export-inventory`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Inventory {
+    pub project: String,
+    pub exported_at: DateTime<Utc>,
+    pub instances: Vec<InventoryEntry>,
+}
+
+impl Inventory {
+    /// How many instances could not be described.
+    pub fn failures(&self) -> usize {
+        self.instances.iter().filter(|i| i.error.is_some()).count()
+    }
+}
+
+/// The time zone timestamps are shown in. Only display changes: stored, serialized and
+/// exported times stay in UTC.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisplayZone {
+    #[default]
+    Utc,
+    /// The system's zone, as chrono sees it (honoring `TZ`).
+    Local,
+}
+
+impl DisplayZone {
+    /// The zone's name for titles and column headers.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Utc => "UTC",
+            Self::Local => "local",
+        }
+    }
+
+    /// `time` in this zone, formatted with `format` and no zone suffix.
+    pub fn format(self, time: DateTime<Utc>, format: &str) -> String {
+        match self {
+            Self::Utc => time.format(format).to_string(),
+            Self::Local => time.with_timezone(&Local).format(format).to_string(),
+        }
+    }
+
+    /// The calendar day `time` falls on in this zone.
+    pub fn day_of(self, time: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Self::Utc => time.date_naive(),
+            Self::Local => time.with_timezone(&Local).date_naive(),
+        }
+    }
+
+    /// `time` as `YYYY-MM-DD HH:MM` followed by `UTC` or its local UTC offset.
+    pub fn stamp(self, time: DateTime<Utc>) -> String {
+        match self {
+            Self::Utc => self.format(time, "%Y-%m-%d %H:%M UTC"),
+            Self::Local => self.format(time, "%Y-%m-%d %H:%M %:z"),
+        }
+    }
+}
+
+/// An instance's weekly maintenance window, an hour starting at `hour` UTC on `day`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    pub day: Weekday,
+    pub hour: u32,
+}
+
+impl MaintenanceWindow {
+    /// Parses Cloud SQL's day (1 for Monday to 7 for Sunday, or a day name) and hour.
+    /// Day 0 means no window is set, so Cloud SQL may do maintenance at any time.
+    pub fn parse(day: &str, hour: &str) -> Option<Self> {
+        let day = match day.parse::<u32>() {
+            Ok(number @ 1..=7) => Weekday::try_from(number as u8 - 1).ok()?,
+            Ok(_) => return None,
+            Err(_) => day.parse().ok()?,
+        };
+        let hour = hour.parse().ok().filter(|hour| *hour < 24)?;
+        Some(Self { day, hour })
+    }
+
+    /// Whether `now` is inside the window or less than `lead` before it starts.
+    pub fn is_near(&self, now: DateTime<Utc>, lead: TimeDelta) -> bool {
+        let today = now.date_naive();
+        (-1..=1)
+            .filter_map(|offset| today.checked_add_signed(TimeDelta::days(offset)))
+            .filter(|date| date.weekday() == self.day)
+            .filter_map(|date| date.and_hms_opt(self.hour, 0, 0))
+            .map(|start| start.and_utc())
+            .any(|start| start - lead <= now && now < start + TimeDelta::hours(1))
+    }
+}
+
+impl fmt::Display for MaintenanceWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:02}:00 UTC", self.day, self.hour)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Backup {
     pub id: String,
     pub start_time: Option<DateTime<Utc>>,
     pub backup_type: String,
     pub status: String,
+    pub description: String,
+}
+
+impl Backup {
+    /// Type and status of a backup id the user typed in, whose metadata is unknown.
+    pub const MANUAL: &'static str = "Manual";
+
+    /// Whether the backup is known to have completed. Backups that failed or are still
+    /// being taken cannot be restored from.
+    pub fn completed(&self) -> bool {
+        self.status == "SUCCESSFUL"
+    }
+
+    /// Whether the status is unknown because the id was typed in rather than listed.
+    pub fn status_unknown(&self) -> bool {
+        self.status == Self::MANUAL
+    }
+
+    /// Labels embedded in the description by [`describe_with_labels`].
+    pub fn labels(&self) -> BTreeMap<String, String> {
+        parse_labels(&self.description)
+    }
+
+    /// Whether the backup was taken on `day` as seen in `tz`.
+    pub fn taken_on<Tz: TimeZone>(&self, day: NaiveDate, tz: &Tz) -> bool {
+        self.start_time
+            .is_some_and(|time| time.with_timezone(tz).date_naive() == day)
+    }
+
+    /// The start time with its zone, as in [`DisplayZone::stamp`], or "time unknown".
+    pub fn taken_at(&self, zone: DisplayZone) -> String {
+        self.start_time
+            .map(|t| zone.stamp(t))
+            .unwrap_or_else(|| "time unknown".to_string())
+    }
+
+    /// The start time as `YYYY-MM-DD HH:MM` in `zone`, or "Unknown". The time of day
+    /// tells apart backups taken on the same date.
+    pub fn date(&self, zone: DisplayZone) -> String {
+        self.start_time
+            .map(|t| zone.format(t, "%Y-%m-%d %H:%M"))
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+
+    /// A row of the backup list: date and time in `zone`, id and any labels.
+    pub fn display_line(&self, zone: DisplayZone) -> String {
+        let labels = self.labels();
+        let date = self.date(zone);
+        if labels.is_empty() {
+            format!("{} | {}", date, self.id)
+        } else {
+            format!("{} | {} [{}]", date, self.id, format_labels(&labels))
+        }
+    }
+}
+
+impl fmt::Display for Backup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (taken {}", self.id, self.taken_at(DisplayZone::Utc))?;
+        for field in [&self.backup_type, &self.status] {
+            if !field.is_empty() {
+                write!(f, ", {}", field)?;
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+/// Whether a backup that did not fail was taken on `day`; used to keep scheduled runs
+/// from creating a second backup on the same day.
+pub fn has_backup_on<Tz: TimeZone>(backups: &[Backup], day: NaiveDate, tz: &Tz) -> bool {
+    backups
+        .iter()
+        .any(|backup| backup.status != "FAILED" && backup.taken_on(day, tz))
+}
+
+/// One row of the retention view: the backups in an age range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgeBucket {
+    pub label: &'static str,
+    pub count: usize,
+    pub oldest: Option<DateTime<Utc>>,
+}
+
+/// Groups `backups` by age at `now`: the last 7 days, 8-30 and 31-90 days, and older.
+/// Backups without a start time get a final row of their own, if there are any.
+pub fn backups_by_age(backups: &[Backup], now: DateTime<Utc>) -> Vec<AgeBucket> {
+    const BUCKETS: [(&str, i64); 4] = [
+        ("Last 7 days", 7),
+        ("8-30 days", 30),
+        ("31-90 days", 90),
+        ("Older", i64::MAX),
+    ];
+    let mut rows: Vec<AgeBucket> = BUCKETS
+        .iter()
+        .map(|(label, _)| AgeBucket {
+            label,
+            count: 0,
+            oldest: None,
+        })
+        .collect();
+    let mut unknown = 0;
+    for backup in backups {
+        let Some(start) = backup.start_time else {
+            unknown += 1;
+            continue;
+        };
+        let age = (now - start).num_days();
+        let index = BUCKETS
+            .iter()
+            .position(|(_, days)| age < *days)
+            .unwrap_or(BUCKETS.len() - 1);
+        let row = &mut rows[index];
+        row.count += 1;
+        row.oldest = Some(row.oldest.map_or(start, |oldest| oldest.min(start)));
+    }
+    if unknown > 0 {
+        rows.push(AgeBucket {
+            label: "Time unknown",
+            count: unknown,
+            oldest: None,
+        });
+    }
+    rows
+}
+
+/// Splits a Cloud SQL database version such as `POSTGRES_15` or `MYSQL_8_0_31` into the
+/// engine and its numeric version parts. Trailing editions (`SQLSERVER_2019_STANDARD`)
+/// are dropped; versions with no number yield `None`.
+pub fn parse_database_version(version: &str) -> Option<(&str, Vec<u32>)> {
+    let mut parts = version.split('_');
+    let engine = parts.next().filter(|engine| !engine.is_empty())?;
+    let numbers: Vec<u32> = parts.map_while(|part| part.parse().ok()).collect();
+    (!numbers.is_empty()).then_some((engine, numbers))
+}
+
+/// Whether `source` is a newer version of the same engine than `target`, which Cloud SQL
+/// refuses to restore. Different engines or unparseable versions are not compared, and a
+/// version matches a more precise one it is a prefix of (`MYSQL_8_0` and `MYSQL_8_0_31`).
+pub fn is_newer_version(source: &str, target: &str) -> bool {
+    match (parse_database_version(source), parse_database_version(target)) {
+        (Some((source_engine, source)), Some((target_engine, target)))
+            if source_engine.eq_ignore_ascii_case(target_engine) =>
+        {
+            source.iter().zip(&target).find(|(s, t)| s != t).is_some_and(|(s, t)| s > t)
+        }
+        _ => false,
+    }
+}
+
+/// Labels as `key=value` pairs separated by commas, for display.
+pub fn format_labels(labels: &BTreeMap<String, String>) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Separates the free-text name from `key=value` labels in a backup description.
+pub const LABEL_SEPARATOR: &str = " | ";
+
+/// Parses `key=value` pairs separated by `;` from the part of a description after
+/// [`LABEL_SEPARATOR`]. A description without the separator has no labels, whatever it
+/// contains. Segments that are not `key=value` pairs are ignored.
+pub fn parse_labels(description: &str) -> BTreeMap<String, String> {
+    let Some((_, labels)) = description.rsplit_once(LABEL_SEPARATOR) else {
+        return BTreeMap::new();
+    };
+
+    labels
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Parses user-entered labels, rejecting any segment that is not a `key=value` pair.
+pub fn parse_label_input(input: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut labels = BTreeMap::new();
+    for pair in input.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        match pair.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                labels.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            _ => {
+                return Err(format!(
+                    "Invalid label '{}'. Use key=value pairs separated by ';'.",
+                    pair
+                ))
+            }
+        }
+    }
+    Ok(labels)
+}
+
+/// Builds a backup description of the form `name | key=value;key=value`.
+pub fn describe_with_labels(name: &str, labels: &BTreeMap<String, String>) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let labels = labels
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(";");
+    format!("{}{}{}", name, LABEL_SEPARATOR, labels)
+}
+
+/// An inclusive range of calendar days used to filter the backup list, in the zone the
+/// list is shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    /// Parses `YYYY-MM-DD` for a single day or `YYYY-MM-DD..YYYY-MM-DD` for a range.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let parse_date = |value: &str| {
+            NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| {
+                format!(
+                    "Invalid date '{}'. Use YYYY-MM-DD or YYYY-MM-DD..YYYY-MM-DD.",
+                    value.trim()
+                )
+            })
+        };
+
+        let (start, end) = match input.split_once("..") {
+            Some((start, end)) => (parse_date(start)?, parse_date(end)?),
+            None => {
+                let date = parse_date(input)?;
+                (date, date)
+            }
+        };
+        if start > end {
+            return Err(format!("Date range starts after it ends: {}..{}", start, end));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Whether `time` falls on one of the days as seen in `zone`.
+    pub fn contains(&self, time: DateTime<Utc>, zone: DisplayZone) -> bool {
+        let date = zone.day_of(time);
+        self.start <= date && date <= self.end
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}..{}", self.start, self.end)
+        }
+    }
+}
+
+/// Restore targets that need extra confirmation (`--protect-pattern`).
+///
+/// A regular expression, matched case-insensitively anywhere in the name. An empty
+/// pattern protects nothing.
+#[derive(Debug, Clone)]
+pub struct ProtectPattern {
+    source: String,
+    regex: Option<Regex>,
+}
+
+impl ProtectPattern {
+    pub const DEFAULT: &'static str = "prod";
+
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let regex = match pattern {
+            "" => None,
+            pattern => Some(
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| format!("Invalid protect pattern '{}': {}", pattern, e))?,
+            ),
+        };
+        Ok(Self {
+            source: pattern.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        self.regex.as_ref().is_some_and(|regex| regex.is_match(name))
+    }
+}
+
+impl Default for ProtectPattern {
+    fn default() -> Self {
+        Self::parse(Self::DEFAULT).expect("default protect pattern is valid")
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -28,12 +593,146 @@ pub struct Operation {
     pub error_message: Option<String>,
 }
 
+impl Operation {
+    /// Operation types Cloud SQL reports for a backup restore.
+    pub const RESTORE_TYPES: &'static [&'static str] = &["RESTORE_VOLUME", "UPDATE"];
+    /// Operation types Cloud SQL reports for an on-demand backup.
+    pub const BACKUP_TYPES: &'static [&'static str] = &["BACKUP_VOLUME", "BACKUP"];
+
+    /// Returns a warning if the operation's type is known and not one of `expected`.
+    pub fn type_mismatch(&self, expected: &[&str], label: &str) -> Option<String> {
+        if self.operation_type == "Unknown" || expected.contains(&self.operation_type.as_str()) {
+            None
+        } else {
+            Some(format!(
+                "Operation {} is a {} operation, not a {}",
+                self.id, self.operation_type, label
+            ))
+        }
+    }
+
+    /// Where the operation is in its lifecycle. A reported error means it failed,
+    /// whatever its status says.
+    pub fn phase(&self) -> OperationPhase {
+        if self.error_message.is_some() {
+            OperationPhase::Failed
+        } else {
+            OperationPhase::from_status(&self.status)
+        }
+    }
+
+    /// The operation on one line with its phase icon and start and end times, for
+    /// following it from a terminal.
+    pub fn watch_line(&self) -> String {
+        let mut line = format!("{} {}", self.phase().icon(), self);
+        let time = |t: DateTime<Utc>| t.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+        match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) => {
+                line.push_str(&format!(" [started {}, ended {}]", time(start), time(end)))
+            }
+            (Some(start), None) => line.push_str(&format!(" [started {}]", time(start))),
+            (None, Some(end)) => line.push_str(&format!(" [ended {}]", time(end))),
+            (None, None) => {}
+        }
+        line
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.operation_type, self.id, self.status)?;
+        if let Some(error) = &self.error_message {
+            write!(f, " - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds an [`Operation`] from its id and the operations API response, defaulting
+/// missing fields to `Unknown` and dropping timestamps that do not parse.
+impl From<(String, GcpApiResponse)> for Operation {
+    fn from((id, response): (String, GcpApiResponse)) -> Self {
+        Self {
+            id,
+            operation_type: response
+                .operation_type
+                .unwrap_or_else(|| "Unknown".to_string()),
+            status: response.status.unwrap_or_else(|| "Unknown".to_string()),
+            target_id: response.target_id.unwrap_or_else(|| "Unknown".to_string()),
+            start_time: response.start_time.and_then(|s| s.parse().ok()),
+            end_time: response.end_time.and_then(|s| s.parse().ok()),
+            error_message: response.error.map(|e| e.message),
+        }
+    }
+}
+
+/// Where an operation is in its lifecycle, as interpreted from its Cloud SQL status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationPhase {
+    /// Accepted but not started; Cloud SQL runs one operation per instance at a time, so
+    /// this is usually a wait on another operation.
+    Queued,
+    Executing,
+    Done,
+    Failed,
+    /// No status yet, or one this tool does not know.
+    Checking,
+}
+
+impl OperationPhase {
+    pub fn from_status(status: &str) -> Self {
+        match status {
+            "PENDING" => Self::Queued,
+            "RUNNING" => Self::Executing,
+            "DONE" => Self::Done,
+            "FAILED" | "ERROR" => Self::Failed,
+            _ => Self::Checking,
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::Queued => "⏳",
+            Self::Executing => "🔄",
+            Self::Done => "✅",
+            Self::Failed => "❌",
+            Self::Checking => "📊",
+        }
+    }
+
+    /// A sentence on what Cloud SQL is doing with an operation of `operation_type`.
+    pub fn detail(self, operation_type: Option<&str>) -> String {
+        let what = match operation_type {
+            Some("RESTORE_VOLUME") => "the backup restore".to_string(),
+            Some("BACKUP_VOLUME") | Some("BACKUP") => "the backup".to_string(),
+            Some("UPDATE") => "the instance update".to_string(),
+            Some(other) if other != "Unknown" => {
+                format!("the {} operation", other.to_lowercase().replace('_', " "))
+            }
+            _ => "the operation".to_string(),
+        };
+        match self {
+            Self::Queued => format!(
+                "Waiting to start {}; another operation on the instance may need to finish first.",
+                what
+            ),
+            Self::Executing => format!("Cloud SQL is executing {}.", what),
+            Self::Done => format!("Cloud SQL finished {}.", what),
+            Self::Failed => format!("Cloud SQL reported {} as failed.", what),
+            Self::Checking => "Waiting for the first status update...".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RestoreRequest {
     #[serde(rename = "restoreBackupContext")]
     pub restore_backup_context: RestoreBackupContext,
 }
 
+/// The Admin API's `restoreBackupContext`. It has no database filter: a restore always
+/// replaces the whole target instance, so [`RestoreConfig::intended_databases`] is never
+/// sent, and an unknown field would make the API reject the request.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RestoreBackupContext {
     #[serde(rename = "backupRunId")]
@@ -43,6 +742,34 @@ pub struct RestoreBackupContext {
     pub instance_id: String,
 }
 
+impl From<&RestoreConfig> for RestoreRequest {
+    fn from(config: &RestoreConfig) -> Self {
+        Self {
+            restore_backup_context: RestoreBackupContext {
+                backup_run_id: config.backup_id.clone(),
+                project: config.source_project.clone(),
+                instance_id: config.source_instance.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateBackupRequest {
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl From<&CreateBackupConfig> for CreateBackupRequest {
+    fn from(config: &CreateBackupConfig) -> Self {
+        Self {
+            description: config.description.clone(),
+            location: config.location.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GcpApiResponse {
     pub name: Option<String>,
@@ -70,10 +797,17 @@ pub enum AppState {
     SelectingSourceProject,
     SelectingSourceInstance,
     SelectingBackup,
+    /// Read-only list of every backup in the source project; picking one fills in the
+    /// source instance and backup.
+    BrowsingProjectBackups,
     SelectingTargetProject,
     SelectingTargetInstance,
     ConfirmRestore,
     PerformingRestore,
+    /// Operations persisted by earlier sessions, with their latest known status.
+    MonitoringStoredOperations,
+    /// Following one operation given with `--watch`, outside of either flow.
+    WatchingOperation,
     Error(String),
 
     // States for creating a backup
@@ -84,13 +818,39 @@ pub enum AppState {
     PerformingCreateBackup,
 }
 
+impl AppState {
+    /// The operation a step of a flow belongs to, or `None` outside the two flows.
+    pub fn operation_mode(&self) -> Option<OperationMode> {
+        match self {
+            AppState::SelectingSourceProject
+            | AppState::SelectingSourceInstance
+            | AppState::SelectingBackup
+            | AppState::BrowsingProjectBackups
+            | AppState::SelectingTargetProject
+            | AppState::SelectingTargetInstance
+            | AppState::ConfirmRestore
+            | AppState::PerformingRestore => Some(OperationMode::Restore),
+            AppState::SelectingProjectForBackup
+            | AppState::SelectingInstanceForBackup
+            | AppState::EnteringBackupName
+            | AppState::ConfirmCreateBackup
+            | AppState::PerformingCreateBackup => Some(OperationMode::CreateBackup),
+            AppState::SelectingOperation
+            | AppState::CheckingPrerequisites
+            | AppState::MonitoringStoredOperations
+            | AppState::WatchingOperation
+            | AppState::Error(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputMode {
     Normal,
     Editing,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum OperationMode {
     Restore,
     CreateBackup,
@@ -103,6 +863,29 @@ pub struct RestoreConfig {
     pub source_instance: String,
     pub target_project: String,
     pub target_instance: String,
+    /// Databases the user cares about. Informational only: Cloud SQL always restores
+    /// the whole instance.
+    pub intended_databases: Vec<String>,
+    /// Why the restore is being done, recorded in the operation history.
+    pub reason: Option<String>,
+}
+
+impl RestoreConfig {
+    /// The instance the backup was taken from, as `project/instance`.
+    pub fn source(&self) -> String {
+        format!("{}/{}", self.source_project, self.source_instance)
+    }
+
+    /// The instance being overwritten, as `project/instance`.
+    pub fn target(&self) -> String {
+        format!("{}/{}", self.target_project, self.target_instance)
+    }
+}
+
+impl fmt::Display for RestoreConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "backup {} of {} onto {}", self.backup_id, self.source(), self.target())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -111,4 +894,23 @@ pub struct CreateBackupConfig {
     pub instance: String,
     pub name: String,
     pub description: String,
+    /// Region or multi-region to store the backup in. `None` leaves it to Cloud SQL.
+    pub location: Option<String>,
+}
+
+impl CreateBackupConfig {
+    /// The instance being backed up, as `project/instance`.
+    pub fn target(&self) -> String {
+        format!("{}/{}", self.project, self.instance)
+    }
+}
+
+impl fmt::Display for CreateBackupConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' of {}", self.name, self.target())?;
+        if let Some(location) = &self.location {
+            write!(f, " in {}", location)?;
+        }
+        Ok(())
+    }
 }
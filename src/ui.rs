@@ -1,4 +1,5 @@
 use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::{
     backend::Backend,
@@ -6,14 +7,20 @@ use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
-        Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap, BorderType
+        Block, Borders, Cell, Clear, ListItem, Paragraph, Row, Table, Wrap, BorderType,
     },
     Frame, Terminal,
 };
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use crate::app::App;
-use crate::types::{AppState, InputMode, OperationMode};
+use crate::app::{App, MAX_RESTORE_RETRIES};
+use crate::keymap::Action;
+use crate::types::{
+    backups_by_age, format_labels, AppState, Backup, DisplayZone, InputMode, Operation,
+    OperationMode, OperationPhase, SqlInstance,
+};
 
 // Clean color palette for better visibility and modern look
 const BASE_FG: Color = Color::Rgb(216, 222, 233);          // Main text
@@ -25,14 +32,24 @@ const HIGHLIGHT_BG: Color = Color::Rgb(59, 66, 82);        // Selection backgrou
 const BORDER_COLOR: Color = Color::Rgb(76, 86, 106);       // Inactive borders
 const INPUT_TEXT: Color = Color::Rgb(235, 203, 139);       // Input text - bright and visible
 
-pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result<()> {
+/// Runs the TUI until the user quits, handing the app back for the exit summary.
+pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Result<App> {
+    let shutdown_requested = spawn_shutdown_listener();
     app.initialize().await?;
+    // From here on, GCP requests made by key handlers and status ticks run on background
+    // tasks and report back over a channel that is drained every tick, so the screen keeps
+    // redrawing (with the panel's spinner) while gcloud is slow. Startup replays above
+    // still wait for them.
+    app.enable_background_requests();
     let mut last_tick = Instant::now();
     let mut last_status_check = Instant::now();
+    // Restarted by every key press, so auto-refresh only fires once the user is idle.
+    let mut last_list_refresh = Instant::now();
     let tick_rate = Duration::from_millis(250);
     let status_check_interval = Duration::from_secs(5);
 
     loop {
+        app.apply_background_results().await;
         terminal.draw(|f| ui(f, &mut app))?;
 
         let timeout = tick_rate
@@ -42,16 +59,24 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Re
         if crossterm::event::poll(timeout)? {
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
-                    match app.input_mode {
-                        InputMode::Normal => {
-                            if let Err(e) =
-                                handle_normal_input(&mut app, key.code, key.modifiers).await
-                            {
-                                app.state = AppState::Error(e.to_string());
+                    last_list_refresh = Instant::now();
+                    // Raw mode swallows SIGINT, so Ctrl-C arrives as a key press.
+                    if key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        app.should_quit = true;
+                    } else {
+                        match app.input_mode {
+                            InputMode::Normal => {
+                                if let Err(e) =
+                                    handle_normal_input(&mut app, key.code, key.modifiers).await
+                                {
+                                    app.state = AppState::Error(e.to_string());
+                                }
+                            }
+                            InputMode::Editing => {
+                                handle_edit_input(&mut app, key.code).await?;
                             }
-                        }
-                        InputMode::Editing => {
-                            handle_edit_input(&mut app, key.code).await?;
                         }
                     }
                 }
@@ -62,60 +87,151 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> Re
             last_tick = Instant::now();
         }
 
-        if last_status_check.elapsed() >= status_check_interval {
+        let _ = app.retry_restore_if_due().await;
+
+        if app.auto_refresh.is_some_and(|interval| last_list_refresh.elapsed() >= interval) {
+            if app.idle_in_list() {
+                let _ = app.refresh_list().await;
+            }
+            last_list_refresh = Instant::now();
+        }
+
+        if !app.polling_paused && last_status_check.elapsed() >= status_check_interval {
             if app.restore_flow.operation_id.is_some() {
                 let _ = app.check_restore_status().await;
             }
-            if app.create_backup_flow.operation_id.is_some() {
+            if app.create_backup_flow.has_operations() {
                 let _ = app.check_backup_status().await;
             }
+            if matches!(app.state, AppState::MonitoringStoredOperations) {
+                let _ = app.poll_stored_operations().await;
+            } else {
+                app.poll_detached_operations().await;
+            }
+            if matches!(app.state, AppState::WatchingOperation) {
+                app.poll_watched_operation().await;
+            }
+            app.poll_instance_state().await;
             last_status_check = Instant::now();
         }
 
+        if shutdown_requested.load(Ordering::SeqCst) {
+            app.should_quit = true;
+        }
+
+        if app.should_quit {
+            break;
+        }
+
         if matches!(app.state, AppState::Error(_)) && !app.show_help {
             break;
         }
     }
 
-    Ok(())
+    Ok(app)
 }
 
-pub async fn handle_normal_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
-    match key {
-        KeyCode::Char('q') => {
-            // In a test environment, we don't want to exit the process.
-            if !cfg!(test) {
-                std::process::exit(0);
+/// Listens for SIGINT/SIGTERM (Ctrl-C/Ctrl-Break/close on Windows) in the background.
+/// The returned flag is checked by the event loop so it can exit through the normal
+/// terminal-restore path instead of dying with the terminal still in raw mode.
+fn spawn_shutdown_listener() -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let listener_flag = flag.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        listener_flag.store(true, Ordering::SeqCst);
+    });
+    flag
+}
+
+#[cfg(unix)]
+async fn shutdown_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = sigterm.recv() => {}
             }
         }
-        KeyCode::Esc => {
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(windows)]
+async fn shutdown_signal() {
+    use tokio::signal::windows::{ctrl_break, ctrl_close};
+
+    match (ctrl_break(), ctrl_close()) {
+        (Ok(mut ctrl_break), Ok(mut ctrl_close)) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = ctrl_break.recv() => {}
+                _ = ctrl_close.recv() => {}
+            }
+        }
+        _ => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+pub async fn handle_normal_input(app: &mut App, key: KeyCode, _modifiers: KeyModifiers) -> Result<()> {
+    if app.resume_prompt {
+        match app.keymap.action_for(key) {
+            Some(Action::Select) => app.resume_stored_operations().await?,
+            Some(Action::Back) => app.resume_prompt = false,
+            Some(Action::Quit) => app.should_quit = true,
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    match app.keymap.action_for(key) {
+        Some(Action::Quit) => app.should_quit = true,
+        Some(Action::Back) => {
             if app.error.is_some() {
                 app.error = None;
+                app.error_scroll = 0;
+            } else if app.dry_run_preview.is_some() {
+                app.dry_run_preview = None;
+            } else if app.gcloud_preview.is_some() {
+                app.gcloud_preview = None;
+            } else if app.show_operation_details {
+                app.show_operation_details = false;
+            } else if app.restore_flow.show_comparison {
+                app.restore_flow.show_comparison = false;
+            } else if app.restore_flow.show_retention {
+                app.restore_flow.show_retention = false;
             } else if app.show_help {
                 app.toggle_help();
             } else if app.manual_input_active {
                 app.cancel_manual_input();
             } else {
+                app.invalidate_pending_requests();
                 match app.state {
+                    AppState::WatchingOperation => app.should_quit = true,
                     AppState::ConfirmRestore => {
                         app.restore_flow.target_instance = None;
-                        app.restore_flow.selected_instance_index = 0;
                         app.state = AppState::SelectingTargetInstance;
                     }
                     AppState::ConfirmCreateBackup => {
                         app.create_backup_flow.config = None;
+                        app.create_backup_flow.batch_configs.clear();
                         app.state = AppState::EnteringBackupName;
+                        app.edit_backup_name();
                     }
                     AppState::SelectingSourceInstance => {
                         app.restore_flow.source_project = None;
                         app.restore_flow.instances.clear();
-                        app.restore_flow.selected_instance_index = 0;
                         app.state = AppState::SelectingSourceProject;
                     }
-                    AppState::SelectingBackup => {
-                        app.restore_flow.source_instance = None;
-                        app.restore_flow.backups.clear();
-                        app.restore_flow.selected_backup_index = 0;
+                    AppState::SelectingBackup => app.back_to_source_instances().await?,
+                    AppState::BrowsingProjectBackups => {
+                        app.restore_flow.project_backups.clear();
                         app.state = AppState::SelectingSourceInstance;
                     }
                     AppState::SelectingTargetProject => {
@@ -125,16 +241,16 @@ pub async fn handle_normal_input(app: &mut App, key: KeyCode, modifiers: KeyModi
                     AppState::SelectingTargetInstance => {
                         app.restore_flow.target_project = None;
                         app.restore_flow.instances.clear();
-                        app.restore_flow.selected_instance_index = 0;
                         app.state = AppState::SelectingTargetProject;
                     }
                     AppState::PerformingRestore => {
+                        app.restore_flow.retry_at = None;
                         app.state = AppState::SelectingTargetInstance;
                     }
                     AppState::SelectingInstanceForBackup => {
                         app.create_backup_flow.project = None;
                         app.create_backup_flow.instances.clear();
-                        app.create_backup_flow.selected_instance_index = 0;
+                        app.create_backup_flow.marked_instances.clear();
                         app.state = AppState::SelectingProjectForBackup;
                     }
                     AppState::EnteringBackupName => {
@@ -144,17 +260,23 @@ pub async fn handle_normal_input(app: &mut App, key: KeyCode, modifiers: KeyModi
                     AppState::PerformingCreateBackup => {
                         app.state = AppState::ConfirmCreateBackup;
                     }
-                    _ => {
-                        app.state = AppState::SelectingOperation;
-                    }
+                    _ => app.reset_to_operation_selection(),
                 }
             }
         }
-        KeyCode::Char('h') => app.toggle_help(),
-        KeyCode::Up => app.move_selection_up(),
-        KeyCode::Down => app.move_selection_down(),
-        KeyCode::Enter => app.select_current_item().await?,
-        KeyCode::Char('m') => match app.state {
+        Some(Action::Help) => app.toggle_help(),
+        Some(Action::Up) if app.error.is_some() => {
+            app.error_scroll = app.error_scroll.saturating_sub(1)
+        }
+        Some(Action::Down) if app.error.is_some() => {
+            app.error_scroll = app.error_scroll.saturating_add(1)
+        }
+        Some(Action::Up) if app.show_help => app.help_scroll = app.help_scroll.saturating_sub(1),
+        Some(Action::Down) if app.show_help => app.help_scroll = app.help_scroll.saturating_add(1),
+        Some(Action::Up) => app.move_selection_up(),
+        Some(Action::Down) => app.move_selection_down(),
+        Some(Action::Select) => app.select_current_item().await?,
+        Some(Action::Manual) => match app.state {
             AppState::SelectingSourceProject
             | AppState::SelectingTargetProject
             | AppState::SelectingProjectForBackup => {
@@ -168,57 +290,95 @@ pub async fn handle_normal_input(app: &mut App, key: KeyCode, modifiers: KeyModi
             AppState::SelectingBackup => {
                 app.start_manual_input("backup");
             }
-            AppState::EnteringBackupName => {
-                app.start_manual_input("backup_name");
-            }
+            AppState::EnteringBackupName => app.edit_backup_name(),
             _ => {}
         },
-        KeyCode::Char('r') => {
-            match app.state {
-                AppState::SelectingSourceInstance | AppState::SelectingTargetInstance => {
-                    if let Some(project) = &app.restore_flow.source_project.clone() {
-                        app.load_instances(project).await?;
-                    }
-                }
-                AppState::SelectingInstanceForBackup => {
-                    if let Some(project) = &app.create_backup_flow.project.clone() {
-                        app.load_instances(project).await?;
-                    }
-                }
-                AppState::SelectingBackup => {
-                    if let (Some(project), Some(instance)) = (
-                        &app.restore_flow.source_project.clone(),
-                        &app.restore_flow.source_instance.clone(),
-                    ) {
-                        app.load_backups(project, instance).await?;
-                    }
-                }
-                _ => {}
+        Some(Action::Refresh) => {
+            if matches!(app.state, AppState::MonitoringStoredOperations) {
+                app.poll_stored_operations().await?;
             }
+            if matches!(app.state, AppState::WatchingOperation) {
+                app.poll_watched_operation().await;
+            }
+            app.refresh_list().await?;
             if app.restore_flow.operation_id.is_some() {
                 app.check_restore_status().await?;
             }
-            if app.create_backup_flow.operation_id.is_some() {
+            if app.create_backup_flow.has_operations() {
                 app.check_backup_status().await?;
             }
         }
-        KeyCode::Char('n') => {
-            app.state = AppState::SelectingOperation;
-            app.operation_mode = None;
-            app.restore_flow = crate::state::restore_flow::RestoreFlow::new();
-            app.create_backup_flow = crate::state::create_backup_flow::CreateBackupFlow::new();
+        // `--watch` shows only its operation, so there is no flow to start over.
+        Some(Action::New) if matches!(app.state, AppState::WatchingOperation) => {}
+        Some(Action::New) => app.reset_to_operation_selection(),
+        // `action_for` only finds global actions; the rest depend on the step.
+        _ => {
+            let bound = |action| app.keymap.binds(action, key);
+            match app.state {
+                _ if bound(Action::AccessToken) => app.refresh_access_token().await?,
+                AppState::SelectingOperation if bound(Action::PastOperations) => {
+                    app.resume_stored_operations().await?;
+                }
+                _ if bound(Action::PausePolling) && polling_toggle_available(app) => {
+                    app.toggle_polling()
+                }
+                _ if bound(Action::CompactFooter) => app.compact_footer = !app.compact_footer,
+                AppState::SelectingSourceInstance if bound(Action::ProjectBackups) => {
+                    if let Some(project) = app.restore_flow.source_project.clone() {
+                        app.state = AppState::BrowsingProjectBackups;
+                        app.load_all_backups(&project).await?;
+                    }
+                }
+                AppState::BrowsingProjectBackups if bound(Action::Sort) => {
+                    app.toggle_project_backup_sort();
+                }
+                AppState::SelectingTargetProject if bound(Action::SameAsSource) => {
+                    app.use_source_project_as_target().await?;
+                }
+                AppState::SelectingTargetInstance if bound(Action::SameAsSource) => {
+                    app.use_source_instance_as_target().await?;
+                }
+                AppState::SelectingBackup if bound(Action::Mark) => {
+                    app.restore_flow.toggle_compare_mark();
+                }
+                AppState::SelectingBackup if bound(Action::Compare) => {
+                    app.open_backup_comparison();
+                }
+                AppState::SelectingBackup if bound(Action::DateFilter) => {
+                    app.start_manual_input("backup_date_filter");
+                }
+                AppState::SelectingBackup if bound(Action::Retention) => {
+                    app.open_retention_view();
+                }
+                AppState::SelectingInstanceForBackup if bound(Action::Mark) => {
+                    app.toggle_instance_mark();
+                }
+                AppState::ConfirmCreateBackup if bound(Action::Labels) => {
+                    app.start_manual_input("backup_labels");
+                }
+                AppState::ConfirmCreateBackup if bound(Action::Location) => {
+                    app.start_manual_input("backup_location");
+                }
+                _ if bound(Action::Details) && app.has_operation_details() => {
+                    app.show_operation_details()
+                }
+                AppState::ConfirmRestore if bound(Action::Databases) => {
+                    app.start_manual_input("databases");
+                }
+                _ if bound(Action::GcloudCommand) && gcloud_command_available(app) => {
+                    app.show_gcloud_command()
+                }
+                _ => {}
+            }
         }
-        _ => {}
     }
     Ok(())
 }
 
 pub async fn handle_edit_input(app: &mut App, key: KeyCode) -> Result<()> {
     match key {
-        KeyCode::Enter => {
-            if app.manual_input_active {
-                app.finish_manual_input().await?;
-            }
+        KeyCode::Enter if app.manual_input_active => {
+            app.finish_manual_input().await?;
         }
         KeyCode::Esc => {
             if app.manual_input_active {
@@ -247,7 +407,7 @@ pub async fn handle_edit_input(app: &mut App, key: KeyCode) -> Result<()> {
     Ok(())
 }
 
-fn ui(f: &mut Frame, app: &mut App) {
+pub fn ui(f: &mut Frame, app: &mut App) {
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -264,25 +424,419 @@ fn ui(f: &mut Frame, app: &mut App) {
     if app.show_help {
         render_help_popup(f, app);
     }
-    if app.manual_input_active {
-        render_manual_input_popup(f, app);
-    }
     if matches!(app.state, AppState::ConfirmRestore) {
         render_restore_warning_popup(f, app);
     }
     if matches!(app.state, AppState::ConfirmCreateBackup) {
         render_create_backup_warning_popup(f, app);
     }
+    if app.manual_input_active {
+        render_manual_input_popup(f, app);
+    }
+    if app.dry_run_preview.is_some() {
+        render_dry_run_preview_popup(f, app);
+    }
+    if app.gcloud_preview.is_some() {
+        render_gcloud_preview_popup(f, app);
+    }
+    if app.show_operation_details && app.has_operation_details() {
+        render_operation_details_popup(f, app);
+    }
+    if app.restore_flow.show_comparison {
+        render_backup_comparison_popup(f, app);
+    }
+    if app.restore_flow.show_retention {
+        render_retention_popup(f, app);
+    }
+    if app.resume_prompt {
+        render_resume_prompt_popup(f, app);
+    }
     if app.error.is_some() {
         render_error_popup(f, app);
     }
 }
 
+fn render_backup_comparison_popup(f: &mut Frame, app: &App) {
+    if let Some((first, second)) = app.restore_flow.compared_backups() {
+        let popup_area = centered_rect(80, 50, f.area());
+        f.render_widget(Clear, popup_area);
+
+        let fields = |backup: &Backup| {
+            [
+                backup.id.clone(),
+                backup.taken_at(app.display_zone),
+                backup.backup_type.clone(),
+                backup.status.clone(),
+                backup.description.clone(),
+            ]
+        };
+        let rows: Vec<Row> = ["ID", "Taken", "Type", "Status", "Description"]
+            .into_iter()
+            .zip(fields(first).into_iter().zip(fields(second)))
+            .map(|(label, (a, b))| {
+                let style = if a == b {
+                    Style::default().fg(BASE_FG)
+                } else {
+                    Style::default().fg(WARNING_COLOR)
+                };
+                Row::new(vec![
+                    Cell::from(label).style(Style::default().fg(ACCENT_COLOR)),
+                    Cell::from(a),
+                    Cell::from(b),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ],
+        )
+        .header(
+            Row::new(vec!["", "[1]", "[2]"])
+                .style(Style::default().fg(BASE_FG).add_modifier(Modifier::BOLD)),
+        )
+        .block(
+            Block::default()
+                .title("Compare Backups - differences highlighted, Esc to close")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(BASE_FG).bg(Color::Black)),
+        );
+
+        f.render_widget(table, popup_area);
+    }
+}
+
+fn render_retention_popup(f: &mut Frame, app: &App) {
+    let popup_area = centered_rect_fixed(60, 10, f.area());
+    f.render_widget(Clear, popup_area);
+
+    let rows: Vec<Row> = backups_by_age(app.restore_flow.backups.items(), Utc::now())
+        .into_iter()
+        .map(|bucket| {
+            let style = if bucket.count == 0 {
+                Style::default().fg(BORDER_COLOR)
+            } else {
+                Style::default().fg(BASE_FG)
+            };
+            let oldest = bucket
+                .oldest
+                .map(|time| app.display_zone.format(time, "%Y-%m-%d"))
+                .unwrap_or_default();
+            Row::new(vec![
+                Cell::from(bucket.label).style(Style::default().fg(ACCENT_COLOR)),
+                Cell::from(bucket.count.to_string()),
+                Cell::from(oldest),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["Age", "Backups", "Oldest"])
+            .style(Style::default().fg(BASE_FG).add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .title(format!(
+                "Backups of {} by Age - Esc to close",
+                app.restore_flow.source_instance.as_deref().unwrap_or("instance")
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(Style::default().fg(BASE_FG).bg(Color::Black)),
+    );
+
+    f.render_widget(table, popup_area);
+}
+
+fn render_gcloud_preview_popup(f: &mut Frame, app: &App) {
+    if let Some(commands) = &app.gcloud_preview {
+        let popup_area = centered_rect(80, 40, f.area());
+        f.render_widget(Clear, popup_area);
+
+        let mut text = vec![
+            Line::from(Span::styled(
+                "Run this to do the same with gcloud (nothing is executed here):",
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        text.extend(commands.lines().map(Line::from));
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "Press Esc to close",
+            Style::default().fg(Color::Yellow),
+        )));
+
+        let block = Block::default()
+            .title("Equivalent gcloud Command")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(Style::default().fg(BASE_FG).bg(Color::Black));
+
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, popup_area);
+    }
+}
+
+fn render_operation_details_popup(f: &mut Frame, app: &App) {
+    let popup_area = centered_rect(70, 50, f.area());
+    f.render_widget(Clear, popup_area);
+
+    let mut text = match app.operation_details() {
+        Some(op) => operation_detail_lines(app, op),
+        None => vec![Line::from(OperationPhase::Checking.detail(None))],
+    };
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "Press Esc to close",
+        Style::default().fg(Color::Yellow),
+    )));
+
+    let block = Block::default()
+        .title("Operation Details")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(BASE_FG).bg(Color::Black));
+
+    f.render_widget(
+        Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+/// One labelled line per field of `op`: its status, type, target, times and error.
+fn operation_detail_lines(app: &App, op: &Operation) -> Vec<Line<'static>> {
+    let field = |label: &str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<10}", label),
+                Style::default().fg(ACCENT_COLOR).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(value, style),
+        ])
+    };
+    let plain = Style::default().fg(BASE_FG);
+    let phase = op.phase();
+    let time = |time: Option<DateTime<Utc>>| {
+        time.map(|t| app.display_zone.stamp(t))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    vec![
+        field("Operation", op.id.clone(), plain),
+        field("Type", op.operation_type.clone(), plain),
+        field(
+            "Status",
+            format!("{} {}", phase.icon(), op.status),
+            Style::default().fg(phase_color(phase)),
+        ),
+        field("Target", op.target_id.clone(), plain),
+        field("Started", time(op.start_time), plain),
+        field("Ended", time(op.end_time), plain),
+        field(
+            "Error",
+            op.error_message.clone().unwrap_or_else(|| "none".to_string()),
+            plain,
+        ),
+    ]
+}
+
+/// The `--watch` screen: the followed operation's details, refreshed until it finishes.
+fn render_watched_operation(f: &mut Frame, area: Rect, app: &App) {
+    let Some(flow) = &app.watch_flow else {
+        return;
+    };
+    let mut text = match &flow.operation {
+        Some(op) => operation_detail_lines(app, op),
+        None => vec![Line::from(OperationPhase::Checking.detail(None))],
+    };
+    if let Some(error) = &flow.poll_error {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            format!("{} Retrying.", error),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    text.push(Line::from(""));
+    let hint = if flow.is_finished() {
+        format!("Finished. Press {} to quit.", app.keymap.label(Action::Quit))
+    } else {
+        format!("Press {} to stop watching.", app.keymap.label(Action::Quit))
+    };
+    text.push(Line::from(Span::styled(hint, Style::default().fg(Color::Yellow))));
+
+    let block = Block::default()
+        .title(format!("Watching {} in {}", flow.operation_id, flow.project))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .style(Style::default().fg(ACCENT_COLOR));
+
+    f.render_widget(
+        Paragraph::new(text).block(block).wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn render_dry_run_preview_popup(f: &mut Frame, app: &App) {
+    if let Some(preview) = &app.dry_run_preview {
+        let popup_area = centered_rect(70, 60, f.area());
+        f.render_widget(Clear, popup_area);
+
+        let mut preview_text = vec![
+            Line::from(Span::styled(
+                "No request was sent. This is what would have been submitted:",
+                Style::default()
+                    .fg(WARNING_COLOR)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        preview_text.extend(preview.lines().map(Line::from));
+        preview_text.push(Line::from(""));
+        preview_text.push(Line::from(Span::styled(
+            "Press Esc to close",
+            Style::default().fg(Color::Yellow),
+        )));
+
+        let block = Block::default()
+            .title("Dry Run - Request Preview")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(Style::default().fg(BASE_FG).bg(Color::Black));
+
+        let paragraph = Paragraph::new(preview_text)
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, popup_area);
+    }
+}
+
+fn render_resume_prompt_popup(f: &mut Frame, app: &App) {
+    let popup_area = centered_rect(60, 30, f.area());
+    f.render_widget(Clear, popup_area);
+
+    let count = app.operation_store.in_flight.len();
+    let mut text = vec![
+        Line::from(Span::styled(
+            format!(
+                "{} operation{} from a previous session may still be running:",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            Style::default()
+                .fg(WARNING_COLOR)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    text.extend(app.operation_store.in_flight.iter().map(|op| {
+        Line::from(format!(
+            "{:?} in {} ({}), started {}",
+            op.mode,
+            op.project,
+            op.operation_id,
+            app.display_zone.stamp(op.started_at)
+        ))
+    }));
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        format!(
+            "[{}] Resume monitoring | [{}] Dismiss",
+            app.keymap.label(Action::Select),
+            app.keymap.label(Action::Back)
+        ),
+        Style::default().fg(Color::Yellow),
+    )));
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title("Unfinished Operations")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(BASE_FG).bg(Color::Black)),
+        )
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, popup_area);
+}
+
+fn render_stored_operations(f: &mut Frame, area: Rect, app: &App) {
+    let store = &app.operation_store;
+    let rows: Vec<Row> = store
+        .in_flight
+        .iter()
+        .chain(store.history.iter())
+        .map(|op| {
+            let phase = OperationPhase::from_status(&op.status);
+            let (icon, color) = (phase.icon(), phase_color(phase));
+            Row::new(vec![
+                Cell::from(format!("{:?}", op.mode)),
+                Cell::from(op.project.clone()),
+                Cell::from(app.display_zone.format(op.started_at, "%Y-%m-%d %H:%M")),
+                Cell::from(format!("{} {}", icon, op.status)),
+                Cell::from(op.error.clone().unwrap_or_else(|| op.operation_id.clone())),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(13),
+            Constraint::Percentage(20),
+            Constraint::Length(17),
+            Constraint::Length(12),
+            Constraint::Min(0),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Operation".to_string(),
+            "Project".to_string(),
+            format!("Started ({})", app.display_zone.label()),
+            "Status".to_string(),
+            "Details".to_string(),
+        ])
+            .style(Style::default().fg(BASE_FG).add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .title(format!(
+                "Stored Operations - {} in flight, {} finished",
+                store.in_flight.len(),
+                store.history.len()
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(Style::default().fg(ACCENT_COLOR)),
+    );
+
+    f.render_widget(table, area);
+}
+
 fn render_error_popup(f: &mut Frame, app: &mut App) {
     if let Some(error_msg) = &app.error {
-        let popup_area = centered_rect(60, 25, f.area());
-        f.render_widget(Clear, popup_area); //this clears the background
-
+        let area = f.area();
         let error_text = vec![
             Line::from(""),
             Line::from(Span::styled(
@@ -290,11 +844,33 @@ fn render_error_popup(f: &mut Frame, app: &mut App) {
                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
-            Line::from(error_msg.as_str()),
-        ];
+        ]
+        .into_iter()
+        .chain(error_msg.lines().map(Line::from))
+        .collect::<Vec<_>>();
+
+        // Grow with the message up to most of the screen, then scroll.
+        let width = (area.width * 60 / 100).max(40.min(area.width));
+        let content_height = wrapped_height(&error_text, width.saturating_sub(2));
+        let max_height = (area.height * 80 / 100).max(7.min(area.height));
+        let height = content_height.saturating_add(2).clamp(7.min(max_height), max_height);
+        let popup_area = centered_rect_fixed(width, height, area);
+        f.render_widget(Clear, popup_area); //this clears the background
+
+        let max_scroll = content_height.saturating_sub(popup_area.height.saturating_sub(2));
+        app.error_scroll = app.error_scroll.min(max_scroll);
 
+        let title = if max_scroll > 0 {
+            format!(
+                "Error ({}/{} to scroll)",
+                app.keymap.label(Action::Up),
+                app.keymap.label(Action::Down)
+            )
+        } else {
+            "Error".to_string()
+        };
         let block = Block::default()
-            .title("Error")
+            .title(title)
             .borders(Borders::ALL)
             .border_type(BorderType::Double)
             .style(Style::default().fg(Color::Red));
@@ -302,12 +878,23 @@ fn render_error_popup(f: &mut Frame, app: &mut App) {
         let paragraph = Paragraph::new(error_text)
             .block(block)
             .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true });
+            .wrap(Wrap { trim: true })
+            .scroll((app.error_scroll, 0));
 
         f.render_widget(paragraph, popup_area);
     }
 }
 
+/// Rows `lines` take up when wrapped to `width` columns, saturating at `u16::MAX`.
+fn wrapped_height(lines: &[Line], width: u16) -> u16 {
+    let width = usize::from(width.max(1));
+    let height: usize = lines
+        .iter()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+    u16::try_from(height).unwrap_or(u16::MAX)
+}
+
 fn render_header(f: &mut Frame, area: Rect, app: &App) {
     let title = if app.dry_run_mode {
         " GCP SQL Backup Tool - DRY RUN MODE "
@@ -321,10 +908,13 @@ fn render_header(f: &mut Frame, area: Rect, app: &App) {
         AppState::SelectingSourceProject => "Step 1/5: Select Source Project",
         AppState::SelectingSourceInstance => "Step 2/5: Select Source Instance",
         AppState::SelectingBackup => "Step 3/5: Select Backup",
+        AppState::BrowsingProjectBackups => "Step 2/5: Select a Backup from Any Instance",
         AppState::SelectingTargetProject => "Step 4/5: Select Target Project",
         AppState::SelectingTargetInstance => "Step 5/5: Select Target Instance",
         AppState::ConfirmRestore => "Step 6: Confirm Restoration",
         AppState::PerformingRestore => "Monitoring Restore Progress...",
+        AppState::MonitoringStoredOperations => "Operations from Previous Sessions",
+        AppState::WatchingOperation => "Watching an Operation",
         AppState::SelectingProjectForBackup => "Step 1/4: Select Project for Backup",
         AppState::SelectingInstanceForBackup => "Step 2/4: Select Instance for Backup",
         AppState::EnteringBackupName => "Step 3/4: Enter Backup Name",
@@ -333,7 +923,7 @@ fn render_header(f: &mut Frame, area: Rect, app: &App) {
         AppState::Error(_) => "Error Occurred",
     };
 
-    let header_block = Block::default()
+    let mut header_block = Block::default()
         .title(title)
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
@@ -344,6 +934,10 @@ fn render_header(f: &mut Frame, area: Rect, app: &App) {
             Style::default().fg(BASE_FG).bg(BASE_BG)
         });
 
+    if let Some(identity) = identity_label(app) {
+        header_block = header_block.title(Line::from(identity).right_aligned());
+    }
+
     let header_content = Paragraph::new(subtitle)
         .style(Style::default().fg(ACCENT_COLOR))
         .alignment(Alignment::Center)
@@ -352,10 +946,39 @@ fn render_header(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(header_content, area);
 }
 
+/// Describes who operations run as, e.g. ` as: me@company.com (dry run) `.
+fn identity_label(app: &App) -> Option<String> {
+    let user = app.authenticated_user.as_ref()?;
+    let mut notes = Vec::new();
+    if user.ends_with(".gserviceaccount.com") {
+        notes.push("service account".to_string());
+    }
+    if app.dry_run_mode {
+        notes.push("dry run".to_string());
+    }
+    if app.restore_disabled() {
+        notes.push("restore disabled".to_string());
+    }
+    if let Some(refreshed_at) = app.token_refreshed_at {
+        notes.push(format!(
+            "token refreshed {}",
+            refreshed_at.with_timezone(&Local).format("%H:%M")
+        ));
+    }
+    Some(if notes.is_empty() {
+        format!(" as: {} ", user)
+    } else {
+        format!(" as: {} ({}) ", user, notes.join(", "))
+    })
+}
+
 fn render_content(f: &mut Frame, area: Rect, app: &mut App) {
     match &app.state {
         AppState::SelectingOperation => render_operation_selection(f, area, app),
         AppState::CheckingPrerequisites => render_loading(f, area, "Checking prerequisites..."),
+        AppState::BrowsingProjectBackups => render_project_backup_list(f, area, app),
+        AppState::MonitoringStoredOperations => render_stored_operations(f, area, app),
+        AppState::WatchingOperation => render_watched_operation(f, area, app),
         AppState::SelectingSourceProject
         | AppState::SelectingSourceInstance
         | AppState::SelectingBackup
@@ -437,7 +1060,7 @@ fn render_backup_instance_selection(f: &mut Frame, area: Rect, app: &mut App) {
             && app.create_backup_flow.instance.is_none()
         {
             Style::default().fg(ACCENT_COLOR)
-        } else if app.create_backup_flow.instance.is_some() {
+        } else if app.create_backup_flow.instance.is_some() || app.create_backup_flow.is_batch() {
             Style::default().fg(SUCCESS_COLOR)
         } else {
             Style::default().fg(BORDER_COLOR)
@@ -445,11 +1068,13 @@ fn render_backup_instance_selection(f: &mut Frame, area: Rect, app: &mut App) {
 
         let instance_content = if let Some(instance) = &app.create_backup_flow.instance {
             format!("✓ {}", instance)
+        } else if app.create_backup_flow.is_batch() {
+            format!("✓ {}", app.create_backup_flow.marked_instances.join(", "))
         } else if matches!(app.state, AppState::SelectingInstanceForBackup) {
-            if app.loading {
+            if app.loading.instances {
                 "→ Loading instances...".to_string()
             } else if app.create_backup_flow.instances.is_empty() {
-                "→ No instances found".to_string()
+                empty_instance_list_message(app)
             } else {
                 "→ Select instance...".to_string()
             }
@@ -461,7 +1086,7 @@ fn render_backup_instance_selection(f: &mut Frame, area: Rect, app: &mut App) {
             Paragraph::new(instance_content)
                 .block(
                     Block::default()
-                        .title("Instance to Backup")
+                        .title(instance_panel_title(app, "Instance to Backup"))
                         .borders(Borders::ALL)
                         .border_type(BorderType::Rounded)
                         .style(instance_style),
@@ -476,7 +1101,7 @@ fn render_backup_instance_selection(f: &mut Frame, area: Rect, app: &mut App) {
 fn render_backup_name_input(f: &mut Frame, area: Rect, app: &mut App) {
     let name_style = if matches!(app.state, AppState::EnteringBackupName) {
         Style::default().fg(ACCENT_COLOR)
-    } else if app.create_backup_flow.config.is_some() {
+    } else if app.create_backup_flow.is_configured() {
         Style::default().fg(SUCCESS_COLOR)
     } else {
         Style::default().fg(BORDER_COLOR)
@@ -484,6 +1109,12 @@ fn render_backup_name_input(f: &mut Frame, area: Rect, app: &mut App) {
 
     let name_content = if let Some(config) = &app.create_backup_flow.config {
         format!("✓ {}", config.name)
+    } else if let Some(first) = app.create_backup_flow.batch_configs.first() {
+        format!(
+            "✓ {} ({} backups)",
+            first.name,
+            app.create_backup_flow.batch_configs.len()
+        )
     } else if matches!(app.state, AppState::EnteringBackupName) {
         "→ Press Enter to name backup...".to_string()
     } else {
@@ -506,50 +1137,181 @@ fn render_backup_name_input(f: &mut Frame, area: Rect, app: &mut App) {
 }
 
 fn render_backup_status(f: &mut Frame, area: Rect, app: &mut App) {
-    let status_content = if let Some(_operation_id) = &app.create_backup_flow.operation_id {
-        match app.create_backup_flow.status.as_deref() {
-            Some("DONE") => "✅ Backup created successfully!",
-            Some("RUNNING") => "🔄 Backup in progress...",
-            Some("PENDING") => "⏳ Backup is pending...",
-            Some("FAILED") | Some("ERROR") => "❌ Backup failed!",
-            _ => "📊 Checking backup status...",
-        }
-    } else if app.create_backup_flow.config.is_some() {
-        "✅ Ready to create backup!\nPress Enter to confirm."
+    if !app.create_backup_flow.batch_operations.is_empty() {
+        render_batch_backup_status(f, area, app);
+        return;
+    }
+
+    let flow = &app.create_backup_flow;
+    let phase = OperationPhase::from_status(flow.status.as_deref().unwrap_or_default());
+    let mut status_content = if flow.operation_id.is_some() {
+        let headline = match phase {
+            OperationPhase::Done => "Backup created successfully!",
+            OperationPhase::Executing => "Backup in progress...",
+            OperationPhase::Queued => "Backup is queued...",
+            OperationPhase::Failed => "Backup failed!",
+            OperationPhase::Checking => "Checking backup status...",
+        };
+        format!(
+            "{} {}\n{}",
+            phase.icon(),
+            headline,
+            phase.detail(flow.operation_type.as_deref())
+        )
+    } else if app.loading.operation_status {
+        "🔄 Submitting backup request...".to_string()
+    } else if flow.is_configured() {
+        "✅ Ready to create backup!\nPress Enter to confirm.".to_string()
     } else {
-        "Complete previous steps."
+        "Complete previous steps.".to_string()
     };
-
-    let status_style = if let Some(_) = &app.create_backup_flow.operation_id {
-        match app.create_backup_flow.status.as_deref() {
-            Some("DONE") => Style::default().fg(SUCCESS_COLOR),
-            Some("RUNNING") => Style::default().fg(WARNING_COLOR),
-            Some("PENDING") => Style::default().fg(ACCENT_COLOR),
-            Some("FAILED") | Some("ERROR") => Style::default().fg(Color::Red),
-            _ => Style::default().fg(WARNING_COLOR),
+    if let Some(warning) = &app.create_backup_flow.operation_warning {
+        status_content.push_str(&format!("\n⚠️  {}", warning));
+    }
+    if app.polling_paused && app.create_backup_flow.operation_id.is_some() {
+        status_content.push_str(&format!("\n{}", polling_paused_note(app)));
+    }
+    if let (Some(project), Some(instance)) = (&flow.project, &flow.instance) {
+        if let Some(state) = app.instance_not_ready(project, instance) {
+            status_content.push_str(&format!("\n⚠️  {} is {}, not RUNNABLE", instance, state));
         }
-    } else if app.create_backup_flow.config.is_some() {
+    }
+
+    let status_style = if app.create_backup_flow.operation_id.is_some() {
+        Style::default().fg(phase_color(phase))
+    } else if app.create_backup_flow.is_configured() {
         Style::default().fg(SUCCESS_COLOR)
     } else {
         Style::default().fg(BORDER_COLOR)
     };
 
-    f.render_widget(
-        Paragraph::new(status_content)
-            .block(
-                Block::default()
-                    .title("Backup Status")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .style(status_style),
-            )
-            .alignment(Alignment::Center)
+    render_status_pane(f, area, "Backup Status", status_content, status_style);
+}
+
+fn polling_paused_note(app: &App) -> String {
+    format!(
+        "⏸  Polling paused - press {} to resume / {} to refresh once",
+        app.keymap.label(Action::PausePolling),
+        app.keymap.label(Action::Refresh)
+    )
+}
+
+/// Whether the gcloud command key shows the command here. On the backup list it gives way
+/// to comparing backups if the two share a key.
+fn gcloud_command_available(app: &App) -> bool {
+    app.gcloud_command_mode().is_some()
+        && !(matches!(app.state, AppState::SelectingBackup)
+            && app.keymap.overlaps(Action::Compare, Action::GcloudCommand))
+}
+
+/// Whether the polling key toggles polling here. On the source instance step it gives way
+/// to the project-wide backup list if the two share a key.
+fn polling_toggle_available(app: &App) -> bool {
+    app.polls_status()
+        && !(matches!(app.state, AppState::SelectingSourceInstance)
+            && app.keymap.overlaps(Action::ProjectBackups, Action::PausePolling))
+}
+
+fn phase_color(phase: OperationPhase) -> Color {
+    match phase {
+        OperationPhase::Done => SUCCESS_COLOR,
+        OperationPhase::Executing | OperationPhase::Checking => WARNING_COLOR,
+        OperationPhase::Queued => ACCENT_COLOR,
+        OperationPhase::Failed => Color::Red,
+    }
+}
+
+/// Renders an operation status pane.
+fn render_status_pane(f: &mut Frame, area: Rect, title: &str, content: String, style: Style) {
+    f.render_widget(
+        Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(title.to_string())
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .style(style),
+            )
+            .alignment(Alignment::Center)
             .wrap(Wrap { trim: true }),
         area,
     );
 }
 
+fn render_batch_backup_status(f: &mut Frame, area: Rect, app: &App) {
+    let (done, failed, total) = app.create_backup_flow.batch_progress();
+
+    let rows: Vec<Row> = app
+        .create_backup_flow
+        .batch_operations
+        .iter()
+        .map(|op| {
+            let phase = OperationPhase::from_status(&op.status);
+            let (icon, color) = (phase.icon(), phase_color(phase));
+            let detail = op
+                .error
+                .clone()
+                .or_else(|| op.operation_id.clone())
+                .unwrap_or_default();
+            Row::new(vec![
+                Cell::from(op.instance.clone()),
+                Cell::from(format!("{} {}", icon, op.status)),
+                Cell::from(detail),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let border_style = if failed > 0 {
+        Style::default().fg(Color::Red)
+    } else if done == total {
+        Style::default().fg(SUCCESS_COLOR)
+    } else {
+        Style::default().fg(WARNING_COLOR)
+    };
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(35),
+            Constraint::Length(12),
+            Constraint::Min(0),
+        ],
+    )
+    .header(
+        Row::new(vec!["Instance", "Status", "Operation"])
+            .style(Style::default().fg(BASE_FG).add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .title(format!(
+                "Backup Status - {}/{} done, {} failed{}",
+                done,
+                total,
+                failed,
+                if app.polling_paused { " - polling paused" } else { "" }
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(border_style),
+    );
+
+    f.render_widget(table, area);
+}
+
+/// Below this width the restore sections are stacked instead of shown side by side.
+const COMPACT_LAYOUT_WIDTH: u16 = 100;
+
+pub fn uses_compact_layout(app: &App, width: u16) -> bool {
+    app.compact_layout || width < COMPACT_LAYOUT_WIDTH
+}
+
 fn render_two_section_layout(f: &mut Frame, area: Rect, app: &mut App) {
+    if uses_compact_layout(app, area.width) {
+        render_stacked_sections(f, area, app);
+        return;
+    }
+
     // Create 2-section horizontal layout like example app
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -563,6 +1325,63 @@ fn render_two_section_layout(f: &mut Frame, area: Rect, app: &mut App) {
     render_target_section(f, main_chunks[1], app);
 }
 
+/// Single-column layout: only the section being worked on is expanded, the other is
+/// collapsed to a one-line summary.
+fn render_stacked_sections(f: &mut Frame, area: Rect, app: &mut App) {
+    let source_active = matches!(
+        app.state,
+        AppState::SelectingSourceProject
+            | AppState::SelectingSourceInstance
+            | AppState::SelectingBackup
+    );
+    let flow = &app.restore_flow;
+    let source_summary = section_summary(&[
+        &flow.source_project,
+        &flow.source_instance,
+        &flow.selected_backup,
+    ]);
+    let target_summary = section_summary(&[&flow.target_project, &flow.target_instance]);
+
+    if source_active {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+        render_source_section(f, chunks[0], app);
+        render_collapsed_section(f, chunks[1], "Target", target_summary);
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+        render_collapsed_section(f, chunks[0], "Source", source_summary);
+        render_target_section(f, chunks[1], app);
+    }
+}
+
+fn section_summary(parts: &[&Option<String>]) -> String {
+    parts
+        .iter()
+        .map(|part| part.as_deref().unwrap_or("…"))
+        .collect::<Vec<_>>()
+        .join(" › ")
+}
+
+fn render_collapsed_section(f: &mut Frame, area: Rect, title: &str, summary: String) {
+    f.render_widget(
+        Paragraph::new(summary)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .style(Style::default().fg(BORDER_COLOR)),
+            )
+            .alignment(Alignment::Center),
+        area,
+    );
+}
+
 fn render_source_section(f: &mut Frame, area: Rect, app: &mut App) {
     let source_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -624,10 +1443,10 @@ fn render_source_section(f: &mut Frame, area: Rect, app: &mut App) {
         let instance_content = if let Some(instance) = &app.restore_flow.source_instance {
             format!("✓ {}", instance)
         } else if matches!(app.state, AppState::SelectingSourceInstance) {
-            if app.loading {
+            if app.loading.instances {
                 "→ Loading instances...".to_string()
             } else if app.restore_flow.instances.is_empty() {
-                "→ No instances found".to_string()
+                empty_instance_list_message(app)
             } else {
                 "→ Select instance...".to_string()
             }
@@ -639,7 +1458,7 @@ fn render_source_section(f: &mut Frame, area: Rect, app: &mut App) {
             Paragraph::new(instance_content)
                 .block(
                     Block::default()
-                        .title("Source Instance")
+                        .title(instance_panel_title(app, "Source Instance"))
                         .borders(Borders::ALL)
                         .border_type(BorderType::Rounded)
                         .style(instance_style),
@@ -670,12 +1489,24 @@ fn render_source_section(f: &mut Frame, area: Rect, app: &mut App) {
         let backup_content = if let Some(backup) = &app.restore_flow.selected_backup {
             format!("✓ {}", backup)
         } else if matches!(app.state, AppState::SelectingBackup) {
-            if app.loading {
+            if app.loading.backups {
                 "→ Loading backups...".to_string()
-            } else if app.restore_flow.backups.is_empty() {
-                "→ No backups found".to_string()
+            } else if app.restore_flow.backups.items().is_empty() {
+                format!(
+                    "→ No backups found - press [{}] to create one",
+                    app.keymap.label(Action::Select)
+                )
+            } else if let Some(range) = app.restore_flow.date_filter() {
+                format!(
+                    "→ No backups in {} - press [{}] to change the date filter",
+                    range,
+                    app.keymap.label(Action::DateFilter)
+                )
             } else {
-                format!("→ Choose from {} backups", app.restore_flow.backups.len())
+                format!(
+                    "→ Choose from {} backups",
+                    app.restore_flow.backups.items().len()
+                )
             }
         } else {
             "Pending...".to_string()
@@ -698,101 +1529,180 @@ fn render_source_section(f: &mut Frame, area: Rect, app: &mut App) {
 }
 
 fn render_instance_list(f: &mut Frame, area: Rect, app: &mut App, title: &str) {
-    let (instances, selected_index) = match app.operation_mode {
-        Some(OperationMode::Restore) => (
-            &app.restore_flow.instances,
-            app.restore_flow.selected_instance_index,
-        ),
-        Some(OperationMode::CreateBackup) => (
-            &app.create_backup_flow.instances,
-            app.create_backup_flow.selected_instance_index,
-        ),
-        None => (&app.restore_flow.instances, 0), // Default or error case
+    let panel_title = instance_panel_title(app, title);
+    let mode = app.operation_mode;
+    let marked = &app.create_backup_flow.marked_instances;
+    let list = match mode {
+        Some(OperationMode::CreateBackup) => &mut app.create_backup_flow.instances,
+        _ => &mut app.restore_flow.instances,
     };
 
-    let items: Vec<ListItem> = instances
-        .iter()
-        .enumerate()
-        .map(|(i, instance)| {
-            let style = if i == selected_index {
-                Style::default()
-                    .fg(ACCENT_COLOR)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(BASE_FG)
-            };
-            ListItem::new(format!("  {}", instance.name)).style(style)
-        })
-        .collect();
+    let summary = instance_summary(list.items());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(panel_title)
+        .title(Line::from(summary).right_aligned())
+        .style(Style::default().fg(ACCENT_COLOR));
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .title(title)
-                .style(Style::default().fg(ACCENT_COLOR)),
-        )
-        .highlight_style(
+    let selected_index = list.selected();
+    list.render(f, area, block, list_highlight(), |i, instance| {
+        let style = if i == selected_index {
             Style::default()
-                .bg(HIGHLIGHT_BG)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("► ");
+                .fg(ACCENT_COLOR)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(BASE_FG)
+        };
+        let style = if instance.is_replica() {
+            style.add_modifier(Modifier::DIM)
+        } else {
+            style
+        };
+        let marker = match mode {
+            Some(OperationMode::CreateBackup) if marked.contains(&instance.name) => {
+                "[x] "
+            }
+            Some(OperationMode::CreateBackup) => "[ ] ",
+            _ => "",
+        };
+        ListItem::new(format!("  {}{}", marker, instance.summary())).style(style)
+    });
+}
 
-    let mut state = ListState::default();
-    state.select(Some(selected_index));
+/// Explains an empty instance list: either the gcloud error, or the usual reasons a
+/// successful call comes back empty.
+fn empty_instance_list_message(app: &App) -> String {
+    let keys = format!(
+        "[{}] Manual entry | [{}] Retry",
+        app.keymap.label(Action::Manual),
+        app.keymap.label(Action::Refresh)
+    );
+    match &app.instance_load_error {
+        Some(error) => format!("→ Failed to load instances:\n{}\n{}", error, keys),
+        None => format!(
+            "→ No instances found.\nCheck the project ID, that the Cloud SQL Admin API is \
+             enabled and that you can list instances.\n{}",
+            keys
+        ),
+    }
+}
 
-    f.render_stateful_widget(list, area, &mut state);
+fn instance_panel_title(app: &App, title: &str) -> String {
+    match &app.engine_filter {
+        Some(engine) => format!("{} [{}]", title, engine),
+        None => title.to_string(),
+    }
+}
+
+fn instance_summary(instances: &[SqlInstance]) -> String {
+    match instances.len() {
+        1 => " 1 instance ".to_string(),
+        n => format!(" {} instances ", n),
+    }
 }
 
+fn backup_summary(backups: &[&Backup], zone: DisplayZone) -> String {
+    let count = match backups.len() {
+        1 => "1 backup".to_string(),
+        n => format!("{} backups", n),
+    };
+    match backups.iter().filter_map(|b| b.start_time).max() {
+        Some(newest) => format!(" {}, newest {} ", count, zone.format(newest, "%Y-%m-%d")),
+        None => format!(" {} ", count),
+    }
+}
+
+/// Backup lists longer than this suggest filtering by date.
+const LARGE_BACKUP_LIST: usize = 1000;
+
 fn render_backup_list(f: &mut Frame, area: Rect, app: &mut App) {
-    let items: Vec<ListItem> = app
-        .restore_flow
-        .backups
-        .iter()
-        .enumerate()
-        .map(|(i, backup)| {
-            let style = if i == app.restore_flow.selected_backup_index {
-                Style::default()
-                    .fg(ACCENT_COLOR)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(BASE_FG)
-            };
+    let flow = &mut app.restore_flow;
+    let backups = flow.backups.filtered_items();
+    let mut summary = backup_summary(&backups, app.display_zone);
+    let mut title = match flow.date_filter() {
+        Some(range) => format!("Source Backup [{}]", range),
+        None => {
+            if backups.len() > LARGE_BACKUP_LIST {
+                summary.push_str(&format!(
+                    "- [{}] to filter by date ",
+                    app.keymap.label(Action::DateFilter)
+                ));
+            }
+            "Source Backup".to_string()
+        }
+    };
+    title.push_str(&format!(" - times in {}", app.display_zone.label()));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(title)
+        .title(Line::from(summary).right_aligned())
+        .style(Style::default().fg(ACCENT_COLOR));
 
-            // Format the date (without time)
-            let date_str = backup
-                .start_time
-                .map(|t| t.format("%Y-%m-%d").to_string())
-                .unwrap_or_else(|| "Unknown".to_string());
+    let list = &mut flow.backups;
+    let selected = list.selected();
+    let marks = &flow.compare_marks;
+    let zone = app.display_zone;
+    list.render(f, area, block, list_highlight(), |i, backup| {
+        // The selection only adds a background, so the status color stays visible.
+        let style = Style::default().fg(backup_status_color(&backup.status));
+        let style = if i == selected {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        };
 
-            // Create display text with date and backup ID
-            let display_text = format!("  {} | {}", date_str, backup.id);
+        let marker = match marks.iter().position(|mark| *mark == backup.id) {
+            Some(position) => format!("[{}] ", position + 1),
+            None if marks.is_empty() => String::new(),
+            None => "[ ] ".to_string(),
+        };
+        ListItem::new(format!("  {}{}", marker, backup.display_line(zone))).style(style)
+    });
+}
 
-            ListItem::new(display_text).style(style)
-        })
-        .collect();
+/// Row color of a backup: green once it completed, red if it failed, yellow otherwise
+/// (still running, or unknown for manually entered backups).
+fn backup_status_color(status: &str) -> Color {
+    match status {
+        "SUCCESSFUL" => SUCCESS_COLOR,
+        "FAILED" => Color::Red,
+        _ => WARNING_COLOR,
+    }
+}
 
-    let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .title("Source Backup")
-                .style(Style::default().fg(ACCENT_COLOR)),
-        )
-        .highlight_style(
-            Style::default()
-                .bg(HIGHLIGHT_BG)
-                .add_modifier(Modifier::BOLD),
+fn render_project_backup_list(f: &mut Frame, area: Rect, app: &mut App) {
+    let flow = &mut app.restore_flow;
+    let project = flow.source_project.as_deref().unwrap_or("project");
+    let summary = if app.loading.backups {
+        " Loading backups... ".to_string()
+    } else {
+        format!(
+            " {} backups, {} ",
+            flow.project_backups.len(),
+            flow.project_backup_sort.label()
         )
-        .highlight_symbol("► ");
-
-    let mut state = ListState::default();
-    state.select(Some(app.restore_flow.selected_backup_index));
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!("All Backups in {}", project))
+        .title(Line::from(summary).right_aligned())
+        .style(Style::default().fg(ACCENT_COLOR));
 
-    f.render_stateful_widget(list, area, &mut state);
+    let zone = app.display_zone;
+    flow.project_backups.render(f, area, block, list_highlight(), |_, (instance, backup)| {
+        ListItem::new(format!(
+            "  {} | {} | {} | {} {}",
+            backup.taken_at(zone),
+            instance,
+            backup.id,
+            backup.backup_type,
+            backup.status
+        ))
+        .style(Style::default().fg(BASE_FG))
+    });
 }
 
 fn render_target_section(f: &mut Frame, area: Rect, app: &mut App) {
@@ -856,10 +1766,10 @@ fn render_target_section(f: &mut Frame, area: Rect, app: &mut App) {
         let instance_content = if let Some(instance) = &app.restore_flow.target_instance {
             format!("✓ {}", instance)
         } else if matches!(app.state, AppState::SelectingTargetInstance) {
-            if app.loading {
+            if app.loading.instances {
                 "→ Loading instances...".to_string()
             } else if app.restore_flow.instances.is_empty() {
-                "→ No instances found".to_string()
+                empty_instance_list_message(app)
             } else {
                 "→ Select instance...".to_string()
             }
@@ -871,7 +1781,7 @@ fn render_target_section(f: &mut Frame, area: Rect, app: &mut App) {
             Paragraph::new(instance_content)
                 .block(
                     Block::default()
-                        .title("Target Instance")
+                        .title(instance_panel_title(app, "Target Instance"))
                         .borders(Borders::ALL)
                         .border_type(BorderType::Rounded)
                         .style(instance_style),
@@ -883,32 +1793,66 @@ fn render_target_section(f: &mut Frame, area: Rect, app: &mut App) {
     }
 
     // Status/Info section - Now shows restore progress with actual status
-    let status_content = if let Some(_operation_id) = &app.restore_flow.operation_id {
-        match app.restore_flow.status.as_deref() {
-            Some("DONE") => "✅ Restore completed successfully!\nBackup has been applied.",
-            Some("RUNNING") => {
-                "🔄 Restore in progress...\nPlease wait, this may take several minutes."
+    let phase = OperationPhase::from_status(app.restore_flow.status.as_deref().unwrap_or_default());
+    let mut status_content = if app.restore_flow.operation_id.is_some() {
+        let (headline, note) = match phase {
+            OperationPhase::Done => ("Restore completed successfully!", "Backup has been applied."),
+            OperationPhase::Executing => {
+                ("Restore in progress...", "Please wait, this may take several minutes.")
             }
-            Some("PENDING") => "⏳ Restore is pending...\nOperation is queued for execution.",
-            Some("FAILED") | Some("ERROR") => "❌ Restore failed!\nCheck logs for details.",
-            _ => "📊 Checking restore status...\nMonitoring progress...",
+            OperationPhase::Queued => ("Restore is queued...", ""),
+            OperationPhase::Failed => ("Restore failed!", "Check logs for details."),
+            OperationPhase::Checking => ("Checking restore status...", ""),
+        };
+        let mut content = format!(
+            "{} {}\n{}",
+            phase.icon(),
+            headline,
+            phase.detail(app.restore_flow.operation_type.as_deref())
+        );
+        if !note.is_empty() {
+            content.push_str(&format!("\n{}", note));
         }
+        content
+    } else if app.loading.operation_status {
+        "🔄 Submitting restore request...".to_string()
     } else if app.restore_flow.target_instance.is_some()
         && app.restore_flow.selected_backup.is_some()
     {
-        "✅ Ready to restore!\nPress Enter to confirm."
+        "✅ Ready to restore!\nPress Enter to confirm.".to_string()
     } else {
-        "Complete source\nselection first."
+        "Complete source\nselection first.".to_string()
     };
-
-    let status_style = if let Some(_) = &app.restore_flow.operation_id {
-        match app.restore_flow.status.as_deref() {
-            Some("DONE") => Style::default().fg(SUCCESS_COLOR),
-            Some("RUNNING") => Style::default().fg(WARNING_COLOR),
-            Some("PENDING") => Style::default().fg(ACCENT_COLOR),
-            Some("FAILED") | Some("ERROR") => Style::default().fg(Color::Red),
-            _ => Style::default().fg(WARNING_COLOR),
+    if let Some(retry_at) = app.restore_flow.retry_at {
+        status_content = format!(
+            "⏳ Target instance is busy.\nRetrying in {}s (attempt {}/{}).\nPress Esc to cancel.",
+            retry_at.saturating_duration_since(Instant::now()).as_secs(),
+            app.restore_flow.retry_attempts,
+            MAX_RESTORE_RETRIES
+        );
+    }
+    if let Some(warning) = &app.restore_flow.operation_warning {
+        status_content.push_str(&format!("\n⚠️  {}", warning));
+    }
+    if let Some(marker) = &app.restore_flow.marker_operation_id {
+        status_content.push_str(&format!(
+            "\n🏷  Restore recorded as a backup of the target (operation {})",
+            marker
+        ));
+    }
+    if app.polling_paused && app.restore_flow.operation_id.is_some() {
+        status_content.push_str(&format!("\n{}", polling_paused_note(app)));
+    }
+    if let (Some(project), Some(instance)) =
+        (&app.restore_flow.target_project, &app.restore_flow.target_instance)
+    {
+        if let Some(state) = app.instance_not_ready(project, instance) {
+            status_content.push_str(&format!("\n⚠️  {} is {}, not RUNNABLE", instance, state));
         }
+    }
+
+    let status_style = if app.restore_flow.operation_id.is_some() {
+        Style::default().fg(phase_color(phase))
     } else if app.restore_flow.target_instance.is_some()
         && app.restore_flow.selected_backup.is_some()
     {
@@ -917,67 +1861,7 @@ fn render_target_section(f: &mut Frame, area: Rect, app: &mut App) {
         Style::default().fg(BORDER_COLOR)
     };
 
-    f.render_widget(
-        Paragraph::new(status_content)
-            .block(
-                Block::default()
-                    .title("Restore Status")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .style(status_style),
-            )
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true }),
-        target_chunks[2],
-    );
-}
-
-fn render_welcome(f: &mut Frame, area: Rect) {
-    let block = Block::default()
-        .title(" Welcome ")
-        .title_alignment(Alignment::Center)
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .style(Style::default().fg(BASE_FG).bg(BASE_BG));
-
-    let welcome_text = vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            "GCP SQL Backup Restore Tool",
-            Style::default()
-                .fg(ACCENT_COLOR)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from("This tool helps you restore SQL backups between GCP projects."),
-        Line::from(""),
-        Line::from("Steps:"),
-        Line::from("  1. Select source project and instance"),
-        Line::from("  2. Choose a backup to restore"),
-        Line::from("  3. Select target project and instance"),
-        Line::from("  4. Confirm and execute restoration"),
-        Line::from("  5. Monitor progress in real-time"),
-        Line::from(""),
-        Line::from("Navigation:"),
-        Line::from("  • Use ESC to go back to previous steps"),
-        Line::from("  • Use Q to quit the application"),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Press 'p' to start with project selection",
-            Style::default().fg(WARNING_COLOR),
-        )),
-        Line::from(Span::styled(
-            "Press 'h' for detailed help",
-            Style::default().fg(BORDER_COLOR),
-        )),
-    ];
-
-    let paragraph = Paragraph::new(welcome_text)
-        .block(block)
-        .alignment(Alignment::Center)
-        .wrap(Wrap { trim: true });
-
-    f.render_widget(paragraph, area);
+    render_status_pane(f, target_chunks[2], "Restore Status", status_content, status_style);
 }
 
 fn render_loading(f: &mut Frame, area: Rect, message: &str) {
@@ -1002,57 +1886,6 @@ fn render_loading(f: &mut Frame, area: Rect, message: &str) {
     f.render_widget(loading, area);
 }
 
-fn render_project_selection(f: &mut Frame, area: Rect, app: &App, title: &str) {
-    let block = Block::default()
-        .title(title)
-        .title_alignment(Alignment::Center)
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .style(Style::default().fg(BASE_FG).bg(BASE_BG));
-
-    let content = vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            "Manual Project ID Entry",
-            Style::default()
-                .fg(ACCENT_COLOR)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from("Enter your GCP project ID manually"),
-        Line::from("for security and flexibility."),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Press [Enter] to open input",
-            Style::default().fg(INPUT_TEXT),
-        )),
-    ];
-
-    if !app.remembered_projects.is_empty() {
-        let recent_text = format!("Recent: {}", app.remembered_projects.join(", "));
-        let content_with_recent = [
-            content,
-            vec![
-                Line::from(""),
-                Line::from(Span::styled(recent_text, Style::default().fg(BORDER_COLOR))),
-            ],
-        ]
-        .concat();
-
-        let paragraph = Paragraph::new(content_with_recent)
-            .block(block)
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true });
-        f.render_widget(paragraph, area);
-    } else {
-        let paragraph = Paragraph::new(content)
-            .block(block)
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true });
-        f.render_widget(paragraph, area);
-    }
-}
-
 fn render_error(f: &mut Frame, area: Rect, error_msg: &str) {
     let error_text = vec![
         Line::from(""),
@@ -1079,20 +1912,9 @@ fn render_error(f: &mut Frame, area: Rect, error_msg: &str) {
 
 fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     let help_text = if app.manual_input_active {
-        " [Enter] Confirm | [Esc] Cancel "
+        " [Enter] Confirm | [Esc] Cancel ".to_string()
     } else {
-        match app.state {
-            AppState::SelectingOperation => " [↑/↓] Navigate | [Enter] Select | [h] Help | [q] Quit ",
-            _ => {
-                if app.restore_flow.operation_id.is_some()
-                    || app.create_backup_flow.operation_id.is_some()
-                {
-                    " [↑/↓] Navigate | [Enter] Select | [Esc] Back | [r] Refresh | [n] New | [h] Help | [q] Quit "
-                } else {
-                    " [↑/↓] Navigate | [Enter] Select | [Esc] Back | [r] Refresh | [h] Help | [q] Quit "
-                }
-            }
-        }
+        fit_footer_hints(footer_hints(app), area.width.saturating_sub(2) as usize)
     };
 
     f.render_widget(
@@ -1110,36 +1932,247 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     );
 }
 
-fn render_restore_warning_popup(f: &mut Frame, app: &App) {
-    if let Some(config) = &app.restore_flow.config {
-        let popup_area = centered_rect(85, 60, f.area());
-        f.render_widget(Clear, popup_area);
+/// How readily a footer hint is dropped when the footer is too narrow; later ranks go
+/// first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum HintRank {
+    /// Moving, selecting, going back, help and quitting; always shown.
+    Essential,
+    /// Keys specific to the current step.
+    Step,
+    /// Keys that are rarely needed or listed in the help anyway.
+    Extra,
+}
 
-        let warning_block = Block::default()
-            .title("⚠️  CRITICAL WARNING - BACKUP RESTORATION  ⚠️")
-            .title_alignment(Alignment::Center)
-            .borders(Borders::ALL)
-            .border_type(BorderType::Double)
-            .style(
-                Style::default()
-                    .fg(Color::White)
-                    .bg(Color::Rgb(139, 0, 0)), // Dark red background
-            );
+/// Builds the footer for the current step from the active key bindings. With
+/// [`App::compact_footer`] only the essential hints are included.
+fn footer_hints(app: &App) -> Vec<(HintRank, String)> {
+    let keys = &app.keymap;
+    let hint = |action: Action, text: &str| format!("[{}] {}", keys.label(action), text);
+    let step = |action: Action, text: &str| (HintRank::Step, hint(action, text));
+
+    let mut hints = vec![
+        (
+            HintRank::Essential,
+            format!("[{}/{}] Navigate", keys.label(Action::Up), keys.label(Action::Down)),
+        ),
+        (HintRank::Essential, hint(Action::Select, "Select")),
+    ];
+    match app.state {
+        AppState::SelectingOperation => hints.push(step(Action::PastOperations, "Past Operations")),
+        AppState::SelectingInstanceForBackup => hints.insert(1, step(Action::Mark, "Mark")),
+        AppState::SelectingSourceInstance => {
+            hints.push(step(Action::ProjectBackups, "All Project Backups"))
+        }
+        AppState::BrowsingProjectBackups => {
+            hints[1].1 = hint(Action::Select, "Restore from");
+            hints.push(step(Action::Sort, "Sort"));
+        }
+        AppState::SelectingBackup if app.no_backups_to_restore() => {
+            hints[1].1 = hint(Action::Select, "Create a Backup");
+        }
+        AppState::SelectingBackup => {
+            hints.push(step(Action::DateFilter, "Filter by Date"));
+            hints.push(step(Action::Mark, "Mark to Compare"));
+            hints.push(step(Action::Retention, "Backups by Age"));
+            if app.restore_flow.compared_backups().is_some() {
+                hints.push(step(Action::Compare, "Compare"));
+            }
+        }
+        AppState::SelectingTargetProject => {
+            hints.push(step(Action::SameAsSource, "Same as Source"))
+        }
+        AppState::SelectingTargetInstance
+            if app.restore_flow.source_project == app.restore_flow.target_project =>
+        {
+            hints.push(step(Action::SameAsSource, "Same as Source"))
+        }
+        _ => {}
+    }
+    if gcloud_command_available(app) {
+        hints.push(step(Action::GcloudCommand, "gcloud Command"));
+    }
+    if app.has_operation_details() {
+        hints.push(step(Action::Details, "Details"));
+    }
+    if polling_toggle_available(app) {
+        hints.push(step(
+            Action::PausePolling,
+            if app.polling_paused {
+                "Resume Polling"
+            } else {
+                "Pause Polling"
+            },
+        ));
+    }
+    if !matches!(app.state, AppState::SelectingOperation) {
+        hints.push((HintRank::Essential, hint(Action::Back, "Back")));
+        hints.push((HintRank::Extra, hint(Action::Refresh, "Refresh")));
+        if app.restore_flow.operation_id.is_some() || app.create_backup_flow.has_operations() {
+            hints.push(step(Action::New, "New"));
+        }
+    }
+    hints.push((HintRank::Essential, hint(Action::Help, "Help")));
+    hints.push((HintRank::Essential, hint(Action::Quit, "Quit")));
 
-        f.render_widget(warning_block, popup_area);
+    if app.compact_footer {
+        hints.retain(|(rank, _)| *rank == HintRank::Essential);
+        hints.push((HintRank::Essential, hint(Action::CompactFooter, "All Keys")));
+    } else {
+        hints.push((HintRank::Extra, hint(Action::CompactFooter, "Fewer Keys")));
+    }
+    hints
+}
 
-        let inner_area = Rect {
-            x: popup_area.x + 2,
-            y: popup_area.y + 2,
-            width: popup_area.width.saturating_sub(4),
-            height: popup_area.height.saturating_sub(4),
-        };
+/// Joins `hints` into a footer line of at most `width` columns, dropping the
+/// lowest-ranked hints first, later ones before earlier ones of the same rank.
+/// Essential hints are kept even if they do not fit.
+fn fit_footer_hints(mut hints: Vec<(HintRank, String)>, width: usize) -> String {
+    loop {
+        let texts: Vec<&str> = hints.iter().map(|(_, text)| text.as_str()).collect();
+        let line = format!(" {} ", texts.join(" | "));
+        if Line::from(line.as_str()).width() <= width {
+            return line;
+        }
+        let droppable = hints
+            .iter()
+            .enumerate()
+            .filter(|(_, (rank, _))| *rank != HintRank::Essential)
+            .max_by_key(|(index, (rank, _))| (*rank, *index))
+            .map(|(index, _)| index);
+        match droppable {
+            Some(index) => {
+                hints.remove(index);
+            }
+            None => return line,
+        }
+    }
+}
+
+/// The accept/cancel hint of a confirmation popup, using the keys that
+/// `handle_normal_input` actually accepts for [`Action::Select`] and [`Action::Back`].
+fn confirm_hint_spans(app: &App, accept: &str, cancel: &str) -> Vec<Span<'static>> {
+    vec![
+        Span::styled(
+            format!("[{}] ", app.keymap.label(Action::Select)),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{}  ", accept), Style::default().fg(Color::White)),
+        Span::styled(
+            format!("[{}] ", app.keymap.label(Action::Back)),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{}  ", cancel), Style::default().fg(Color::White)),
+    ]
+}
+
+/// When and how the selected backup was taken, for the restore confirmation. The type
+/// says how long the backup is kept: automated backups age out under the instance's
+/// retention setting, on-demand ones stay until deleted.
+fn backup_details(app: &App, backup_id: &str) -> String {
+    match app
+        .restore_flow
+        .backups
+        .items()
+        .iter()
+        .find(|b| b.id == backup_id)
+    {
+        Some(backup) if !backup.status_unknown() => {
+            let kind = match backup.backup_type.as_str() {
+                "AUTOMATED" => "AUTOMATED, expires under the instance's backup retention",
+                "ON_DEMAND" => "ON_DEMAND, kept until deleted",
+                other => other,
+            };
+            let picked = if app.restore_flow.auto_selected_backup {
+                ", latest, picked automatically"
+            } else {
+                ""
+            };
+            format!("{} ({}{})", backup.taken_at(app.display_zone), kind, picked)
+        }
+        _ => "(manual id, type and status unknown - make sure this backup completed)"
+            .to_string(),
+    }
+}
+
+/// What a confirmation popup asks to go ahead with. Each kind has its own title, icon and
+/// colors, so a popup for one action cannot be mistaken for another's; an action that
+/// destroys something different gets a kind of its own rather than borrowing one.
+#[derive(Clone, Copy)]
+enum ConfirmAction {
+    /// Overwrites every database on the target instance.
+    Restore,
+    /// Adds a backup; nothing existing is changed.
+    CreateBackup,
+}
+
+impl ConfirmAction {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Restore => "⚠️  CRITICAL WARNING - BACKUP RESTORATION  ⚠️",
+            Self::CreateBackup => "✅  Confirm Backup Creation  ✅",
+        }
+    }
+
+    /// With reduced motion, the restore warning drops its saturated red background and
+    /// keeps a plain one with a red border.
+    fn background(self, reduce_motion: bool) -> Color {
+        match self {
+            Self::Restore if reduce_motion => Color::Reset,
+            Self::Restore => Color::Rgb(139, 0, 0),
+            Self::CreateBackup => Color::DarkGray,
+        }
+    }
+
+    fn border_style(self, reduce_motion: bool) -> Style {
+        match self {
+            Self::Restore if reduce_motion => Style::default().fg(Color::LightRed),
+            _ => Style::default(),
+        }
+    }
+}
+
+/// Clears a confirmation popup taking `percent_y` of the height, draws its frame for
+/// `action` and returns the area inside it.
+fn render_confirm_frame(f: &mut Frame, app: &App, action: ConfirmAction, percent_y: u16) -> Rect {
+    let popup_area = centered_rect(85, percent_y, f.area());
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title(action.title())
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(action.border_style(app.reduce_motion))
+        .style(
+            Style::default()
+                .fg(Color::White)
+                .bg(action.background(app.reduce_motion)),
+        );
+    f.render_widget(block, popup_area);
+
+    Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + 2,
+        width: popup_area.width.saturating_sub(4),
+        height: popup_area.height.saturating_sub(4),
+    }
+}
+
+fn render_restore_warning_popup(f: &mut Frame, app: &App) {
+    if let Some(config) = &app.restore_flow.config {
+        let action = ConfirmAction::Restore;
+        let background = action.background(app.reduce_motion);
+        let inner_area = render_confirm_frame(f, app, action, 70);
+        let estimate = app.restore_estimate(config);
+        let config_rows = 11 + u16::from(estimate.is_some()) + u16::from(app.mark_restores);
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
-                Constraint::Length(8),
+                Constraint::Length(config_rows),
                 Constraint::Length(3),
                 Constraint::Min(0),
             ])
@@ -1155,14 +2188,51 @@ fn render_restore_warning_popup(f: &mut Frame, app: &App) {
         f.render_widget(
             Paragraph::new(header_text)
                 .alignment(Alignment::Center)
-                .style(Style::default().bg(Color::Rgb(139, 0, 0))),
+                .style(Style::default().bg(background)),
             chunks[0],
         );
 
-        let source_text = format!("{} → {}", config.source_project, config.source_instance);
-        let target_text = format!("{} → {}", config.target_project, config.target_instance);
+        let source_text = config.source();
+        let mut target_text = config.target();
+        if let Some(state) =
+            app.instance_not_ready(&config.target_project, &config.target_instance)
+        {
+            target_text.push_str(&format!(" ({}, not ready yet)", state));
+        }
+        if let Some(window) =
+            app.maintenance_window_near(&config.target_project, &config.target_instance, Utc::now())
+        {
+            target_text.push_str(&format!(
+                " - maintenance window {}, the restore may be queued",
+                window
+            ));
+        }
+        let reason_text = match (&config.reason, app.require_reason) {
+            (Some(reason), _) => reason.clone(),
+            (None, true) => "required - press [Enter] to give one".to_string(),
+            (None, false) => "none given".to_string(),
+        };
+        let flow = &app.restore_flow;
+        let newer_source = flow.newer_source_version();
+        let engine_text = format!(
+            "{} → {}",
+            flow.source_version.as_deref().unwrap_or("unknown"),
+            flow.instance_version(&config.target_instance).unwrap_or("unknown")
+        );
+        let (engine_text, engine_style) = match newer_source {
+            Some(_) => (
+                format!("{} - backup is from a NEWER version, the restore will fail", engine_text),
+                Style::default().fg(WARNING_COLOR).add_modifier(Modifier::BOLD),
+            ),
+            None => (engine_text, Style::default().fg(Color::White)),
+        };
+        let databases_text = if config.intended_databases.is_empty() {
+            format!("not specified - press [{}] to note them", app.keymap.label(Action::Databases))
+        } else {
+            format!("{} (whole instance is still restored)", config.intended_databases.join(", "))
+        };
 
-        let config_text = vec![
+        let mut config_text = vec![
             Line::from(Span::styled(
                 "Restoration Configuration:",
                 Style::default()
@@ -1188,6 +2258,18 @@ fn render_restore_warning_popup(f: &mut Frame, app: &App) {
                 ),
                 Span::styled(&config.backup_id, Style::default().fg(Color::White)),
             ]),
+            Line::from(vec![
+                Span::styled(
+                    "🕒 Taken: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    backup_details(app, &config.backup_id),
+                    Style::default().fg(Color::White),
+                ),
+            ]),
             Line::from(vec![
                 Span::styled(
                     "🎯 Target: ",
@@ -1197,26 +2279,96 @@ fn render_restore_warning_popup(f: &mut Frame, app: &App) {
                 ),
                 Span::styled(&target_text, Style::default().fg(Color::White)),
             ]),
+            Line::from(vec![
+                Span::styled(
+                    "⚙  Engine: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&engine_text, engine_style),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "🗄  Databases: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&databases_text, Style::default().fg(Color::White)),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "📝 Reason: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&reason_text, Style::default().fg(Color::White)),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "📐 Scope: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    "FULL restore - Cloud SQL cannot restore individual databases",
+                    Style::default().fg(Color::White),
+                ),
+            ]),
         ];
+        if let Some(estimate) = estimate {
+            config_text.push(Line::from(vec![
+                Span::styled(
+                    "⏱  Duration: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(estimate, Style::default().fg(Color::White)),
+            ]));
+        }
+        if app.mark_restores {
+            config_text.push(Line::from(vec![
+                Span::styled(
+                    "🏷  Afterwards: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        "an on-demand backup of {} records this restore (--mark-restores)",
+                        config.target_instance
+                    ),
+                    Style::default().fg(Color::White),
+                ),
+            ]));
+        }
         f.render_widget(
             Paragraph::new(config_text)
                 .alignment(Alignment::Left)
-                .style(Style::default().bg(Color::Rgb(139, 0, 0)))
+                .style(Style::default().bg(background))
                 .wrap(Wrap { trim: true }),
             chunks[1],
         );
 
+        let danger_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+        let danger_style = if app.reduce_motion {
+            danger_style.fg(Color::LightRed).add_modifier(Modifier::REVERSED)
+        } else {
+            danger_style.add_modifier(Modifier::SLOW_BLINK)
+        };
         let danger_text = vec![Line::from(Span::styled(
             "⚠️  THIS WILL COMPLETELY REPLACE THE TARGET DATABASE  ⚠️",
-            Style::default()
-                .fg(Color::Red)
-                .add_modifier(Modifier::BOLD)
-                .add_modifier(Modifier::SLOW_BLINK),
+            danger_style,
         ))];
         f.render_widget(
             Paragraph::new(danger_text)
                 .alignment(Alignment::Center)
-                .style(Style::default().bg(Color::Rgb(139, 0, 0))),
+                .style(Style::default().bg(background)),
             chunks[2],
         );
 
@@ -1235,6 +2387,10 @@ fn render_restore_warning_popup(f: &mut Frame, app: &App) {
                     Style::default().fg(Color::White),
                 ),
             ]),
+            Line::from(Span::styled(
+                "• Cloud SQL restores the WHOLE instance - every database on it is replaced",
+                Style::default().fg(Color::White),
+            )),
             Line::from(Span::styled(
                 "• This operation cannot be undone or reversed",
                 Style::default().fg(Color::White),
@@ -1244,29 +2400,48 @@ fn render_restore_warning_popup(f: &mut Frame, app: &App) {
                 Style::default().fg(Color::White),
             )),
             Line::from(""),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    "[Enter] ",
+            if app.restore_target_protected() {
+                Line::from(Span::styled(
+                    format!(
+                        "🔒 Protected target (matches '{}'): type {} to confirm",
+                        app.protect_pattern.as_str(),
+                        config.target_instance
+                    ),
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(
-                    "PROCEED WITH RESTORATION  ",
-                    Style::default().fg(Color::White),
-                ),
-                Span::styled(
-                    "[Esc] ",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("CANCEL AND GO BACK", Style::default().fg(Color::White)),
-            ]),
+                ))
+            } else if app.restore_target_in_use() {
+                Line::from(Span::styled(
+                    format!(
+                        "🟢 {} is RUNNABLE and likely in use: type its name to confirm",
+                        config.target_instance
+                    ),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from("")
+            },
+            Line::from(
+                [
+                    confirm_hint_spans(app, "PROCEED WITH RESTORATION", "CANCEL AND GO BACK"),
+                    vec![
+                        Span::styled(
+                            format!("[{}] ", app.keymap.label(Action::Databases)),
+                            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled("NOTE DATABASES", Style::default().fg(Color::White)),
+                    ],
+                ]
+                .concat(),
+            ),
         ];
         f.render_widget(
             Paragraph::new(instructions_text)
                 .alignment(Alignment::Center)
-                .style(Style::default().bg(Color::Rgb(139, 0, 0)))
+                .style(Style::default().bg(background))
                 .wrap(Wrap { trim: true }),
             chunks[3],
         );
@@ -1274,31 +2449,54 @@ fn render_restore_warning_popup(f: &mut Frame, app: &App) {
 }
 
 fn render_create_backup_warning_popup(f: &mut Frame, app: &App) {
-    if let Some(config) = &app.create_backup_flow.config {
-        let popup_area = centered_rect(85, 60, f.area());
-        f.render_widget(Clear, popup_area);
-
-        let warning_block = Block::default()
-            .title("✅  Confirm Backup Creation  ✅")
-            .title_alignment(Alignment::Center)
-            .borders(Borders::ALL)
-            .border_type(BorderType::Double)
-            .style(Style::default().fg(Color::White).bg(Color::DarkGray));
-
-        f.render_widget(warning_block, popup_area);
+    let flow = &app.create_backup_flow;
+    if let Some(config) = flow.config.as_ref().or(flow.batch_configs.first()) {
+        let action = ConfirmAction::CreateBackup;
+        let background = action.background(app.reduce_motion);
 
-        let inner_area = Rect {
-            x: popup_area.x + 2,
-            y: popup_area.y + 2,
-            width: popup_area.width.saturating_sub(4),
-            height: popup_area.height.saturating_sub(4),
+        let labels_text = if flow.labels.is_empty() {
+            format!("none - press [{}] to add", app.keymap.label(Action::Labels))
+        } else {
+            format_labels(&flow.labels)
+        };
+        let location_text = match (&flow.location, &config.location) {
+            (Some(location), _) => location.clone(),
+            (None, Some(region)) => format!("{} (instance region)", region),
+            (None, None) => "Cloud SQL default".to_string(),
         };
+        let (instance_text, name_text) = if flow.batch_configs.is_empty() {
+            let mut instance_text = config.instance.clone();
+            if let Some(window) =
+                app.maintenance_window_near(&config.project, &config.instance, Utc::now())
+            {
+                instance_text.push_str(&format!(
+                    " - maintenance window {}, the backup may be queued",
+                    window
+                ));
+            }
+            (instance_text, config.name.clone())
+        } else {
+            (
+                flow.batch_configs
+                    .iter()
+                    .map(|c| c.instance.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                flow.batch_configs
+                    .iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        };
+
+        let inner_area = render_confirm_frame(f, app, action, 60);
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
-                Constraint::Length(8),
+                Constraint::Length(9),
                 Constraint::Min(0),
             ])
             .split(inner_area);
@@ -1312,7 +2510,7 @@ fn render_create_backup_warning_popup(f: &mut Frame, app: &App) {
         f.render_widget(
             Paragraph::new(header_text)
                 .alignment(Alignment::Center)
-                .style(Style::default().bg(Color::DarkGray)),
+                .style(Style::default().bg(background)),
             chunks[0],
         );
 
@@ -1340,7 +2538,7 @@ fn render_create_backup_warning_popup(f: &mut Frame, app: &App) {
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(&config.instance, Style::default().fg(Color::White)),
+                Span::styled(&instance_text, Style::default().fg(Color::White)),
             ]),
             Line::from(vec![
                 Span::styled(
@@ -1349,13 +2547,31 @@ fn render_create_backup_warning_popup(f: &mut Frame, app: &App) {
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(&config.name, Style::default().fg(Color::White)),
+                Span::styled(&name_text, Style::default().fg(Color::White)),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "🏷  Labels: ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&labels_text, Style::default().fg(Color::White)),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "📍 Location: ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&location_text, Style::default().fg(Color::White)),
             ]),
         ];
         f.render_widget(
             Paragraph::new(config_text)
                 .alignment(Alignment::Left)
-                .style(Style::default().bg(Color::DarkGray))
+                .style(Style::default().bg(background))
                 .wrap(Wrap { trim: true }),
             chunks[1],
         );
@@ -1363,40 +2579,49 @@ fn render_create_backup_warning_popup(f: &mut Frame, app: &App) {
         let instructions_text = vec![
             Line::from(""),
             Line::from(Span::styled(
-                "This will create a new backup for the specified instance.",
+                "This adds a new backup of the instance. Nothing is overwritten or lost.",
                 Style::default().fg(Color::White),
             )),
             Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    "[Enter] ",
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("CREATE BACKUP  ", Style::default().fg(Color::White)),
-                Span::styled(
-                    "[Esc] ",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("CANCEL", Style::default().fg(Color::White)),
-            ]),
+            Line::from(
+                [
+                    confirm_hint_spans(app, "CREATE BACKUP", "CANCEL"),
+                    vec![
+                        Span::styled(
+                            format!("[{}] ", app.keymap.label(Action::Labels)),
+                            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled("LABELS  ", Style::default().fg(Color::White)),
+                        Span::styled(
+                            format!("[{}] ", app.keymap.label(Action::Location)),
+                            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled("LOCATION", Style::default().fg(Color::White)),
+                    ],
+                ]
+                .concat(),
+            ),
         ];
         f.render_widget(
             Paragraph::new(instructions_text)
                 .alignment(Alignment::Center)
-                .style(Style::default().bg(Color::DarkGray))
+                .style(Style::default().bg(background))
                 .wrap(Wrap { trim: true }),
             chunks[2],
         );
     }
 }
 
-fn render_help_popup(f: &mut Frame, _app: &App) {
+fn help_line(keys: &str, description: &str) -> String {
+    format!("  {:<10}{}", keys, description)
+}
+
+fn render_help_popup(f: &mut Frame, app: &mut App) {
     let popup_area = centered_rect(80, 70, f.area());
     f.render_widget(Clear, popup_area);
+    let keys = &app.keymap;
 
-    let help_text = vec![
+    let mut help_text = vec![
         Line::from(Span::styled(
             "🔧 HELP - GCP SQL Backup Tool",
             Style::default()
@@ -1418,9 +2643,12 @@ fn render_help_popup(f: &mut Frame, _app: &App) {
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )),
-        Line::from("  ↑/↓       Navigate through lists"),
-        Line::from("  Enter     Select item or confirm action"),
-        Line::from("  Esc       Go back to previous step"),
+        Line::from(help_line(
+            &format!("{}/{}", keys.label(Action::Up), keys.label(Action::Down)),
+            "Navigate through lists",
+        )),
+        Line::from(help_line(&keys.label(Action::Select), "Select item or confirm action")),
+        Line::from(help_line(&keys.label(Action::Back), "Go back to previous step")),
         Line::from(""),
         Line::from(Span::styled(
             "Commands:",
@@ -1428,21 +2656,114 @@ fn render_help_popup(f: &mut Frame, _app: &App) {
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )),
-        Line::from("  M         Manual input for projects/instances"),
-        Line::from("  R         Refresh current list or operation status"),
-        Line::from("  N         Start a new operation"),
-        Line::from("  H         Toggle this help screen"),
-        Line::from("  Q         Quit application"),
+        Line::from(help_line(&keys.label(Action::Mark), "Mark instances for a batch backup")),
+        Line::from(help_line(
+            &keys.label(Action::Manual),
+            "Manual input for projects/instances",
+        )),
+    ];
+    if !app.restore_disabled() {
+        help_text.extend([
+            Line::from(help_line(
+                &keys.label(Action::DateFilter),
+                "Filter backups by date or date range",
+            )),
+            Line::from(help_line(
+                &format!("{}/{}", keys.label(Action::Mark), keys.label(Action::Compare)),
+                "Mark two backups and compare them side by side",
+            )),
+            Line::from(help_line(
+                &keys.label(Action::Retention),
+                "Count the instance's backups by age, to plan pruning",
+            )),
+            Line::from(help_line(
+                &keys.label(Action::ProjectBackups),
+                "List backups of every instance in the source project",
+            )),
+            Line::from(help_line(
+                &keys.label(Action::Sort),
+                "Change the sort order of the project-wide backup list",
+            )),
+            Line::from(help_line(
+                &keys.label(Action::SameAsSource),
+                "Use the source project/instance as the restore target",
+            )),
+            Line::from(help_line(
+                &keys.label(Action::Databases),
+                "Note the databases you care about (restore confirmation)",
+            )),
+        ]);
+    }
+    help_text.extend([
+        Line::from(help_line(
+            &keys.label(Action::Labels),
+            "Add key=value labels to a backup (backup confirmation)",
+        )),
+        Line::from(help_line(
+            &keys.label(Action::Location),
+            "Pin the backup location (backup confirmation)",
+        )),
+        Line::from(help_line(
+            &keys.label(Action::Refresh),
+            "Refresh current list or operation status",
+        )),
+        Line::from(help_line(
+            &keys.label(Action::AccessToken),
+            "Refresh the gcloud access token",
+        )),
+        Line::from(help_line(
+            &keys.label(Action::PastOperations),
+            "Show operations from this and previous sessions",
+        )),
+        Line::from(help_line(
+            &keys.label(Action::PausePolling),
+            "Pause/resume status polling while an operation runs",
+        )),
+        Line::from(help_line(
+            &keys.label(Action::GcloudCommand),
+            "Show the equivalent gcloud command (confirm/progress steps)",
+        )),
+        Line::from(help_line(
+            &format!("{}/{}", keys.label(Action::Details), keys.label(Action::Select)),
+            "Show the full status of the running operation (progress steps)",
+        )),
+        Line::from(help_line(
+            &keys.label(Action::CompactFooter),
+            "Switch the controls bar between essential and all keys",
+        )),
+        Line::from(help_line(&keys.label(Action::New), "Start a new operation")),
+        Line::from(help_line(&keys.label(Action::Help), "Toggle this help screen")),
+        Line::from(help_line(&keys.label(Action::Quit), "Quit application")),
         Line::from(""),
         Line::from(Span::styled(
-            "Press H or Esc to close this help",
+            format!(
+                "Press {} or {} to close this help",
+                keys.label(Action::Help),
+                keys.label(Action::Back)
+            ),
             Style::default().fg(Color::Yellow),
         )),
-    ];
-
+    ]);
+
+    // Clamp the scroll offset here, where the wrapped content height is known.
+    let inner_height = popup_area.height.saturating_sub(2);
+    let max_scroll = wrapped_height(&help_text, popup_area.width.saturating_sub(2))
+        .saturating_sub(inner_height);
+    app.help_scroll = app.help_scroll.min(max_scroll);
+
+    let title = if max_scroll > 0 {
+        format!(
+            "Help ({}/{} to scroll)",
+            keys.label(Action::Up),
+            keys.label(Action::Down)
+        )
+    } else {
+        "Help".to_string()
+    };
     let help = Paragraph::new(help_text)
-        .block(Block::default().borders(Borders::ALL).title("Help"))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .wrap(Wrap { trim: true })
+        .scroll((app.help_scroll, 0))
         .style(Style::default().bg(Color::Black));
 
     f.render_widget(help, popup_area);
@@ -1455,24 +2776,17 @@ fn render_operation_selection(f: &mut Frame, area: Rect, app: &mut App) {
         .border_type(BorderType::Rounded)
         .style(Style::default().fg(ACCENT_COLOR));
 
-    let items = vec![
-        ListItem::new("Restore a backup"),
-        ListItem::new("Create a new backup"),
-    ];
-
-    let list = List::new(items)
-        .block(block)
-        .highlight_style(
-            Style::default()
-                .bg(HIGHLIGHT_BG)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("► ");
-
-    let mut state = ListState::default();
-    state.select(Some(app.selected_operation_index));
+    app.operations.render(f, area, block, list_highlight(), |_, mode| match mode {
+        OperationMode::Restore => ListItem::new("Restore a backup"),
+        OperationMode::CreateBackup => ListItem::new("Create a new backup"),
+    });
+}
 
-    f.render_stateful_widget(list, area, &mut state);
+/// Style of the selected row of every list.
+fn list_highlight() -> Style {
+    Style::default()
+        .bg(HIGHLIGHT_BG)
+        .add_modifier(Modifier::BOLD)
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -1495,6 +2809,19 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// Centers a box of `width` x `height` cells inside `r`, shrinking it to fit when `r` is
+/// smaller than the requested size.
+pub fn centered_rect_fixed(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width);
+    let height = height.min(r.height);
+    Rect {
+        x: r.x.saturating_add((r.width - width) / 2),
+        y: r.y.saturating_add((r.height - height) / 2),
+        width,
+        height,
+    }
+}
+
 fn render_manual_input_popup(f: &mut Frame, app: &App) {
     let area = f.area();
     let min_width = 50;
@@ -1505,19 +2832,26 @@ fn render_manual_input_popup(f: &mut Frame, app: &App) {
         (area.width * 60 / 100).min(max_width).max(min_width)
     };
 
-    let height = 9;
+    let popup_area = centered_rect_fixed(width, 9, area);
 
-    let popup_area = Rect {
-        x: (area.width.saturating_sub(width)) / 2,
-        y: (area.height.saturating_sub(height)) / 2,
-        width,
-        height,
+    let confirm_title = match app.protected_confirm_remaining().as_secs() {
+        0 => "Type the Target Instance Name to Confirm".to_string(),
+        secs => format!("Type the Target Instance Name to Confirm (wait {}s)", secs + 1),
     };
-
     let title = match app.manual_input_type.as_str() {
         "source_project" => "Enter Source Project ID",
+        "confirm_restore" => confirm_title.as_str(),
         "target_project" => "Enter Target Project ID",
+        "backup_name" if app.create_backup_flow.is_batch() => {
+            "Name Template for All Backups ({instance}, {date})"
+        }
         "backup_name" => "Enter a Name for the Backup",
+        "reason" if app.require_reason => "Reason for This Restore (required)",
+        "reason" => "Reason for This Restore (optional, kept in the history)",
+        "databases" => "Databases You Intend to Restore (comma-separated)",
+        "backup_labels" => "Backup Labels (e.g. env=staging;ticket=JIRA-123)",
+        "backup_location" => "Backup Location (e.g. europe-west1 or eu; empty for region)",
+        "backup_date_filter" => "Backup Date or Range (YYYY-MM-DD[..YYYY-MM-DD], empty clears)",
         _ => "Enter Input",
     };
 
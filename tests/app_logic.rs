@@ -1,7 +1,19 @@
-use gcp_snap_crab::app::App;
-use gcp_snap_crab::gcp::MockGcpClientTrait;
-use gcp_snap_crab::types::{AppState, InputMode, OperationMode, SqlInstance};
+use gcp_snap_crab::app::{
+    is_dry_run_operation, App, MAX_REMEMBERED_PROJECTS, MAX_RESTORE_RETRIES,
+    MAX_WATCH_FAILURES, PROTECTED_CONFIRM_DELAY,
+};
+use gcp_snap_crab::failure::FailureKind;
+use gcp_snap_crab::gcp::{instance_busy_error, GcpClientTrait, MockGcpClientTrait};
+use gcp_snap_crab::operation_store::TrackedOperation;
+use gcp_snap_crab::session::SavedSession;
+use gcp_snap_crab::state::watch_flow::WatchFlow;
+use gcp_snap_crab::types::{
+    AppState, Backup, CreateBackupConfig, CreateBackupRequest, DateRange, DisplayZone, InputMode,
+    InstanceStatus, Operation, OperationMode, RestoreConfig, RestoreRequest, SqlInstance,
+};
 use anyhow::anyhow;
+use chrono::{TimeZone, Utc};
+use std::time::Instant;
 
 #[test]
 fn test_app_initialization() {
@@ -31,7 +43,7 @@ async fn test_initialize_success() {
         app.authenticated_user,
         Some("test-user@google.com".to_string())
     );
-    assert!(!app.loading);
+    assert!(!app.loading.any());
 }
 
 #[tokio::test]
@@ -47,14 +59,14 @@ async fn test_initialize_failure() {
 
     assert_eq!(app.state, AppState::Error("gcloud not found".to_string()));
     assert!(app.authenticated_user.is_none());
-    assert!(!app.loading);
+    assert!(!app.loading.any());
 }
 
 #[tokio::test]
 async fn test_select_operation_restore() {
     let mock_gcp_client = MockGcpClientTrait::new();
     let mut app = App::new(Box::new(mock_gcp_client), false);
-    app.selected_operation_index = 0; // Restore
+    app.operations.select(0); // Restore
 
     app.select_current_item().await.unwrap();
 
@@ -68,7 +80,7 @@ async fn test_select_operation_restore() {
 async fn test_select_operation_create_backup() {
     let mock_gcp_client = MockGcpClientTrait::new();
     let mut app = App::new(Box::new(mock_gcp_client), false);
-    app.selected_operation_index = 1; // Create Backup
+    app.operations.select(1); // Create Backup
 
     app.select_current_item().await.unwrap();
 
@@ -86,6 +98,7 @@ async fn test_finish_manual_input_source_project() {
         database_version: "v1".to_string(),
         region: "region-1".to_string(),
         tier: "db-n1-standard-1".to_string(),
+        instance_type: "CLOUD_SQL_INSTANCE".to_string(),
     }];
 
     let mut mock_gcp_client = MockGcpClientTrait::new();
@@ -113,7 +126,7 @@ async fn test_finish_manual_input_source_project() {
     assert!(!app.manual_input_active);
     assert_eq!(app.input_mode, InputMode::Normal);
     assert_eq!(app.restore_flow.instances.len(), 1);
-    assert_eq!(app.restore_flow.instances[0].name, "instance-1");
+    assert_eq!(app.restore_flow.instances.items()[0].name, "instance-1");
 }
 
 #[test]
@@ -122,45 +135,2221 @@ fn test_navigation_instance_selection() {
     let mut app = App::new(Box::new(mock_gcp_client), false);
     app.state = AppState::SelectingInstanceForBackup;
     app.operation_mode = Some(OperationMode::CreateBackup);
-    app.create_backup_flow.instances = vec![
+    app.create_backup_flow.instances.set_items(vec![
         SqlInstance {
             name: "instance-1".to_string(),
             database_version: "".to_string(),
             region: "".to_string(),
             tier: "".to_string(),
+            instance_type: "CLOUD_SQL_INSTANCE".to_string(),
         },
         SqlInstance {
             name: "instance-2".to_string(),
             database_version: "".to_string(),
             region: "".to_string(),
             tier: "".to_string(),
+            instance_type: "CLOUD_SQL_INSTANCE".to_string(),
         },
         SqlInstance {
             name: "instance-3".to_string(),
             database_version: "".to_string(),
             region: "".to_string(),
             tier: "".to_string(),
+            instance_type: "CLOUD_SQL_INSTANCE".to_string(),
         },
-    ];
-    app.create_backup_flow.selected_instance_index = 1;
+    ]);
+    app.create_backup_flow.instances.select(1);
 
     // Move down
     app.move_selection_down();
-    assert_eq!(app.create_backup_flow.selected_instance_index, 2);
+    assert_eq!(app.create_backup_flow.instances.selected(), 2);
 
     // Move down at the end
     app.move_selection_down();
-    assert_eq!(app.create_backup_flow.selected_instance_index, 2);
+    assert_eq!(app.create_backup_flow.instances.selected(), 2);
 
     // Move up
     app.move_selection_up();
-    assert_eq!(app.create_backup_flow.selected_instance_index, 1);
+    assert_eq!(app.create_backup_flow.instances.selected(), 1);
 
     // Move up
     app.move_selection_up();
-    assert_eq!(app.create_backup_flow.selected_instance_index, 0);
+    assert_eq!(app.create_backup_flow.instances.selected(), 0);
 
     // Move up at the start
     app.move_selection_up();
-    assert_eq!(app.create_backup_flow.selected_instance_index, 0);
-}
\ No newline at end of file
+    assert_eq!(app.create_backup_flow.instances.selected(), 0);
+}
+
+#[test]
+fn test_selection_clamps_at_the_ends_by_default() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.backups.set_items(
+        (1..=3)
+            .map(|day| backup(&day.to_string(), Some(day)))
+            .collect(),
+    );
+
+    app.move_selection_up();
+    assert_eq!(app.restore_flow.backups.selected(), 0);
+
+    app.restore_flow.backups.select(2);
+    app.move_selection_down();
+    assert_eq!(app.restore_flow.backups.selected(), 2);
+
+    app.state = AppState::SelectingOperation;
+    app.move_selection_up();
+    assert_eq!(app.operations.selected(), 0);
+}
+
+#[test]
+fn test_wrap_selection_continues_from_the_other_end() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.wrap_selection = true;
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.backups.set_items(
+        (1..=3)
+            .map(|day| backup(&day.to_string(), Some(day)))
+            .collect(),
+    );
+
+    app.move_selection_up();
+    assert_eq!(app.restore_flow.backups.selected(), 2);
+    app.move_selection_down();
+    assert_eq!(app.restore_flow.backups.selected(), 0);
+
+    app.state = AppState::SelectingTargetInstance;
+    app.restore_flow.instances.set_items(instances(&["a", "b"]));
+    app.move_selection_up();
+    assert_eq!(app.restore_flow.instances.selected(), 1);
+    app.move_selection_down();
+    assert_eq!(app.restore_flow.instances.selected(), 0);
+
+    app.state = AppState::SelectingOperation;
+    let last = app.available_operations().len() - 1;
+    app.move_selection_up();
+    assert_eq!(app.operations.selected(), last);
+    app.move_selection_down();
+    assert_eq!(app.operations.selected(), 0);
+}
+
+#[test]
+fn test_wrap_selection_on_an_empty_list_stays_at_zero() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.wrap_selection = true;
+    app.state = AppState::SelectingBackup;
+
+    app.move_selection_up();
+    app.move_selection_down();
+    assert_eq!(app.restore_flow.backups.selected(), 0);
+}
+
+#[tokio::test]
+async fn test_load_instances_applies_engine_filter() {
+    let mut instances = instances(&["mysql-instance", "postgres-instance"]);
+    instances[0].database_version = "MYSQL_8_0".to_string();
+
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .times(1)
+        .returning(move |_| Ok(instances.clone()));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.engine_filter = Some("POSTGRES".to_string());
+
+    app.load_instances("test-project").await.unwrap();
+
+    assert_eq!(app.restore_flow.instances.len(), 1);
+    assert_eq!(
+        app.restore_flow.instances.items()[0].name,
+        "postgres-instance"
+    );
+}
+
+#[test]
+fn test_manual_instance_matches_any_engine() {
+    let mut instance = instances(&["manual-instance"]).remove(0);
+    instance.database_version = SqlInstance::MANUAL.to_string();
+
+    assert!(instance.matches_engine("POSTGRES"));
+    assert!(instance.matches_engine("MYSQL"));
+}
+
+#[test]
+fn test_manual_instances_have_no_known_engine_or_region() {
+    let manual = SqlInstance::manual("typed-instance");
+    assert!(manual.is_manual());
+    assert!(manual.matches_engine("SQLSERVER"));
+    assert_eq!(manual.known_region(), None);
+    assert!(!manual.is_replica());
+
+    let listed = instances(&["listed"]).remove(0);
+    assert!(!listed.is_manual());
+    assert_eq!(listed.known_region(), Some("europe-west1"));
+
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.create_backup_flow.instances.set_items(vec![manual]);
+    assert_eq!(app.create_backup_flow.backup_location("typed-instance"), None);
+}
+
+#[tokio::test]
+async fn test_read_replica_cannot_be_restore_target() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingTargetInstance;
+    let mut replicas = instances(&["replica-1"]);
+    replicas[0].instance_type = "READ_REPLICA_INSTANCE".to_string();
+    app.restore_flow.instances.set_items(replicas);
+
+    app.select_current_item().await.unwrap();
+
+    assert_eq!(app.state, AppState::SelectingTargetInstance);
+    assert!(app.restore_flow.target_instance.is_none());
+    assert!(app.error.unwrap().contains("read replica"));
+}
+
+#[tokio::test]
+async fn test_instance_without_backups_offers_to_create_one() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("db-2".to_string());
+    app.restore_flow
+        .instances
+        .set_items(instances(&["db-1", "db-2"]));
+    assert!(app.no_backups_to_restore());
+
+    app.select_current_item().await.unwrap();
+
+    assert_eq!(app.operation_mode, Some(OperationMode::CreateBackup));
+    assert_eq!(app.state, AppState::EnteringBackupName);
+    assert_eq!(app.manual_input_type, "backup_name");
+    let flow = &app.create_backup_flow;
+    assert_eq!(flow.project.as_deref(), Some("source-project"));
+    assert_eq!(flow.instance.as_deref(), Some("db-2"));
+    assert_eq!(flow.instances.selected(), 1);
+    assert!(app.restore_flow.source_instance.is_none());
+}
+
+#[tokio::test]
+async fn test_backups_hidden_by_a_filter_do_not_offer_a_new_backup() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("db-2".to_string());
+    app.restore_flow.backups.set_items(vec![backup("1", None)]);
+    app.restore_flow
+        .set_date_filter(DateRange::parse("2024-01-01").unwrap(), DisplayZone::Utc);
+
+    app.select_current_item().await.unwrap();
+
+    assert!(!app.no_backups_to_restore());
+    assert_eq!(app.operation_mode, Some(OperationMode::Restore));
+    assert_eq!(app.state, AppState::SelectingBackup);
+}
+
+#[tokio::test]
+async fn test_choosing_another_source_instance_clears_the_date_filter() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_backups()
+        .times(1)
+        .returning(|_, _| Ok(vec![backup("1", Some(1))]));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingSourceInstance;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow
+        .instances
+        .set_items(instances(&["db-1", "db-2"]));
+    app.restore_flow.instances.select(1);
+    app.restore_flow
+        .set_date_filter(DateRange::parse("2024-01-01").unwrap(), DisplayZone::Utc);
+
+    app.select_current_item().await.unwrap();
+
+    assert_eq!(app.state, AppState::SelectingBackup);
+    assert!(app.restore_flow.date_filter().is_none());
+    assert_eq!(app.restore_flow.backups.len(), 1);
+}
+
+#[tokio::test]
+async fn test_dry_run_restore_previews_request_body() {
+    let mock_gcp_client = MockGcpClientTrait::new();
+    let mut app = App::new(Box::new(mock_gcp_client), true);
+    app.restore_flow.config = Some(restore_config());
+
+    app.perform_restore().await.unwrap();
+
+    let preview = app.dry_run_preview.expect("dry run should produce a preview");
+    assert!(preview.contains("/projects/target-project/instances/target-instance/restoreBackup"));
+    assert!(preview.contains("\"backupRunId\": \"1700000000000\""));
+    assert!(preview.contains("\"instanceId\": \"source-instance\""));
+}
+
+#[tokio::test]
+async fn test_noting_intended_databases_updates_restore_config() {
+    let mock_gcp_client = MockGcpClientTrait::new();
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.selected_backup = Some("1700000000000".to_string());
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("source-instance".to_string());
+    app.restore_flow.target_project = Some("target-project".to_string());
+    app.restore_flow.target_instance = Some("target-instance".to_string());
+    app.create_restore_config();
+
+    app.start_manual_input("databases");
+    app.manual_input_buffer = "orders, customers,".to_string();
+    app.finish_manual_input().await.unwrap();
+
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    let config = app.restore_flow.config.unwrap();
+    assert_eq!(config.intended_databases, vec!["orders", "customers"]);
+}
+
+#[tokio::test]
+async fn test_load_instances_failure_records_error_for_panel() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .times(1)
+        .returning(|_| Err(anyhow!("API [sqladmin.googleapis.com] not enabled")));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::CreateBackup);
+
+    app.load_instances("test-project").await.unwrap();
+
+    assert!(app.create_backup_flow.instances.is_empty());
+    assert_eq!(
+        app.instance_load_error,
+        Some("API [sqladmin.googleapis.com] not enabled".to_string())
+    );
+    assert!(app.error.is_some());
+}
+
+#[tokio::test]
+async fn test_batch_backup_tracks_each_instance() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_create_backup()
+        .times(2)
+        .returning(|config| Ok(format!("op-{}", config.instance)));
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(2)
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "BACKUP_VOLUME".to_string(),
+                status: "DONE".to_string(),
+                target_id: "instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::CreateBackup);
+    app.state = AppState::SelectingInstanceForBackup;
+    app.create_backup_flow.project = Some("test-project".to_string());
+    app.create_backup_flow
+        .instances
+        .set_items(instances(&["db-1", "db-2", "db-3"]));
+
+    app.toggle_instance_mark();
+    app.move_selection_down();
+    app.move_selection_down();
+    app.toggle_instance_mark();
+    assert_eq!(app.create_backup_flow.marked_instances, vec!["db-1", "db-3"]);
+
+    app.select_current_item().await.unwrap();
+    assert_eq!(app.state, AppState::EnteringBackupName);
+    app.manual_input_buffer = "nightly-{instance}".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert_eq!(app.state, AppState::ConfirmCreateBackup);
+    assert_eq!(app.create_backup_flow.batch_configs[1].name, "nightly-db-3");
+
+    app.select_current_item().await.unwrap();
+    let operations = &app.create_backup_flow.batch_operations;
+    assert_eq!(operations.len(), 2);
+    assert_eq!(operations[0].operation_id, Some("op-db-1".to_string()));
+    assert_eq!(operations[1].status, "RUNNING");
+
+    app.check_backup_status().await.unwrap();
+    assert_eq!(app.create_backup_flow.batch_progress(), (2, 0, 2));
+}
+
+#[tokio::test]
+async fn test_restore_status_warns_on_unexpected_operation_type() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(1)
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "BACKUP_VOLUME".to_string(),
+                status: "RUNNING".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.restore_flow.operation_id = Some("op-123".to_string());
+    app.restore_flow.config = Some(restore_config());
+
+    app.check_restore_status().await.unwrap();
+
+    assert_eq!(app.restore_flow.status, Some("RUNNING".to_string()));
+    let warning = app.restore_flow.operation_warning.unwrap();
+    assert!(warning.contains("BACKUP_VOLUME"));
+}
+
+#[tokio::test]
+async fn test_operation_details_show_the_last_fetched_status() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(1)
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: "RUNNING".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.state = AppState::PerformingRestore;
+    app.select_current_item().await.unwrap();
+    assert!(!app.show_operation_details, "nothing is running yet");
+
+    app.restore_flow.operation_id = Some("op-123".to_string());
+    app.restore_flow.config = Some(restore_config());
+    app.select_current_item().await.unwrap();
+    assert!(app.show_operation_details);
+    assert!(app.operation_details().is_none());
+
+    app.check_restore_status().await.unwrap();
+    let details = app.operation_details().unwrap();
+    assert_eq!(details.id, "op-123");
+    assert_eq!(details.status, "RUNNING");
+
+    app.state = AppState::PerformingCreateBackup;
+    assert!(app.operation_details().is_none());
+}
+
+/// Mock whose backup listing still contains the backup from `restore_config()`.
+fn mock_with_restore_backup() -> MockGcpClientTrait {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client.expect_list_backups().returning(|_, _| {
+        Ok(vec![Backup {
+            id: "1700000000000".to_string(),
+            start_time: None,
+            backup_type: "AUTOMATED".to_string(),
+            status: "SUCCESSFUL".to_string(),
+            description: String::new(),
+        }])
+    });
+    mock_gcp_client
+        .expect_describe_instance()
+        .returning(|_, _| Ok(InstanceStatus::default()));
+    mock_gcp_client
+}
+
+fn restore_config() -> RestoreConfig {
+    RestoreConfig {
+        backup_id: "1700000000000".to_string(),
+        source_project: "source-project".to_string(),
+        source_instance: "source-instance".to_string(),
+        target_project: "target-project".to_string(),
+        target_instance: "target-instance".to_string(),
+        intended_databases: Vec::new(),
+        reason: None,
+    }
+}
+
+#[tokio::test]
+async fn test_finished_restore_is_marked_with_a_backup_of_the_target() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(2)
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: "DONE".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+    mock_gcp_client
+        .expect_create_backup()
+        .withf(|config| {
+            config.project == "target-project"
+                && config.instance == "target-instance"
+                && config.description.starts_with("restore marker ")
+                && config.description.ends_with(
+                    " | by=ops@example.com;restored-from=1700000000000;\
+                     source=source-project/source-instance",
+                )
+        })
+        .times(1)
+        .returning(|_| Ok("marker-op".to_string()));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.mark_restores = true;
+    app.authenticated_user = Some("ops@example.com".to_string());
+    app.restore_flow.config = Some(restore_config());
+    app.restore_flow.operation_id = Some("op-1".to_string());
+
+    app.check_restore_status().await.unwrap();
+    app.check_restore_status().await.unwrap();
+
+    assert!(app.error.is_none(), "{:?}", app.error);
+    assert_eq!(app.restore_flow.marker_operation_id.as_deref(), Some("marker-op"));
+}
+
+#[tokio::test]
+async fn test_restore_marked_before_a_restart_is_not_marked_again() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(2)
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: "DONE".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+    mock_gcp_client
+        .expect_create_backup()
+        .times(1)
+        .returning(|_| Ok("marker-op".to_string()));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.mark_restores = true;
+    app.operation_store
+        .track(OperationMode::Restore, "target-project", "op-1")
+        .unwrap();
+    app.restore_flow.config = Some(restore_config());
+    app.restore_flow.operation_id = Some("op-1".to_string());
+    app.check_restore_status().await.unwrap();
+    assert_eq!(app.operation_store.marker_of("op-1"), Some("marker-op"));
+
+    // Watching the same restore again from a fresh flow, as after a restart.
+    app.restore_flow = Default::default();
+    app.restore_flow.config = Some(restore_config());
+    app.restore_flow.operation_id = Some("op-1".to_string());
+    app.check_restore_status().await.unwrap();
+
+    assert!(app.error.is_none(), "{:?}", app.error);
+    assert_eq!(app.restore_flow.marker_operation_id, None);
+}
+
+#[tokio::test]
+async fn test_restore_on_busy_instance_schedules_retry() {
+    let mut mock_gcp_client = mock_with_restore_backup();
+    mock_gcp_client
+        .expect_restore_backup()
+        .times(1)
+        .returning(|_, _, _| {
+            Err(instance_busy_error(
+                "Restore operation failed: Operation failed because another operation was \
+                 already in progress.",
+            ))
+        });
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.restore_flow.config = Some(restore_config());
+
+    app.perform_restore().await.unwrap();
+
+    assert_eq!(app.state, AppState::PerformingRestore);
+    assert!(app.restore_flow.retry_at.is_some());
+    assert_eq!(app.restore_flow.retry_attempts, 1);
+    assert!(app.error.is_none());
+}
+
+#[tokio::test]
+async fn test_restore_busy_retries_are_capped() {
+    let mut mock_gcp_client = mock_with_restore_backup();
+    mock_gcp_client
+        .expect_restore_backup()
+        .times(1)
+        .returning(|_, _, _| Err(instance_busy_error("another operation is in progress")));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.restore_flow.config = Some(restore_config());
+    app.restore_flow.retry_attempts = MAX_RESTORE_RETRIES;
+
+    app.perform_restore().await.unwrap();
+
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    assert!(app.restore_flow.retry_at.is_none());
+    assert!(app.error.is_some());
+}
+
+#[tokio::test]
+async fn test_reset_to_operation_selection_matches_fresh_app() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    let fresh = App::new(Box::new(MockGcpClientTrait::new()), false);
+
+    app.authenticated_user = Some("test-user@google.com".to_string());
+    app.remembered_projects = vec!["test-project".to_string()];
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::ConfirmRestore;
+    app.operations.select(1);
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.backups.select(3);
+    app.restore_flow.config = Some(restore_config());
+    app.restore_flow.operation_id = Some("op-123".to_string());
+    app.create_backup_flow.marked_instances = vec!["db-1".to_string()];
+    app.error = Some("boom".to_string());
+    app.dry_run_preview = Some("POST ...".to_string());
+    app.start_manual_input("databases");
+    app.manual_input_buffer = "orders".to_string();
+
+    app.reset_to_operation_selection();
+
+    assert_eq!(app.state, fresh.state);
+    assert_eq!(app.operation_mode, fresh.operation_mode);
+    assert_eq!(app.operations.selected(), fresh.operations.selected());
+    assert_eq!(app.input_mode, fresh.input_mode);
+    assert_eq!(app.manual_input_active, fresh.manual_input_active);
+    assert_eq!(app.manual_input_buffer, fresh.manual_input_buffer);
+    assert_eq!(app.manual_input_type, fresh.manual_input_type);
+    assert_eq!(app.error, fresh.error);
+    assert_eq!(app.dry_run_preview, fresh.dry_run_preview);
+    assert!(app.restore_flow.source_project.is_none());
+    assert!(app.restore_flow.config.is_none());
+    assert!(app.restore_flow.operation_id.is_none());
+    assert_eq!(app.restore_flow.backups.selected(), 0);
+    assert!(app.create_backup_flow.marked_instances.is_empty());
+
+    // Session-wide data survives the reset.
+    assert_eq!(
+        app.authenticated_user,
+        Some("test-user@google.com".to_string())
+    );
+    assert_eq!(app.remembered_projects, vec!["test-project"]);
+}
+
+fn backup(id: &str, day: Option<u32>) -> Backup {
+    Backup {
+        id: id.to_string(),
+        start_time: day.map(|d| Utc.with_ymd_and_hms(2024, 6, d, 12, 0, 0).unwrap()),
+        backup_type: "AUTOMATED".to_string(),
+        status: "SUCCESSFUL".to_string(),
+        description: String::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_backup_date_filter_narrows_selection() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.backups.set_items(vec![
+        backup("1", Some(1)),
+        backup("2", Some(10)),
+        backup("3", None),
+        backup("4", Some(15)),
+        backup("5", Some(30)),
+    ]);
+
+    app.start_manual_input("backup_date_filter");
+    app.manual_input_buffer = "2024-06-10..2024-06-15".to_string();
+    app.finish_manual_input().await.unwrap();
+
+    let visible: Vec<_> = app
+        .restore_flow
+        .backups
+        .filtered_items()
+        .iter()
+        .map(|b| b.id.clone())
+        .collect();
+    assert_eq!(visible, vec!["2", "4"]);
+
+    app.move_selection_down();
+    app.move_selection_down();
+    assert_eq!(app.restore_flow.backups.selected(), 1);
+    app.select_current_item().await.unwrap();
+    assert_eq!(app.restore_flow.selected_backup, Some("4".to_string()));
+}
+
+#[tokio::test]
+async fn test_backup_date_filter_rejects_bad_input_and_clears_on_empty() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow
+        .backups
+        .set_items(vec![backup("1", Some(1)), backup("2", None)]);
+
+    app.start_manual_input("backup_date_filter");
+    app.manual_input_buffer = "last tuesday".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.error.is_some());
+    assert!(app.restore_flow.date_filter().is_none());
+
+    app.error = None;
+    app.manual_input_buffer = "2024-06-01".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert_eq!(app.restore_flow.backups.len(), 1);
+
+    app.start_manual_input("backup_date_filter");
+    app.finish_manual_input().await.unwrap();
+    assert!(app.restore_flow.date_filter().is_none());
+    assert_eq!(app.restore_flow.backups.len(), 2);
+}
+
+#[tokio::test]
+async fn test_loading_flags_are_tracked_per_panel() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_backups()
+        .returning(|_, _| Ok(vec![backup("1", Some(1))]));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.loading.instances = true;
+
+    app.load_backups("test-project", "db-1").await.unwrap();
+
+    assert!(!app.loading.backups);
+    assert!(app.loading.instances);
+    assert!(app.loading.any());
+}
+
+#[tokio::test]
+async fn test_refresh_access_token_records_time_or_error() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_refresh_access_token()
+        .times(1)
+        .returning(|| Ok(()));
+    mock_gcp_client
+        .expect_refresh_access_token()
+        .returning(|| Err(anyhow!("gcloud exited with status 1")));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+
+    app.refresh_access_token().await.unwrap();
+    assert!(app.token_refreshed_at.is_some());
+    assert!(app.error.is_none());
+
+    app.refresh_access_token().await.unwrap();
+    assert!(app.error.unwrap().contains("gcloud exited"));
+}
+
+#[tokio::test]
+async fn test_project_backups_sort_and_prefill_restore_source() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client.expect_list_all_backups().returning(|_| {
+        Ok(vec![
+            ("orders-db".to_string(), backup("1", Some(1))),
+            ("billing-db".to_string(), backup("2", Some(5))),
+            ("orders-db".to_string(), backup("3", Some(9))),
+        ])
+    });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.restore_flow.source_project = Some("test-project".to_string());
+    app.state = AppState::BrowsingProjectBackups;
+
+    app.load_all_backups("test-project").await.unwrap();
+    let ids: Vec<_> = app
+        .restore_flow
+        .project_backups
+        .items()
+        .iter()
+        .map(|(_, b)| b.id.clone())
+        .collect();
+    assert_eq!(ids, vec!["3", "2", "1"]);
+
+    app.toggle_project_backup_sort();
+    let order: Vec<_> = app
+        .restore_flow
+        .project_backups
+        .items()
+        .iter()
+        .map(|(instance, b)| format!("{}/{}", instance, b.id))
+        .collect();
+    assert_eq!(order, vec!["billing-db/2", "orders-db/3", "orders-db/1"]);
+
+    app.move_selection_down();
+    app.move_selection_down();
+    app.select_current_item().await.unwrap();
+
+    assert_eq!(app.state, AppState::SelectingTargetProject);
+    assert_eq!(app.restore_flow.source_instance, Some("orders-db".to_string()));
+    assert_eq!(app.restore_flow.selected_backup, Some("1".to_string()));
+    assert_eq!(app.restore_flow.backups.len(), 2);
+    assert_eq!(app.restore_flow.backups.selected(), 1);
+}
+
+#[tokio::test]
+async fn test_unfinished_operations_are_offered_and_repolled_on_startup() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_check_prerequisites()
+        .returning(|| Ok("test-user@google.com".to_string()));
+    mock_gcp_client
+        .expect_get_operation_status()
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: "DONE".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_store
+        .track(OperationMode::Restore, "target-project", "op-123")
+        .unwrap();
+
+    app.initialize().await.unwrap();
+    assert!(app.resume_prompt);
+
+    app.resume_stored_operations().await.unwrap();
+    assert!(!app.resume_prompt);
+    assert_eq!(app.state, AppState::MonitoringStoredOperations);
+    assert!(app.operation_store.in_flight.is_empty());
+    assert_eq!(app.operation_store.history[0].operation_id, "op-123");
+}
+
+#[tokio::test]
+async fn test_no_restore_mode_only_offers_backups() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.set_restore_disabled(true);
+    assert_eq!(app.available_operations(), vec![OperationMode::CreateBackup]);
+
+    app.move_selection_down();
+    assert_eq!(app.operations.selected(), 0);
+    app.select_current_item().await.unwrap();
+    assert_eq!(app.operation_mode, Some(OperationMode::CreateBackup));
+
+    app.restore_flow.config = Some(restore_config());
+    app.perform_restore().await.unwrap();
+    assert_eq!(
+        app.error.as_deref(),
+        Some("Restore is disabled (--no-restore). Press ESC to clear.")
+    );
+    assert!(app.restore_flow.operation_id.is_none());
+}
+
+#[tokio::test]
+async fn test_restore_fails_fast_when_backup_was_deleted() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_backups()
+        .returning(|_, _| Ok(vec![backup("1699999999999", Some(1))]));
+    mock_gcp_client.expect_restore_backup().never();
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.restore_flow.config = Some(restore_config());
+
+    app.perform_restore().await.unwrap();
+
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    assert!(app.error.unwrap().contains("no longer exists"));
+    assert!(app.restore_flow.operation_id.is_none());
+}
+
+#[tokio::test]
+async fn test_typed_backup_id_is_validated_and_skips_to_target() {
+    let mut app = App::new(Box::new(mock_with_restore_backup()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("source-instance".to_string());
+
+    app.start_manual_input("backup");
+    app.manual_input_buffer = "1234".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.error.take().unwrap().contains("not found"));
+    assert_eq!(app.state, AppState::SelectingBackup);
+
+    app.manual_input_buffer = "1700000000000".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.error.is_none());
+    assert!(!app.manual_input_active);
+    assert_eq!(app.state, AppState::SelectingTargetProject);
+    assert_eq!(app.restore_flow.selected_backup, Some("1700000000000".to_string()));
+    assert_eq!(app.restore_flow.backups.items()[0].backup_type, "AUTOMATED");
+}
+
+#[tokio::test]
+async fn test_unknown_typed_backup_id_is_reported_from_the_background() {
+    let mut app = App::new(Box::new(mock_with_restore_backup()), false);
+    app.enable_background_requests();
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("source-instance".to_string());
+
+    app.start_manual_input("backup");
+    app.manual_input_buffer = "1234".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.loading.backups);
+    assert!(app.error.is_none());
+
+    wait_for_background_results(&mut app, 1).await;
+    assert!(!app.loading.backups);
+    assert_eq!(
+        app.error.as_deref(),
+        Some("Backup 1234 was not found on source-instance. Press ESC to clear.")
+    );
+    assert!(app.manual_input_active);
+    assert_eq!(app.state, AppState::SelectingBackup);
+}
+
+#[tokio::test]
+async fn test_list_refresh_keeps_the_selected_backup() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client.expect_list_backups().times(1).returning(|_, _| {
+        Ok(vec![backup("3", Some(3)), backup("2", Some(2)), backup("1", Some(1))])
+    });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("source-instance".to_string());
+    app.restore_flow
+        .backups
+        .set_items(vec![backup("2", Some(2)), backup("1", Some(1))]);
+    app.restore_flow.backups.select(1);
+    app.restore_flow.compare_marks = vec!["1".to_string()];
+
+    app.refresh_list().await.unwrap();
+
+    let flow = &app.restore_flow;
+    assert_eq!(flow.backups.len(), 3);
+    assert_eq!(flow.backups.selected_item().unwrap().id, "1");
+    assert_eq!(flow.compare_marks, vec!["1".to_string()]);
+}
+
+#[test]
+fn test_auto_refresh_waits_for_an_idle_list() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    assert!(app.idle_in_list());
+
+    app.start_manual_input("backup_date_filter");
+    assert!(!app.idle_in_list());
+    app.cancel_manual_input();
+    app.error = Some("Failed to load backups. Press ESC to clear.".to_string());
+    assert!(!app.idle_in_list());
+    app.error = None;
+    app.restore_flow.show_retention = true;
+    assert!(!app.idle_in_list());
+    app.restore_flow.show_retention = false;
+
+    app.state = AppState::ConfirmRestore;
+    assert!(!app.idle_in_list());
+}
+
+#[tokio::test]
+async fn test_unfinished_or_failed_backup_cannot_be_selected() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    let mut failed = backup("1", Some(1));
+    failed.status = "FAILED".to_string();
+    let mut running = backup("2", Some(2));
+    running.status = "RUNNING".to_string();
+    app.restore_flow.backups.set_items(vec![failed, running]);
+
+    app.select_current_item().await.unwrap();
+    assert_eq!(
+        app.error.take().as_deref(),
+        Some(
+            "Backup 1 has status FAILED and cannot be restored from. Pick a SUCCESSFUL backup. \
+             Press ESC to clear."
+        )
+    );
+    app.restore_flow.backups.select(1);
+    app.select_current_item().await.unwrap();
+    assert!(app.error.unwrap().contains("status RUNNING"));
+    assert_eq!(app.state, AppState::SelectingBackup);
+    assert!(app.restore_flow.selected_backup.is_none());
+}
+
+#[tokio::test]
+async fn test_typed_backup_id_with_unknown_status_is_allowed() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+
+    app.start_manual_input("backup");
+    app.manual_input_buffer = "1234".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.restore_flow.backups.items()[0].status_unknown());
+    app.select_current_item().await.unwrap();
+
+    assert!(app.error.is_none());
+    assert_eq!(app.state, AppState::SelectingTargetProject);
+    assert_eq!(app.restore_flow.selected_backup.as_deref(), Some("1234"));
+}
+
+#[tokio::test]
+async fn test_restore_latest_picks_newest_usable_backup() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_backups()
+        .times(1)
+        .returning(|_, _| {
+            let mut failed = backup("3", Some(20));
+            failed.status = "FAILED".to_string();
+            Ok(vec![backup("1", Some(5)), failed, backup("2", Some(10)), backup("0", None)])
+        });
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .returning(|project| match project {
+            "source-project" => Ok(instances(&["source"])),
+            _ => Ok(instances(&["target"])),
+        });
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    let picked = app
+        .prepare_latest_backup_restore("source-project", "source", "target-project", "target")
+        .await
+        .unwrap();
+
+    assert_eq!(picked.id, "2");
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    assert!(app.restore_flow.auto_selected_backup);
+    let config = app.restore_flow.config.unwrap();
+    assert_eq!(config.backup_id, "2");
+    assert_eq!(config.target_instance, "target");
+}
+
+#[tokio::test]
+async fn test_restore_latest_checks_the_target_like_the_tui() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_backups()
+        .returning(|_, _| Ok(vec![backup("1", Some(5))]));
+    mock_gcp_client.expect_list_sql_instances().returning(|_| {
+        let mut listed = instances(&["source", "replica", "older"]);
+        listed[0].database_version = "POSTGRES_16".to_string();
+        listed[1].instance_type = "READ_REPLICA_INSTANCE".to_string();
+        Ok(listed)
+    });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+
+    let error = app
+        .prepare_latest_backup_restore("project", "source", "project", "replica")
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("read replica"), "{}", error);
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::Usage));
+
+    let error = app
+        .prepare_latest_backup_restore("project", "source", "project", "missing")
+        .await
+        .unwrap_err();
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::NotFound));
+
+    app.prepare_latest_backup_restore("project", "source", "project", "older")
+        .await
+        .unwrap();
+    assert_eq!(
+        app.restore_flow.newer_source_version(),
+        Some(("POSTGRES_16", "POSTGRES_15"))
+    );
+}
+
+#[tokio::test]
+async fn test_restore_latest_is_refused_up_front_with_no_restore() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client.expect_list_backups().never();
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.set_restore_disabled(true);
+
+    let error = app
+        .prepare_latest_backup_restore("project", "source", "project", "target")
+        .await
+        .unwrap_err();
+
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::Usage));
+    assert!(error.to_string().contains("--no-restore"));
+}
+
+#[test]
+fn test_backup_location_defaults_to_instance_region_and_can_be_pinned() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.create_backup_flow.project = Some("project".to_string());
+    app.create_backup_flow.instance = Some("db-1".to_string());
+    app.create_backup_flow
+        .instances
+        .set_items(instances(&["db-1"]));
+
+    app.create_backup_config("nightly".to_string());
+    let location = |app: &App| app.create_backup_flow.config.as_ref().unwrap().location.clone();
+    assert_eq!(location(&app).as_deref(), Some("europe-west1"));
+
+    app.set_backup_location(Some("eu".to_string()));
+    assert_eq!(location(&app).as_deref(), Some("eu"));
+    let request = CreateBackupRequest::from(app.create_backup_flow.config.as_ref().unwrap());
+    assert!(serde_json::to_string(&request).unwrap().contains("\"location\":\"eu\""));
+
+    app.set_backup_location(None);
+    assert_eq!(location(&app).as_deref(), Some("europe-west1"));
+}
+
+#[tokio::test]
+async fn test_restore_is_rejected_while_a_backup_is_running() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.create_backup_flow.operation_id = Some("op-backup".to_string());
+    app.create_backup_flow.status = Some("RUNNING".to_string());
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.config = Some(restore_config());
+
+    app.perform_restore().await.unwrap();
+
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    assert!(app.restore_flow.operation_id.is_none());
+    assert!(app.error.unwrap().contains("op-backup"));
+}
+
+#[tokio::test]
+async fn test_backup_is_rejected_after_a_reset_while_a_restore_runs() {
+    let mut mock_gcp_client = mock_with_restore_backup();
+    mock_gcp_client
+        .expect_restore_backup()
+        .times(1)
+        .returning(|_, _, _| Ok("op-restore".to_string()));
+    mock_gcp_client.expect_create_backup().never();
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(1)
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: "DONE".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.config = Some(restore_config());
+    app.perform_restore().await.unwrap();
+    assert_eq!(app.restore_flow.operation_id.as_deref(), Some("op-restore"));
+
+    app.reset_to_operation_selection();
+    assert!(app.polls_status(), "the restore is still followed");
+    app.state = AppState::ConfirmCreateBackup;
+    app.create_backup_flow.config = Some(CreateBackupConfig {
+        project: "target-project".to_string(),
+        instance: "target-instance".to_string(),
+        name: "nightly".to_string(),
+        description: String::new(),
+        location: None,
+    });
+    app.perform_create_backup().await.unwrap();
+
+    assert_eq!(app.state, AppState::ConfirmCreateBackup);
+    assert!(app.create_backup_flow.operation_id.is_none());
+    assert!(app.error.take().unwrap().contains("op-restore"));
+
+    app.poll_detached_operations().await;
+    assert!(app.running_operation().is_none());
+    assert_eq!(app.operation_store.history[0].status, "DONE");
+}
+
+#[tokio::test]
+async fn test_instance_state_is_polled_only_for_the_relevant_instance() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_describe_instance()
+        .withf(|project, instance| project == "target-project" && instance == "target-instance")
+        .times(1)
+        .returning(|_, _| Ok(InstanceStatus::parse("PENDING_CREATE\tALWAYS")));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.poll_instance_state().await;
+    assert!(app.instance_state.is_none());
+
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.target_project = Some("target-project".to_string());
+    app.restore_flow.target_instance = Some("target-instance".to_string());
+    app.poll_instance_state().await;
+
+    assert_eq!(
+        app.instance_not_ready("target-project", "target-instance"),
+        Some("PENDING_CREATE")
+    );
+    assert_eq!(app.instance_not_ready("target-project", "other-instance"), None);
+    assert_eq!(app.instance_not_ready("other-project", "target-instance"), None);
+}
+
+#[tokio::test]
+async fn test_restore_target_is_still_polled_while_the_restore_runs() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_describe_instance()
+        .withf(|project, instance| project == "target-project" && instance == "target-instance")
+        .times(1)
+        .returning(|_, _| Ok(InstanceStatus::parse("MAINTENANCE\tALWAYS")));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.state = AppState::SelectingTargetInstance;
+    app.restore_flow.target_project = Some("target-project".to_string());
+    app.restore_flow.target_instance = Some("target-instance".to_string());
+    app.poll_instance_state().await;
+    assert!(app.instance_state.is_none(), "choosing a target is not acting on it");
+
+    app.restore_flow.operation_id = Some("op-restore".to_string());
+    app.poll_instance_state().await;
+    assert_eq!(
+        app.instance_not_ready("target-project", "target-instance"),
+        Some("MAINTENANCE")
+    );
+}
+
+#[tokio::test]
+async fn test_instance_state_is_described_in_the_background() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_describe_instance()
+        .times(1)
+        .returning(|_, _| Ok(InstanceStatus::parse("RUNNABLE\tALWAYS")));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.target_project = Some("target-project".to_string());
+    app.restore_flow.target_instance = Some("target-instance".to_string());
+    app.restore_flow.config = Some(restore_config());
+    app.enable_background_requests();
+
+    app.poll_instance_state().await;
+    assert!(app.loading.instance_state, "the poll returns before the instance is described");
+    app.select_current_item().await.unwrap();
+    let error = app.error.take().unwrap();
+    assert!(error.contains("Still checking"), "{}", error);
+    assert_eq!(app.state, AppState::ConfirmRestore);
+
+    wait_for_background_results(&mut app, 1).await;
+    assert!(!app.loading.instance_state);
+    assert!(app.restore_target_in_use());
+}
+
+#[tokio::test]
+async fn test_source_can_be_reused_as_restore_target() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .withf(|project| project == "source-project")
+        .times(1)
+        .returning(|_| Ok(instances(&["other", "source-instance"])));
+    mock_gcp_client
+        .expect_describe_instance()
+        .returning(|_, _| Ok(InstanceStatus::default()));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingTargetProject;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("source-instance".to_string());
+    app.restore_flow.selected_backup = Some("1700000000000".to_string());
+
+    app.use_source_project_as_target().await.unwrap();
+    assert_eq!(app.state, AppState::SelectingTargetInstance);
+    assert_eq!(app.restore_flow.target_project.as_deref(), Some("source-project"));
+
+    app.use_source_instance_as_target().await.unwrap();
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    assert_eq!(app.restore_flow.target_instance.as_deref(), Some("source-instance"));
+}
+
+#[tokio::test]
+async fn test_protected_target_requires_typed_name_and_wait() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), true);
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.config = Some(RestoreConfig {
+        target_project: "acme-prod".to_string(),
+        ..restore_config()
+    });
+    assert!(app.restore_target_protected());
+
+    app.select_current_item().await.unwrap();
+    assert!(app.manual_input_active);
+    assert_eq!(app.manual_input_type, "confirm_restore");
+
+    app.manual_input_buffer = "target-instance".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.error.take().unwrap().contains("Wait"));
+    assert!(app.dry_run_preview.is_none());
+
+    app.restore_flow.confirm_started_at = Some(Instant::now() - PROTECTED_CONFIRM_DELAY);
+    app.manual_input_buffer = "other-instance".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.error.take().unwrap().contains("Type target-instance"));
+
+    app.manual_input_buffer = "target-instance".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(!app.manual_input_active);
+    assert!(app.dry_run_preview.is_some());
+}
+
+#[tokio::test]
+async fn test_unprotected_target_confirms_with_single_enter() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), true);
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.config = Some(restore_config());
+
+    app.select_current_item().await.unwrap();
+
+    assert!(!app.manual_input_active);
+    assert!(app.dry_run_preview.is_some());
+}
+
+#[test]
+fn test_gcloud_command_is_shown_only_for_a_configured_operation() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.state = AppState::ConfirmRestore;
+
+    app.show_gcloud_command();
+    assert!(app.gcloud_preview.is_none());
+    assert!(app.error.take().is_some());
+
+    app.restore_flow.config = Some(restore_config());
+    app.show_gcloud_command();
+    let command = app.gcloud_preview.unwrap();
+    assert!(command.starts_with("gcloud sql backups restore 1700000000000"), "{}", command);
+}
+
+#[test]
+fn test_retention_view_needs_loaded_backups() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.state = AppState::SelectingBackup;
+
+    app.open_retention_view();
+    assert!(!app.restore_flow.show_retention);
+    assert!(app.error.take().is_some());
+
+    app.restore_flow
+        .backups
+        .set_items(vec![backup("1", Some(1))]);
+    app.open_retention_view();
+    assert!(app.restore_flow.show_retention);
+}
+
+#[test]
+fn test_marking_a_third_backup_replaces_the_oldest_mark() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.backups.set_items(
+        (1..=3)
+            .map(|day| backup(&day.to_string(), Some(day)))
+            .collect(),
+    );
+
+    app.open_backup_comparison();
+    assert!(app.error.take().is_some());
+
+    for index in 0..3 {
+        app.restore_flow.backups.select(index);
+        app.restore_flow.toggle_compare_mark();
+    }
+    assert_eq!(app.restore_flow.compare_marks, ["2", "3"]);
+
+    app.open_backup_comparison();
+    assert!(app.restore_flow.show_comparison);
+    let (first, second) = app.restore_flow.compared_backups().unwrap();
+    assert_eq!((first.id.as_str(), second.id.as_str()), ("2", "3"));
+
+    app.restore_flow.toggle_compare_mark();
+    assert_eq!(app.restore_flow.compare_marks, ["2"]);
+    assert!(app.restore_flow.compared_backups().is_none());
+}
+
+#[test]
+fn test_remembered_projects_are_most_recent_first_without_duplicates() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+
+    for project in ["alpha", "beta", "gamma", "ALPHA"] {
+        app.remember_project(project);
+    }
+
+    assert_eq!(app.remembered_projects, ["ALPHA", "gamma", "beta"]);
+}
+
+#[test]
+fn test_remembered_projects_are_capped() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+
+    for index in 0..MAX_REMEMBERED_PROJECTS + 3 {
+        app.remember_project(&format!("project-{}", index));
+    }
+
+    assert_eq!(app.remembered_projects.len(), MAX_REMEMBERED_PROJECTS);
+    assert_eq!(
+        app.remembered_projects[0],
+        format!("project-{}", MAX_REMEMBERED_PROJECTS + 2)
+    );
+    assert!(!app.remembered_projects.contains(&"project-0".to_string()));
+}
+
+#[tokio::test]
+async fn test_dry_run_preview_uses_the_configured_endpoint() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), true);
+    app.api_endpoint = "http://localhost:8080/v1".to_string();
+    app.restore_flow.config = Some(restore_config());
+
+    app.perform_restore().await.unwrap();
+
+    let preview = app.dry_run_preview.unwrap();
+    assert!(
+        preview.starts_with(
+            "POST http://localhost:8080/v1/projects/target-project/instances/target-instance/"
+        ),
+        "{}",
+        preview
+    );
+}
+
+fn instances(names: &[&str]) -> Vec<SqlInstance> {
+    names
+        .iter()
+        .map(|name| SqlInstance {
+            name: name.to_string(),
+            database_version: "POSTGRES_15".to_string(),
+            region: "europe-west1".to_string(),
+            tier: "db-n1-standard-1".to_string(),
+            instance_type: "CLOUD_SQL_INSTANCE".to_string(),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_reloading_lists_keeps_the_selected_item() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .returning(|_| Ok(instances(&["new", "a", "b"])));
+    mock_gcp_client
+        .expect_list_backups()
+        .returning(|_, _| Ok(vec![backup("3", Some(3)), backup("1", Some(1))]));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.restore_flow.instances.set_items(instances(&["a", "b"]));
+    app.restore_flow.instances.select(1);
+    app.restore_flow
+        .backups
+        .set_items(vec![backup("1", Some(1)), backup("2", Some(2))]);
+    app.restore_flow.backups.select(0);
+
+    app.load_instances("project").await.unwrap();
+    app.load_backups("project", "b").await.unwrap();
+    assert_eq!(app.restore_flow.instances.selected(), 2);
+    assert_eq!(app.restore_flow.backups.selected(), 1);
+
+    app.restore_flow.backups.select(0);
+    app.load_backups("project", "b").await.unwrap();
+    assert_eq!(app.restore_flow.backups.selected(), 0);
+}
+
+#[tokio::test]
+async fn test_backing_out_of_backups_reselects_the_source_instance() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .withf(|project| project == "source-project")
+        .times(1)
+        .returning(|_| Ok(instances(&["a", "b", "c"])));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow.source_project = Some("source-project".to_string());
+    app.restore_flow.source_instance = Some("c".to_string());
+    app.restore_flow
+        .backups
+        .set_items(vec![backup("1", Some(1))]);
+
+    app.back_to_source_instances().await.unwrap();
+
+    assert_eq!(app.state, AppState::SelectingSourceInstance);
+    assert_eq!(app.restore_flow.instances.selected(), 2);
+    assert!(app.restore_flow.source_instance.is_none());
+    assert!(app.restore_flow.backups.is_empty());
+}
+
+fn mock_for_start_instance(backups: Result<Vec<Backup>, &'static str>) -> MockGcpClientTrait {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_check_prerequisites()
+        .returning(|| Ok("user@example.com".to_string()));
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .withf(|project| project == "source-project")
+        .returning(|_| Ok(instances(&["a", "b"])));
+    mock_gcp_client
+        .expect_list_backups()
+        .withf(|project, instance| project == "source-project" && instance == "b")
+        .returning(move |_, _| backups.clone().map_err(|e| anyhow!(e)));
+    mock_gcp_client
+}
+
+#[tokio::test]
+async fn test_start_instance_opens_its_backups() {
+    let backups = Ok(vec![backup("1", Some(1))]);
+    let mut app = App::new(Box::new(mock_for_start_instance(backups)), false);
+    app.start_project = Some("source-project".to_string());
+    app.start_instance = Some("b".to_string());
+
+    app.initialize().await.unwrap();
+
+    assert_eq!(app.state, AppState::SelectingBackup);
+    assert_eq!(app.operation_mode, Some(OperationMode::Restore));
+    assert_eq!(app.restore_flow.source_instance.as_deref(), Some("b"));
+    assert_eq!(app.restore_flow.backups.len(), 1);
+    assert_eq!(app.remembered_projects, vec!["source-project".to_string()]);
+    assert!(app.error.is_none());
+}
+
+#[tokio::test]
+async fn test_start_instance_falls_back_to_instance_selection() {
+    let mut app = App::new(Box::new(mock_for_start_instance(Err("denied"))), false);
+    app.start_project = Some("source-project".to_string());
+    app.start_instance = Some("b".to_string());
+
+    app.initialize().await.unwrap();
+    assert_eq!(app.state, AppState::SelectingSourceInstance);
+    assert_eq!(app.restore_flow.instances.selected(), 1);
+    assert!(app.restore_flow.source_instance.is_none());
+    assert!(app.error.as_deref().is_some_and(|e| e.contains("denied")));
+
+    let mut app = App::new(Box::new(mock_for_start_instance(Ok(Vec::new()))), false);
+    app.start_project = Some("source-project".to_string());
+    app.start_instance = Some("missing".to_string());
+
+    app.initialize().await.unwrap();
+    assert_eq!(app.state, AppState::SelectingSourceInstance);
+    assert!(app.error.as_deref().is_some_and(|e| e.contains("missing was not found")));
+}
+
+#[tokio::test]
+async fn test_manual_backup_ids_are_validated_and_deduplicated() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingBackup;
+    app.restore_flow
+        .backups
+        .set_items(vec![backup("1700000000000", Some(1))]);
+
+    app.start_manual_input("backup");
+    for invalid in ["backup-1", "17000 00"] {
+        app.manual_input_buffer = invalid.to_string();
+        app.finish_manual_input().await.unwrap();
+        assert!(app.error.take().unwrap().contains("numeric"), "{}", invalid);
+    }
+    assert_eq!(app.restore_flow.backups.len(), 1);
+
+    app.manual_input_buffer = " 1800000000000 ".to_string();
+    app.finish_manual_input().await.unwrap();
+    app.manual_input_buffer = "1800000000000".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert_eq!(app.restore_flow.backups.len(), 2);
+    assert_eq!(app.restore_flow.backups.items()[1].id, "1800000000000");
+    assert_eq!(app.restore_flow.backups.selected(), 1);
+
+    app.manual_input_buffer = "1700000000000".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert_eq!(app.restore_flow.backups.len(), 2);
+    assert_eq!(app.restore_flow.backups.selected(), 0);
+    assert!(app.error.is_none());
+}
+
+#[tokio::test]
+async fn test_flow_step_without_operation_mode_recovers_to_operation_selection() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client.expect_list_sql_instances().never();
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.state = AppState::SelectingSourceInstance;
+    app.restore_flow.source_project = Some("source-project".to_string());
+
+    app.load_instances("source-project").await.unwrap();
+
+    assert_eq!(app.state, AppState::SelectingOperation);
+    assert!(app.restore_flow.source_project.is_none());
+    assert!(app.error.as_deref().is_some_and(|e| e.contains("start it again")));
+    assert!(!app.loading.any());
+
+    app.error = None;
+    app.state = AppState::SelectingProjectForBackup;
+    app.start_manual_input("source_project");
+    app.manual_input_buffer = "source-project".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert_eq!(app.state, AppState::SelectingOperation);
+    assert!(app.error.is_some());
+    assert!(!app.manual_input_active);
+}
+
+#[test]
+fn test_every_flow_state_belongs_to_one_operation() {
+    assert_eq!(AppState::ConfirmRestore.operation_mode(), Some(OperationMode::Restore));
+    assert_eq!(
+        AppState::EnteringBackupName.operation_mode(),
+        Some(OperationMode::CreateBackup)
+    );
+    assert_eq!(AppState::SelectingOperation.operation_mode(), None);
+    assert_eq!(AppState::MonitoringStoredOperations.operation_mode(), None);
+}
+
+/// An app on the target instance list with everything before it chosen.
+fn app_choosing_target(mock_gcp_client: MockGcpClientTrait) -> App {
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingTargetInstance;
+    let flow = &mut app.restore_flow;
+    flow.source_project = Some("source-project".to_string());
+    flow.source_instance = Some("source-instance".to_string());
+    flow.selected_backup = Some("1700000000000".to_string());
+    flow.target_project = Some("target-project".to_string());
+    flow.instances.set_items(instances(&["target-instance"]));
+    app
+}
+
+#[tokio::test]
+async fn test_restore_reason_is_asked_before_confirming_and_recorded() {
+    let mut mock_gcp_client = mock_with_restore_backup();
+    mock_gcp_client
+        .expect_restore_backup()
+        .times(1)
+        .returning(|_, _, _| Ok("op-1".to_string()));
+    let mut app = app_choosing_target(mock_gcp_client);
+
+    app.select_current_item().await.unwrap();
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    assert!(app.manual_input_active);
+    assert_eq!(app.manual_input_type, "reason");
+
+    app.manual_input_buffer = " INC-42 data fix ".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert!(!app.manual_input_active);
+    let config = app.restore_flow.config.as_ref().unwrap();
+    assert_eq!(config.reason.as_deref(), Some("INC-42 data fix"));
+
+    app.select_current_item().await.unwrap();
+    let tracked = app.operation_store.all().next().unwrap();
+    assert_eq!(tracked.reason.as_deref(), Some("INC-42 data fix"));
+}
+
+#[tokio::test]
+async fn test_intended_databases_are_recorded_with_the_restore() {
+    let mut mock_gcp_client = mock_with_restore_backup();
+    mock_gcp_client
+        .expect_restore_backup()
+        .times(1)
+        .returning(|_, _, _| Ok("op-1".to_string()));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.config = Some(RestoreConfig {
+        intended_databases: vec!["orders".to_string(), "billing".to_string()],
+        ..restore_config()
+    });
+
+    app.perform_restore().await.unwrap();
+
+    let tracked = app.operation_store.all().next().unwrap();
+    assert_eq!(tracked.databases, ["orders", "billing"]);
+}
+
+#[tokio::test]
+async fn test_required_reason_blocks_the_restore() {
+    let mut mock_gcp_client = mock_with_restore_backup();
+    mock_gcp_client.expect_restore_backup().never();
+    let mut app = app_choosing_target(mock_gcp_client);
+    app.require_reason = true;
+
+    app.select_current_item().await.unwrap();
+    app.finish_manual_input().await.unwrap();
+    assert!(app.error.take().unwrap().contains("reason is required"));
+    assert!(app.manual_input_active);
+
+    app.cancel_manual_input();
+    app.select_current_item().await.unwrap();
+    assert!(app.manual_input_active);
+    assert_eq!(app.manual_input_type, "reason");
+
+    app.perform_restore().await.unwrap();
+    assert!(app.error.unwrap().contains("reason is required"));
+    assert_eq!(app.state, AppState::ConfirmRestore);
+}
+
+#[tokio::test]
+async fn test_target_in_use_requires_typed_name() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_describe_instance()
+        .withf(|project, instance| project == "target-project" && instance == "target-instance")
+        .returning(|_, _| Ok(InstanceStatus::parse("RUNNABLE\tALWAYS")));
+    mock_gcp_client.expect_restore_backup().never();
+    let mut app = app_choosing_target(mock_gcp_client);
+
+    app.select_current_item().await.unwrap();
+    app.cancel_manual_input();
+    assert!(app.restore_target_in_use());
+    assert!(!app.restore_target_protected());
+
+    app.select_current_item().await.unwrap();
+    assert!(app.manual_input_active);
+    assert_eq!(app.manual_input_type, "confirm_restore");
+}
+
+#[tokio::test]
+async fn test_dry_run_operations_are_never_polled_after_leaving_dry_run() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client.expect_get_operation_status().never();
+    let mut app = App::new(Box::new(mock_gcp_client), true);
+    app.restore_flow.config = Some(restore_config());
+    app.perform_restore().await.unwrap();
+    let operation_id = app.restore_flow.operation_id.clone().unwrap();
+    assert!(is_dry_run_operation(&operation_id));
+
+    app.dry_run_mode = false;
+    app.restore_flow.status = Some("RUNNING".to_string());
+    app.check_restore_status().await.unwrap();
+    assert_eq!(app.restore_flow.status.as_deref(), Some("DONE"));
+
+    app.operation_store
+        .track(OperationMode::Restore, "target-project", &operation_id)
+        .unwrap();
+    app.poll_stored_operations().await.unwrap();
+    assert!(app.operation_store.in_flight.is_empty());
+    assert!(app.error.is_none());
+    assert!(!is_dry_run_operation("operation-1"));
+}
+
+#[tokio::test]
+async fn test_session_summary_lists_operations_with_their_latest_status() {
+    let mut mock_gcp_client = mock_with_restore_backup();
+    mock_gcp_client
+        .expect_restore_backup()
+        .returning(|_, _, _| Ok("op-1".to_string()));
+    mock_gcp_client
+        .expect_get_operation_status()
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: "DONE".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_store
+        .track(OperationMode::Restore, "other-project", "op-from-last-session")
+        .unwrap();
+    assert!(app.session_summary().is_empty());
+
+    app.restore_flow.config = Some(restore_config());
+    app.perform_restore().await.unwrap();
+    app.check_restore_status().await.unwrap();
+    app.reset_to_operation_selection();
+
+    assert_eq!(
+        app.session_summary(),
+        vec!["Restore of target-project/target-instance (op-1): DONE".to_string()]
+    );
+}
+
+fn mock_for_resume(backup_ids: &'static [&'static str]) -> MockGcpClientTrait {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_check_prerequisites()
+        .returning(|| Ok("user@example.com".to_string()));
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .returning(|project| match project {
+            "source-project" => Ok(instances(&["a", "source-instance"])),
+            _ => Ok(instances(&["b", "target-instance"])),
+        });
+    mock_gcp_client.expect_list_backups().returning(move |_, _| {
+        Ok(backup_ids.iter().map(|id| backup(id, Some(1))).collect())
+    });
+    mock_gcp_client
+        .expect_describe_instance()
+        .returning(|_, _| Ok(InstanceStatus::default()));
+    mock_gcp_client
+}
+
+fn saved_session() -> SavedSession {
+    SavedSession {
+        source_project: Some("source-project".to_string()),
+        source_instance: Some("source-instance".to_string()),
+        backup_id: Some("1700000000000".to_string()),
+        target_project: Some("target-project".to_string()),
+        target_instance: Some("target-instance".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_resumed_session_replays_selections_up_to_confirmation() {
+    let mut app = App::new(Box::new(mock_for_resume(&["1", "1700000000000"])), false);
+    app.resumed_session = Some(saved_session());
+
+    app.initialize().await.unwrap();
+
+    assert_eq!(app.state, AppState::ConfirmRestore);
+    assert!(app.error.is_none());
+    let config = app.restore_flow.config.as_ref().unwrap();
+    assert_eq!(config.source(), "source-project/source-instance");
+    assert_eq!(config.target(), "target-project/target-instance");
+    assert_eq!(config.backup_id, "1700000000000");
+    assert!(app.restore_flow.operation_id.is_none());
+}
+
+#[tokio::test]
+async fn test_resumed_session_stops_at_a_selection_that_is_gone() {
+    let mut app = App::new(Box::new(mock_for_resume(&["1"])), false);
+    app.resumed_session = Some(saved_session());
+    app.initialize().await.unwrap();
+    assert_eq!(app.state, AppState::SelectingBackup);
+    assert!(app.error.unwrap().contains("1700000000000 no longer exists"));
+
+    let mut session = saved_session();
+    session.target_instance = Some("deleted".to_string());
+    let mut app = App::new(Box::new(mock_for_resume(&["1700000000000"])), false);
+    app.resumed_session = Some(session);
+    app.initialize().await.unwrap();
+    assert_eq!(app.state, AppState::SelectingTargetInstance);
+    assert!(app.restore_flow.config.is_none());
+    let error = app.error.unwrap();
+    assert!(
+        error.contains("Target instance deleted from the saved session is no longer in"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_watch_operation_polls_until_the_operation_finishes() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    let mut polls = 0;
+    mock_gcp_client
+        .expect_get_operation_status()
+        .withf(|project, operation_id| project == "project" && operation_id == "op-9")
+        .times(4)
+        .returning(move |_, operation_id| {
+            polls += 1;
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: match polls {
+                    1 => "PENDING",
+                    2 | 3 => "RUNNING",
+                    _ => "DONE",
+                }
+                .to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+    let app = App::new(Box::new(mock_gcp_client), false);
+
+    let mut seen = Vec::new();
+    let operation = app
+        .watch_operation(
+            "project",
+            "op-9",
+            std::time::Duration::ZERO,
+            |operation| seen.push(operation.to_string()),
+            |error| panic!("unexpected retry: {}", error),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(operation.status, "DONE");
+    assert_eq!(
+        seen,
+        [
+            "RESTORE_VOLUME op-9: PENDING",
+            "RESTORE_VOLUME op-9: RUNNING",
+            "RESTORE_VOLUME op-9: DONE",
+        ],
+        "unchanged polls are not reported again"
+    );
+}
+
+fn watched_operation(status: &str) -> Operation {
+    Operation {
+        id: "op-9".to_string(),
+        operation_type: "RESTORE_VOLUME".to_string(),
+        status: status.to_string(),
+        target_id: "target-instance".to_string(),
+        start_time: None,
+        end_time: None,
+        error_message: None,
+    }
+}
+
+#[tokio::test]
+async fn test_watch_operation_retries_failed_checks() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    let mut polls = 0;
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(3)
+        .returning(move |_, _| {
+            polls += 1;
+            match polls {
+                1 => Ok(watched_operation("RUNNING")),
+                2 => Err(anyhow!("connection reset")),
+                _ => Ok(watched_operation("DONE")),
+            }
+        });
+    let app = App::new(Box::new(mock_gcp_client), false);
+
+    let mut retried = Vec::new();
+    let operation = app
+        .watch_operation(
+            "project",
+            "op-9",
+            std::time::Duration::ZERO,
+            |_| {},
+            |error| retried.push(error.to_string()),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(operation.status, "DONE");
+    assert_eq!(retried, ["connection reset"]);
+}
+
+#[tokio::test]
+async fn test_watch_operation_gives_up_on_a_missing_operation_or_repeated_failures() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(1)
+        .returning(|_, _| Err(FailureKind::NotFound.error("operation not found")));
+    let app = App::new(Box::new(mock_gcp_client), false);
+    let error = app
+        .watch_operation("project", "op-9", std::time::Duration::ZERO, |_| {}, |_| {})
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "operation not found");
+
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(MAX_WATCH_FAILURES as usize)
+        .returning(|_, _| Err(anyhow!("connection reset")));
+    let app = App::new(Box::new(mock_gcp_client), false);
+    let error = app
+        .watch_operation("project", "op-9", std::time::Duration::ZERO, |_| {}, |_| {})
+        .await
+        .unwrap_err();
+    assert_eq!(error.to_string(), "connection reset");
+}
+
+#[tokio::test]
+async fn test_watch_screen_polls_the_operation_until_it_finishes() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_check_prerequisites()
+        .returning(|| Ok("test-user@google.com".to_string()));
+    let mut polls = 0;
+    mock_gcp_client
+        .expect_get_operation_status()
+        .withf(|project, operation_id| project == "project" && operation_id == "op-9")
+        .times(3)
+        .returning(move |_, _| {
+            polls += 1;
+            match polls {
+                1 => Ok(watched_operation("RUNNING")),
+                2 => Err(anyhow!("connection reset")),
+                _ => Ok(watched_operation("DONE")),
+            }
+        });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.watch_flow = Some(WatchFlow::new("project", "op-9"));
+
+    app.initialize().await.unwrap();
+    assert_eq!(app.state, AppState::WatchingOperation);
+    let flow = app.watch_flow.as_ref().unwrap();
+    assert_eq!(flow.operation.as_ref().unwrap().status, "RUNNING");
+
+    app.poll_watched_operation().await;
+    let flow = app.watch_flow.as_ref().unwrap();
+    assert_eq!(flow.operation.as_ref().unwrap().status, "RUNNING");
+    assert_eq!(
+        flow.poll_error.as_deref(),
+        Some("Failed to check status: connection reset")
+    );
+
+    app.poll_watched_operation().await;
+    let flow = app.watch_flow.as_ref().unwrap();
+    assert!(flow.is_finished());
+    assert!(flow.poll_error.is_none());
+
+    // Finished operations are not checked again.
+    app.poll_watched_operation().await;
+}
+
+/// Applies background results as they arrive until `count` have, failing the test if they
+/// take more than a few seconds.
+async fn wait_for_background_results(app: &mut App, count: usize) {
+    let mut arrived = 0;
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while arrived < count {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            arrived += app.apply_background_results().await;
+        }
+    })
+    .await
+    .expect("the background requests did not finish");
+}
+
+#[tokio::test]
+async fn test_background_loads_are_applied_on_the_next_tick_unless_stale() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .times(2)
+        .returning(|_| Ok(instances(&["db-1"])));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingSourceInstance;
+    app.enable_background_requests();
+
+    app.load_instances("project").await.unwrap();
+    assert!(app.loading.instances, "the handler returns before the list arrives");
+    assert!(app.restore_flow.instances.is_empty());
+    wait_for_background_results(&mut app, 1).await;
+    assert!(!app.loading.instances);
+    assert_eq!(app.restore_flow.instances.len(), 1);
+
+    app.restore_flow.instances.clear();
+    app.load_instances("project").await.unwrap();
+    app.invalidate_pending_requests();
+    wait_for_background_results(&mut app, 1).await;
+    assert!(app.restore_flow.instances.is_empty(), "the user left before it arrived");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_an_older_list_arriving_late_does_not_overwrite_a_newer_one() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .times(2)
+        .returning(|project| {
+            if project == "slow" {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+            Ok(instances(&[project]))
+        });
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::SelectingSourceInstance;
+    app.enable_background_requests();
+
+    app.load_instances("slow").await.unwrap();
+    app.load_instances("fast").await.unwrap();
+    wait_for_background_results(&mut app, 2).await;
+
+    assert_eq!(app.restore_flow.instances.items()[0].name, "fast");
+    assert!(!app.loading.instances);
+}
+
+#[tokio::test]
+async fn test_operations_are_started_and_checked_in_the_background() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_backups()
+        .returning(|_, _| Ok(vec![backup("1700000000000", Some(1))]));
+    mock_gcp_client
+        .expect_restore_backup()
+        .times(1)
+        .returning(|_, _, _| Ok("op-restore".to_string()));
+    mock_gcp_client
+        .expect_get_operation_status()
+        .times(1)
+        .returning(|_, operation_id| {
+            Ok(Operation {
+                id: operation_id.to_string(),
+                operation_type: "RESTORE_VOLUME".to_string(),
+                status: "DONE".to_string(),
+                target_id: "target-instance".to_string(),
+                start_time: None,
+                end_time: None,
+                error_message: None,
+            })
+        });
+    mock_gcp_client
+        .expect_create_backup()
+        .times(1)
+        .returning(|_| Ok("op-marker".to_string()));
+
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.mark_restores = true;
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.config = Some(restore_config());
+    app.enable_background_requests();
+
+    app.perform_restore().await.unwrap();
+    assert_eq!(app.state, AppState::PerformingRestore, "the key handler does not wait");
+    assert!(app.loading.operation_status);
+    wait_for_background_results(&mut app, 1).await;
+    assert_eq!(app.restore_flow.operation_id.as_deref(), Some("op-restore"));
+
+    app.check_restore_status().await.unwrap();
+    assert!(app.restore_flow.status.as_deref() != Some("DONE"));
+    wait_for_background_results(&mut app, 1).await;
+    assert_eq!(app.restore_flow.status.as_deref(), Some("DONE"));
+    wait_for_background_results(&mut app, 1).await;
+    assert_eq!(app.restore_flow.marker_operation_id.as_deref(), Some("op-marker"));
+}
+
+/// A client whose prerequisite check never finishes, like gcloud stuck on a prompt.
+struct HangingClient;
+
+/// Body of the [`HangingClient`] methods that the prerequisite check never gets to.
+fn not_called<T>() -> T {
+    unreachable!("only check_prerequisites is called")
+}
+
+#[async_trait::async_trait]
+impl GcpClientTrait for HangingClient {
+    async fn check_prerequisites(&self) -> anyhow::Result<String> {
+        std::future::pending().await
+    }
+    async fn list_sql_instances(&self, _: &str) -> anyhow::Result<Vec<SqlInstance>> {
+        not_called()
+    }
+    async fn list_backups(&self, _: &str, _: &str) -> anyhow::Result<Vec<Backup>> {
+        not_called()
+    }
+    async fn list_all_backups(&self, _: &str) -> anyhow::Result<Vec<(String, Backup)>> {
+        not_called()
+    }
+    async fn describe_instance(&self, _: &str, _: &str) -> anyhow::Result<InstanceStatus> {
+        not_called()
+    }
+    async fn get_operation_status(&self, _: &str, _: &str) -> anyhow::Result<Operation> {
+        not_called()
+    }
+    async fn restore_backup(&self, _: &RestoreRequest, _: &str, _: &str) -> anyhow::Result<String> {
+        not_called()
+    }
+    async fn create_backup(&self, _: &CreateBackupConfig) -> anyhow::Result<String> {
+        not_called()
+    }
+    async fn refresh_access_token(&self) -> anyhow::Result<()> {
+        not_called()
+    }
+}
+
+#[tokio::test]
+async fn test_prerequisite_check_that_hangs_times_out_into_an_error() {
+    let mut app = App::new(Box::new(HangingClient), false);
+    app.prereq_timeout = std::time::Duration::from_millis(10);
+
+    app.initialize().await.unwrap();
+
+    match &app.state {
+        AppState::Error(message) => {
+            assert!(message.starts_with("Prerequisite check timed out"), "{}", message)
+        }
+        state => panic!("expected the error state, got {:?}", state),
+    }
+}
+
+#[tokio::test]
+async fn test_inventory_keeps_instances_that_could_not_be_described() {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client
+        .expect_list_sql_instances()
+        .times(1)
+        .returning(|_| Ok(instances(&["db-1", "db-2"])));
+    mock_gcp_client
+        .expect_describe_instance()
+        .times(2)
+        .returning(|_, instance| match instance {
+            "db-1" => Ok(InstanceStatus::parse("RUNNABLE\tALWAYS\t7\t3\t100\tREGIONAL")),
+            _ => Err(anyhow!("permission denied")),
+        });
+    let app = App::new(Box::new(mock_gcp_client), false);
+
+    let inventory = app.collect_inventory("project").await.unwrap();
+
+    assert_eq!(inventory.failures(), 1);
+    let json = serde_json::to_value(&inventory).unwrap();
+    let instances = json["instances"].as_array().unwrap();
+    assert_eq!(instances[0]["name"], "db-1");
+    assert_eq!(instances[0]["disk_size_gb"], 100);
+    assert_eq!(instances[0]["high_availability"], true);
+    assert_eq!(instances[0]["maintenance_window"], "Sun 03:00 UTC");
+    assert!(instances[0].get("error").is_none());
+    assert_eq!(instances[1]["tier"], "db-n1-standard-1");
+    assert_eq!(instances[1]["state"], serde_json::Value::Null);
+    assert_eq!(instances[1]["error"], "permission denied");
+}
+
+#[test]
+fn test_restore_estimate_prefers_earlier_restores_over_disk_size() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    let config = restore_config();
+    assert_eq!(app.restore_estimate(&config), None, "nothing to go on");
+
+    let disk = InstanceStatus::parse("RUNNABLE\tALWAYS\t\t\t250\tZONAL");
+    let described = |project: &str| (project.to_string(), "target-instance".to_string());
+    app.instance_state = Some((described("other-project"), disk.clone()));
+    assert_eq!(app.restore_estimate(&config), None, "a same-named instance elsewhere");
+
+    app.instance_state = Some((described("target-project"), disk));
+    assert_eq!(
+        app.restore_estimate(&config).as_deref(),
+        Some("~30 minutes (estimate for the 250 GB disk)")
+    );
+
+    for (minutes, instance) in [(10, "target-instance"), (20, "target-instance"), (90, "other")] {
+        let mut restore = TrackedOperation::new(OperationMode::Restore, "target-project", "op");
+        restore.instance = Some(instance.to_string());
+        restore.status = "DONE".to_string();
+        restore.finished_at = Some(restore.started_at + chrono::TimeDelta::minutes(minutes));
+        app.operation_store.history.push(restore);
+    }
+    assert_eq!(
+        app.restore_estimate(&config).as_deref(),
+        Some("~15 minutes (estimate from 2 earlier restores onto this instance)")
+    );
+}
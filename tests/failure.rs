@@ -0,0 +1,62 @@
+use gcp_snap_crab::failure::FailureKind;
+use reqwest::StatusCode;
+
+#[test]
+fn test_each_failure_class_has_its_own_exit_code() {
+    let codes: Vec<i32> = [
+        FailureKind::Usage,
+        FailureKind::Prerequisites,
+        FailureKind::NotFound,
+        FailureKind::OperationFailed,
+        FailureKind::Timeout,
+    ]
+    .into_iter()
+    .map(FailureKind::exit_code)
+    .collect();
+
+    assert_eq!(codes, vec![2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn test_failure_class_survives_added_context() {
+    let error = FailureKind::NotFound
+        .error("Instance db-1 not found")
+        .context("Restore failed");
+
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::NotFound));
+    assert_eq!(FailureKind::exit_code_for(&error), 4);
+    assert_eq!(format!("{:#}", error), "Restore failed: Instance db-1 not found");
+}
+
+#[test]
+fn test_unclassified_errors_exit_with_one() {
+    let error = anyhow::anyhow!("Restore cancelled");
+
+    assert_eq!(FailureKind::of(&error), None);
+    assert_eq!(FailureKind::exit_code_for(&error), 1);
+}
+
+#[test]
+fn test_api_statuses_are_classified() {
+    assert_eq!(FailureKind::from_status(StatusCode::FORBIDDEN), FailureKind::Prerequisites);
+    assert_eq!(FailureKind::from_status(StatusCode::UNAUTHORIZED), FailureKind::Prerequisites);
+    assert_eq!(FailureKind::from_status(StatusCode::NOT_FOUND), FailureKind::NotFound);
+    assert_eq!(FailureKind::from_status(StatusCode::GATEWAY_TIMEOUT), FailureKind::Timeout);
+    assert_eq!(FailureKind::from_status(StatusCode::CONFLICT), FailureKind::OperationFailed);
+}
+
+#[test]
+fn test_gcloud_stderr_is_classified() {
+    assert_eq!(
+        FailureKind::from_gcloud_stderr(
+            "ERROR: (gcloud.sql.backups.list) PERMISSION_DENIED: The caller does not have \
+             permission"
+        ),
+        Some(FailureKind::Prerequisites)
+    );
+    assert_eq!(
+        FailureKind::from_gcloud_stderr("ERROR: (gcloud.sql.instances.list) HTTPError 404"),
+        Some(FailureKind::NotFound)
+    );
+    assert_eq!(FailureKind::from_gcloud_stderr("ERROR: something else broke"), None);
+}
@@ -0,0 +1,487 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use gcp_snap_crab::failure::FailureKind;
+use gcp_snap_crab::gcp::{
+    api_disabled_error, gcloud_backup_command, gcloud_restore_command, gcloud_token_error,
+    is_instance_busy, is_instance_busy_error, parse_instance_line, parse_response_body,
+    pick_active_account, Gcloud, GcloudTokenSource, GcpClient, GcpClientTrait, TokenSource,
+    USER_AGENT,
+};
+use gcp_snap_crab::types::{CreateBackupConfig, GcpApiResponse, RestoreConfig, RestoreRequest};
+use reqwest::StatusCode;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+#[test]
+fn test_parse_response_body_reports_status_and_snippet() {
+    let body = format!("<html><body>Proxy error{}</body></html>", " ".repeat(500));
+    let error = parse_response_body::<GcpApiResponse>(StatusCode::OK, &body, "operation status")
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("operation status"));
+    assert!(error.contains("200 OK"));
+    assert!(error.contains("<html><body>Proxy error"));
+    assert!(error.len() < 400);
+}
+
+#[test]
+fn test_parse_response_body_accepts_valid_json() {
+    let response: GcpApiResponse =
+        parse_response_body(StatusCode::OK, r#"{"status": "DONE"}"#, "operation status").unwrap();
+    assert_eq!(response.status.as_deref(), Some("DONE"));
+}
+
+/// A Cloud SQL Admin API error body.
+fn api_error(code: u16, message: &str, reason: &str) -> String {
+    serde_json::json!({
+        "error": {"code": code, "message": message, "errors": [{"reason": reason}]}
+    })
+    .to_string()
+}
+
+#[test]
+fn test_busy_errors_are_detected() {
+    let busy = api_error(400, "Operation failed because another operation was already in \
+         progress.", "operationInProgress");
+    assert!(is_instance_busy_error(StatusCode::BAD_REQUEST, &busy));
+    assert!(is_instance_busy_error(StatusCode::CONFLICT, "conflict"));
+
+    let other = api_error(400, "A pending operation on another instance failed.", "invalid");
+    assert!(!is_instance_busy_error(StatusCode::BAD_REQUEST, &other));
+    assert!(!is_instance_busy_error(StatusCode::NOT_FOUND, "The backup run does not exist"));
+}
+
+#[test]
+fn test_parse_instance_line_keeps_instances_with_empty_fields() {
+    let instance = parse_instance_line("db-1\tPOSTGRES_15\t\tdb-custom-2-7680").unwrap();
+    assert_eq!(instance.name, "db-1");
+    assert_eq!(instance.region, "");
+    assert_eq!(instance.tier, "db-custom-2-7680");
+
+    let truncated = parse_instance_line("db-2\tMYSQL_8_0").unwrap();
+    assert_eq!(truncated.database_version, "MYSQL_8_0");
+    assert_eq!(truncated.tier, "");
+
+    assert!(parse_instance_line("").is_none());
+}
+
+#[test]
+fn test_pick_active_account_from_multi_line_output() {
+    let output = "  ops@example.com\n\nci@project.iam.gserviceaccount.com  \n";
+
+    assert_eq!(pick_active_account(output, None).unwrap(), "ops@example.com");
+    assert_eq!(
+        pick_active_account(output, Some("ci@project.iam.gserviceaccount.com")).unwrap(),
+        "ci@project.iam.gserviceaccount.com"
+    );
+    let error = pick_active_account(output, Some("me@example.com"))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("me@example.com is not active"), "{}", error);
+    assert!(pick_active_account("\n", None).is_err());
+}
+
+#[test]
+fn test_gcloud_commands_mirror_the_config() {
+    let restore = RestoreConfig {
+        backup_id: "1700000000000".to_string(),
+        source_project: "src-project".to_string(),
+        source_instance: "db-1".to_string(),
+        target_project: "dst-project".to_string(),
+        target_instance: "db-2".to_string(),
+        intended_databases: Vec::new(),
+        reason: None,
+    };
+    assert_eq!(
+        gcloud_restore_command(&restore),
+        "gcloud sql backups restore 1700000000000 --restore-instance=db-2 \
+         --project=dst-project --backup-instance=db-1 --backup-project=src-project"
+    );
+
+    let backup = CreateBackupConfig {
+        project: "src-project".to_string(),
+        instance: "db-1".to_string(),
+        name: "before it's migrated".to_string(),
+        description: "before it's migrated".to_string(),
+        location: Some("eu".to_string()),
+    };
+    assert_eq!(
+        gcloud_backup_command(&backup),
+        "gcloud sql backups create --instance=db-1 --project=src-project \
+         --description='before it'\\''s migrated' --location=eu"
+    );
+}
+
+#[test]
+fn test_api_disabled_403_becomes_an_instruction() {
+    let body = r#"{"error": {"code": 403, "message": "Cloud SQL Admin API has not been used in
+        project 123 before or it is disabled.", "status": "PERMISSION_DENIED",
+        "details": [{"reason": "SERVICE_DISABLED"}]}}"#;
+
+    let message = api_disabled_error(StatusCode::FORBIDDEN, body, "my-project").unwrap();
+    assert!(message.contains("Enable the Cloud SQL Admin API on project my-project"));
+    assert!(message.contains("--project=my-project"));
+
+    let denied = r#"{"error": {"code": 403, "message": "The caller does not have permission"}}"#;
+    assert!(api_disabled_error(StatusCode::FORBIDDEN, denied, "my-project").is_none());
+    assert!(api_disabled_error(StatusCode::NOT_FOUND, body, "my-project").is_none());
+}
+
+struct FixedToken;
+
+#[async_trait]
+impl TokenSource for FixedToken {
+    async fn fetch_token(&self, _gcloud: &Gcloud) -> Result<(String, Duration)> {
+        Ok(("test-token".to_string(), Duration::from_secs(3600)))
+    }
+}
+
+/// What the mock server received: the request line, lowercased headers and the body.
+struct RecordedRequest {
+    request_line: String,
+    headers: String,
+    body: String,
+}
+
+/// Serves one canned response on a local port. Returns a client pointed at it and a
+/// handle that yields the request it received.
+async fn serve_once(status: &str, body: &str) -> (GcpClient, JoinHandle<RecordedRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}/v1/", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut data = Vec::new();
+        let mut buffer = [0u8; 4096];
+        loop {
+            let read = socket.read(&mut buffer).await.unwrap();
+            data.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&data).to_string();
+            let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        let line = line.to_lowercase();
+                        line.strip_prefix("content-length:")
+                            .map(|value| value.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                body.len() >= length
+            });
+            if complete || read == 0 {
+                break;
+            }
+        }
+        socket.write_all(response.as_bytes()).await.unwrap();
+
+        let text = String::from_utf8(data).unwrap();
+        let (head, body) = text.split_once("\r\n\r\n").unwrap();
+        let (request_line, headers) = head.split_once("\r\n").unwrap_or((head, ""));
+        RecordedRequest {
+            request_line: request_line.to_string(),
+            headers: headers.to_lowercase(),
+            body: body.to_string(),
+        }
+    });
+
+    let client = GcpClient::with_proxy(None)
+        .unwrap()
+        .with_endpoint(&endpoint)
+        .with_token_source(FixedToken);
+    (client, handle)
+}
+
+fn restore_config() -> RestoreConfig {
+    RestoreConfig {
+        backup_id: "1700000000000".to_string(),
+        source_project: "src-project".to_string(),
+        source_instance: "db-1".to_string(),
+        target_project: "dst-project".to_string(),
+        target_instance: "db-2".to_string(),
+        intended_databases: Vec::new(),
+        reason: None,
+    }
+}
+
+fn backup_config() -> CreateBackupConfig {
+    CreateBackupConfig {
+        project: "src-project".to_string(),
+        instance: "db-1".to_string(),
+        name: "nightly".to_string(),
+        description: "nightly".to_string(),
+        location: Some("eu".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_restore_backup_posts_the_request_and_returns_the_operation_id() {
+    let (client, server) =
+        serve_once("200 OK", r#"{"name": "projects/dst-project/operations/op-123"}"#).await;
+    let request = RestoreRequest::from(&restore_config());
+
+    let operation_id = client
+        .restore_backup(&request, "dst-project", "db-2")
+        .await
+        .unwrap();
+
+    assert_eq!(operation_id, "op-123");
+    let received = server.await.unwrap();
+    assert_eq!(
+        received.request_line,
+        "POST /v1/projects/dst-project/instances/db-2/restoreBackup HTTP/1.1"
+    );
+    assert!(received.headers.contains("authorization: bearer test-token"));
+    assert!(received
+        .headers
+        .contains(&format!("user-agent: {}", USER_AGENT.to_lowercase())));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&received.body).unwrap(),
+        serde_json::to_value(&request).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_restore_on_a_busy_instance_fails_as_busy() {
+    let body = api_error(409, "Another operation is in progress.", "operationInProgress");
+    let (client, _server) = serve_once("409 Conflict", &body).await;
+    let request = RestoreRequest::from(&restore_config());
+
+    let error = client
+        .restore_backup(&request, "dst-project", "db-2")
+        .await
+        .unwrap_err();
+
+    assert!(is_instance_busy(&error));
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::OperationFailed));
+    assert!(error.to_string().starts_with("Restore operation failed: "), "{}", error);
+}
+
+#[tokio::test]
+async fn test_restore_backup_without_an_operation_name_fails() {
+    let (client, _server) = serve_once("200 OK", r#"{"kind": "sql#operation"}"#).await;
+    let request = RestoreRequest::from(&restore_config());
+
+    let error = client
+        .restore_backup(&request, "dst-project", "db-2")
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("No operation ID"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_create_backup_posts_description_and_location() {
+    let (client, server) = serve_once("200 OK", r#"{"name": "op-456"}"#).await;
+
+    let operation_id = client.create_backup(&backup_config()).await.unwrap();
+
+    assert_eq!(operation_id, "op-456");
+    let received = server.await.unwrap();
+    assert_eq!(
+        received.request_line,
+        "POST /v1/projects/src-project/instances/db-1/backupRuns HTTP/1.1"
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&received.body).unwrap(),
+        serde_json::json!({"description": "nightly", "location": "eu"})
+    );
+}
+
+#[tokio::test]
+async fn test_create_backup_on_a_disabled_api_says_how_to_enable_it() {
+    let body = r#"{"error": {"code": 403, "status": "PERMISSION_DENIED",
+        "details": [{"reason": "SERVICE_DISABLED"}]}}"#;
+    let (client, _server) = serve_once("403 Forbidden", body).await;
+
+    let error = client.create_backup(&backup_config()).await.unwrap_err();
+
+    assert!(
+        error
+            .to_string()
+            .contains("Enable the Cloud SQL Admin API on project src-project"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_create_backup_reports_malformed_json() {
+    let (client, _server) = serve_once("200 OK", "<html>proxy login</html>").await;
+
+    let error = client.create_backup(&backup_config()).await.unwrap_err();
+
+    let message = error.to_string();
+    assert!(message.contains("Unexpected create backup response"), "{}", message);
+    assert!(message.contains("<html>proxy login</html>"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_get_operation_status_parses_the_operation() {
+    let body = r#"{"name": "op-1", "operationType": "RESTORE_VOLUME", "status": "RUNNING",
+        "targetId": "db-2"}"#;
+    let (client, server) = serve_once("200 OK", body).await;
+
+    let operation = client.get_operation_status("dst-project", "op-1").await.unwrap();
+
+    assert_eq!(operation.status, "RUNNING");
+    assert_eq!(operation.operation_type, "RESTORE_VOLUME");
+    let received = server.await.unwrap();
+    assert_eq!(received.request_line, "GET /v1/projects/dst-project/operations/op-1 HTTP/1.1");
+}
+
+#[tokio::test]
+async fn test_user_agent_names_the_tool_and_version() {
+    let (client, server) = serve_once("200 OK", r#"{"name": "op-1", "status": "DONE"}"#).await;
+
+    client.get_operation_status("dst-project", "op-1").await.unwrap();
+
+    let received = server.await.unwrap();
+    let user_agent = received
+        .headers
+        .lines()
+        .find_map(|line| line.strip_prefix("user-agent: "))
+        .unwrap();
+    assert_eq!(user_agent, format!("gcp-snap-crab/{}", env!("CARGO_PKG_VERSION")));
+}
+
+#[tokio::test]
+async fn test_get_operation_status_for_a_missing_operation_is_not_found() {
+    let body = r#"{"error": {"code": 404, "message": "The operation does not exist."}}"#;
+    let (client, _server) = serve_once("404 Not Found", body).await;
+
+    let error = client.get_operation_status("dst-project", "op-1").await.unwrap_err();
+
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::NotFound));
+}
+
+#[test]
+fn test_token_error_asks_to_reauthenticate_when_gcloud_says_so() {
+    let error = gcloud_token_error(
+        "ERROR: (gcloud.auth.print-access-token) Reauthentication failed. cannot prompt \
+         during non-interactive execution.",
+    );
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::Prerequisites));
+    assert!(error.to_string().starts_with("Reauthentication required"));
+
+    let error = gcloud_token_error("ERROR: something else\n");
+    assert_eq!(error.to_string(), "Failed to get access token: ERROR: something else");
+    assert_eq!(gcloud_token_error("").to_string(), "Failed to get access token");
+}
+
+/// Writes an executable stand-in for gcloud that appends its arguments to `<script>.args`,
+/// one call per line, and then runs `body`.
+fn fake_gcloud(name: &str, body: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gcp-snap-crab-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let script = dir.join(name);
+    let _ = fs::remove_file(script.with_extension("args"));
+    fs::write(&script, format!("#!/bin/sh\necho \"$@\" >> \"$0.args\"\n{}\n", body)).unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    script
+}
+
+fn recorded_args(script: &Path) -> Vec<String> {
+    fs::read_to_string(script.with_extension("args"))
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_access_token_is_fetched_as_the_chosen_account() {
+    let script = fake_gcloud(
+        "token",
+        "case \"$1\" in config) exit 1;; esac\necho fallback-token",
+    );
+    let gcloud = Gcloud::new(Some("ops@example.com".to_string()))
+        .with_program(script.to_str().unwrap());
+
+    let (token, _) = GcloudTokenSource.fetch_token(&gcloud).await.unwrap();
+
+    assert_eq!(token, "fallback-token");
+    assert_eq!(
+        recorded_args(&script),
+        [
+            "config config-helper --format=json --account=ops@example.com",
+            "auth print-access-token --account=ops@example.com",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_gcloud_that_does_not_finish_in_time_is_given_up_on() {
+    let script = fake_gcloud("hang", "exec sleep 5");
+    let gcloud = Gcloud::default().with_program(script.to_str().unwrap());
+
+    let started = std::time::Instant::now();
+    let error = gcloud
+        .run_with_timeout(&["auth", "print-access-token"], Duration::from_millis(100))
+        .await
+        .unwrap_err();
+
+    assert!(started.elapsed() < Duration::from_secs(2), "{:?}", started.elapsed());
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::Timeout));
+    assert!(
+        error
+            .to_string()
+            .starts_with("gcloud auth print-access-token did not finish within 0.1s"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_prerequisites_probe_the_configured_gcloud() {
+    let script = fake_gcloud("prereq", "case \"$1\" in auth) echo ops@example.com;; esac");
+    let client = GcpClient::with_proxy(None)
+        .unwrap()
+        .with_gcloud_program(script.to_str().unwrap());
+
+    let account = client.check_prerequisites().await.unwrap();
+
+    assert_eq!(account, "ops@example.com");
+    assert_eq!(recorded_args(&script)[0], "--version");
+}
+
+#[tokio::test]
+async fn test_missing_gcloud_is_reported_as_not_installed() {
+    let missing = std::env::temp_dir().join("gcp-snap-crab-no-such-gcloud");
+    let client = GcpClient::with_proxy(None)
+        .unwrap()
+        .with_gcloud_program(missing.to_str().unwrap());
+
+    let error = client.check_prerequisites().await.unwrap_err();
+
+    assert_eq!(error.to_string(), "gcloud CLI is not installed");
+    assert_eq!(FailureKind::of(&error), Some(FailureKind::Prerequisites));
+}
+
+#[tokio::test]
+async fn test_listings_run_as_the_chosen_account() {
+    let script = fake_gcloud("list", "echo 'db-1\tMYSQL_8_0\tregion-1'");
+    let client = GcpClient::with_proxy(None)
+        .unwrap()
+        .with_account(Some("ops@example.com".to_string()))
+        .with_gcloud_program(script.to_str().unwrap());
+
+    let instances = client.list_sql_instances("my-project").await.unwrap();
+
+    assert_eq!(instances[0].name, "db-1");
+    let args = recorded_args(&script);
+    assert!(args[0].starts_with("sql instances list --project=my-project"), "{:?}", args);
+    assert!(args[0].ends_with(" --account=ops@example.com"), "{:?}", args);
+}
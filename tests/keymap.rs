@@ -0,0 +1,62 @@
+use crossterm::event::KeyCode;
+use gcp_snap_crab::keymap::{Action, KeyMap};
+
+#[test]
+fn test_default_keymap_matches_built_in_bindings() {
+    let keymap = KeyMap::default();
+    assert_eq!(keymap.action_for(KeyCode::Char('q')), Some(Action::Quit));
+    assert_eq!(keymap.action_for(KeyCode::Esc), Some(Action::Back));
+    assert_eq!(keymap.action_for(KeyCode::Enter), Some(Action::Select));
+    assert_eq!(keymap.action_for(KeyCode::Char('x')), None);
+}
+
+#[test]
+fn test_keymap_overrides_only_listed_actions() {
+    let keymap = KeyMap::from_toml(
+        r#"
+        up = ["Up", "k"]
+        down = ["Down", "j"]
+        help = "?"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(keymap.action_for(KeyCode::Char('k')), Some(Action::Up));
+    assert_eq!(keymap.action_for(KeyCode::Down), Some(Action::Down));
+    assert_eq!(keymap.action_for(KeyCode::Char('?')), Some(Action::Help));
+    assert_eq!(keymap.action_for(KeyCode::Char('h')), None);
+    assert_eq!(keymap.quit, KeyMap::default().quit);
+    assert_eq!(keymap.label(Action::Up), "↑/k");
+}
+
+#[test]
+fn test_keymap_rejects_unknown_keys_and_actions() {
+    assert!(KeyMap::from_toml(r#"quit = "Hyper""#).is_err());
+    assert!(KeyMap::from_toml(r#"explode = "x""#).is_err());
+}
+
+#[test]
+fn test_step_actions_share_keys_and_can_be_rebound() {
+    let keymap = KeyMap::default();
+    assert!(keymap.binds(Action::Sort, KeyCode::Char('s')));
+    assert!(keymap.binds(Action::SameAsSource, KeyCode::Char('s')));
+    assert_eq!(keymap.action_for(KeyCode::Char('s')), None);
+    assert_eq!(keymap.label(Action::Mark), "Space");
+
+    let keymap = KeyMap::from_toml(r#"date_filter = ["/", "f"]"#).unwrap();
+    assert!(keymap.binds(Action::DateFilter, KeyCode::Char('/')));
+    assert_eq!(keymap.label(Action::DateFilter), "//f");
+    assert_eq!(keymap.compare, KeyMap::default().compare);
+}
+
+#[test]
+fn test_overlapping_actions_follow_the_bindings() {
+    let keymap = KeyMap::default();
+    assert!(keymap.overlaps(Action::ProjectBackups, Action::PausePolling));
+    assert!(keymap.overlaps(Action::Compare, Action::GcloudCommand));
+
+    let keymap = KeyMap::from_toml(r#"gcloud_command = ["g", "c"]"#).unwrap();
+    assert!(keymap.overlaps(Action::Compare, Action::GcloudCommand));
+    let keymap = KeyMap::from_toml(r#"project_backups = "b""#).unwrap();
+    assert!(!keymap.overlaps(Action::ProjectBackups, Action::PausePolling));
+}
@@ -0,0 +1,90 @@
+use chrono::{TimeZone, Utc};
+use gcp_snap_crab::operation_store::{OperationStore, TrackedOperation, CSV_HEADER};
+use gcp_snap_crab::types::OperationMode;
+
+#[test]
+fn test_operation_store_round_trips_and_moves_finished_to_history() {
+    let dir = std::env::temp_dir().join(format!("gcp-snap-crab-store-{}", std::process::id()));
+    let path = dir.join("operations.json");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut store = OperationStore::load(&path).unwrap();
+    assert!(store.in_flight.is_empty());
+    store
+        .track(OperationMode::Restore, "prod-project", "op-restore")
+        .unwrap();
+    store
+        .track(OperationMode::CreateBackup, "prod-project", "op-backup")
+        .unwrap();
+    store.update_status("op-backup", "DONE", None, None).unwrap();
+    store.record_marker("op-restore", "op-marker").unwrap();
+
+    let reloaded = OperationStore::load(&path).unwrap();
+    assert_eq!(reloaded.in_flight.len(), 1);
+    assert_eq!(reloaded.in_flight[0].operation_id, "op-restore");
+    assert_eq!(reloaded.in_flight[0].mode, OperationMode::Restore);
+    assert_eq!(reloaded.history.len(), 1);
+    assert_eq!(reloaded.history[0].status, "DONE");
+    assert_eq!(reloaded.marker_of("op-restore"), Some("op-marker"));
+    assert_eq!(reloaded.marker_of("op-backup"), None);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_history_keeps_every_finished_operation() {
+    let mut store = OperationStore::default();
+    for n in 0..120 {
+        let operation_id = format!("op-{}", n);
+        store
+            .track(OperationMode::Restore, "prod-project", &operation_id)
+            .unwrap();
+        store.update_status(&operation_id, "DONE", None, None).unwrap();
+    }
+
+    assert_eq!(store.history.len(), 120);
+    assert_eq!(store.history[0].operation_id, "op-119");
+    assert_eq!(store.history[119].operation_id, "op-0");
+}
+
+#[test]
+fn test_finish_time_comes_from_the_operation_when_known() {
+    let end_time = Utc.with_ymd_and_hms(2024, 6, 1, 12, 7, 30).unwrap();
+    let mut store = OperationStore::default();
+    store
+        .track(OperationMode::Restore, "prod-project", "op-reported")
+        .unwrap();
+    store
+        .track(OperationMode::Restore, "prod-project", "op-unreported")
+        .unwrap();
+
+    store
+        .update_status("op-reported", "DONE", None, Some(end_time))
+        .unwrap();
+    store.update_status("op-unreported", "DONE", None, None).unwrap();
+
+    assert_eq!(store.history[1].operation_id, "op-reported");
+    assert_eq!(store.history[1].finished_at, Some(end_time));
+    assert!(store.history[0].finished_at.unwrap() > end_time);
+}
+
+#[test]
+fn test_csv_row_quotes_fields_with_commas_and_quotes() {
+    let mut operation = TrackedOperation::new(OperationMode::Restore, "staging", "op-1");
+    operation.started_at = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+    operation.finished_at = Some(Utc.with_ymd_and_hms(2024, 6, 1, 12, 7, 30).unwrap());
+    operation.instance = Some("db-staging".to_string());
+    operation.source = Some("prod/db-prod".to_string());
+    operation.backup_id = Some("1700000000000".to_string());
+    operation.status = "FAILED".to_string();
+    operation.error = Some("quota exceeded, \"retry\" later".to_string());
+    operation.reason = Some("INC-42 data fix".to_string());
+    operation.databases = vec!["orders".to_string(), "billing".to_string()];
+
+    assert_eq!(CSV_HEADER.split(',').count(), 13);
+    assert_eq!(
+        operation.to_csv_row(),
+        "2024-06-01T12:00:00+00:00,Restore,prod/db-prod,staging,db-staging,,1700000000000,\
+         op-1,FAILED,450,\"quota exceeded, \"\"retry\"\" later\",INC-42 data fix,orders;billing"
+    );
+}
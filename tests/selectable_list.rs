@@ -0,0 +1,106 @@
+use gcp_snap_crab::selectable_list::SelectableList;
+
+#[test]
+fn test_moving_stops_at_the_ends_unless_wrapping() {
+    let mut list = SelectableList::new(vec![1, 2, 3]);
+    list.move_up(false);
+    assert_eq!(list.selected(), 0);
+    list.move_up(true);
+    assert_eq!(list.selected(), 2);
+    list.move_down(false);
+    assert_eq!(list.selected(), 2);
+    list.move_down(true);
+    assert_eq!(list.selected(), 0);
+    list.move_down(false);
+    assert_eq!(list.selected(), 1);
+
+    let mut empty = SelectableList::<u32>::default();
+    empty.move_down(true);
+    empty.move_up(true);
+    assert_eq!(empty.selected(), 0);
+}
+
+#[test]
+fn test_selection_counts_the_filtered_items() {
+    let mut list = SelectableList::new((1..=10).collect::<Vec<u32>>());
+    list.set_filter(|n: &u32| n.is_multiple_of(2));
+    list.select(2);
+    assert_eq!(list.filtered_items(), [&2, &4, &6, &8, &10]);
+    assert_eq!(list.selected_item(), Some(&6));
+    assert_eq!(list.items().len(), 10);
+
+    list.clear_filter();
+    assert_eq!(list.len(), 10);
+}
+
+#[test]
+fn test_selection_past_the_end_is_clamped() {
+    let mut list = SelectableList::new(vec![1, 2, 3]);
+    list.select(7);
+    assert_eq!(list.selected(), 2);
+    list.move_up(false);
+    assert_eq!(list.selected(), 1);
+    assert!(SelectableList::<u32>::default().selected_item().is_none());
+}
+
+#[test]
+fn test_new_items_keep_the_filter_and_start_at_the_top() {
+    let mut list = SelectableList::new((0..10).collect::<Vec<u32>>());
+    list.set_filter(|n: &u32| n.is_multiple_of(5));
+    list.select(1);
+    list.set_items((0..20).collect());
+    assert_eq!(list.filtered_items(), [&0, &5, &10, &15]);
+    assert_eq!(list.selected(), 0);
+
+    list.push(25);
+    assert_eq!(list.selected_item(), Some(&25));
+    assert!(list.select_where(|n| *n == 10));
+    assert!(!list.select_where(|n| *n == 11));
+    assert_eq!(list.selected_item(), Some(&10));
+}
+
+#[test]
+fn test_view_scrolls_only_when_the_selection_leaves_it() {
+    let mut list = SelectableList::new((0..100).collect::<Vec<u32>>());
+    assert_eq!(list.visible_rows(10), 0..10);
+    list.select(9);
+    assert_eq!(list.visible_rows(10), 0..10);
+    list.select(12);
+    assert_eq!(list.visible_rows(10), 3..13);
+
+    // Moving back up within the view keeps it where it is.
+    list.select(5);
+    assert_eq!(list.visible_rows(10), 3..13);
+    list.select(1);
+    assert_eq!(list.visible_rows(10), 1..11);
+
+    let mut short = SelectableList::new((0..5).collect::<Vec<u32>>());
+    short.select(4);
+    assert_eq!(short.visible_rows(10), 0..5);
+}
+
+#[test]
+fn test_view_is_pulled_back_when_the_list_shrinks() {
+    let mut list = SelectableList::new((0..100).collect::<Vec<u32>>());
+    list.select(95);
+    assert_eq!(list.visible_rows(10), 86..96);
+    list.set_filter(|n: &u32| *n < 20);
+    list.select(15);
+    assert_eq!(list.visible_rows(10), 10..20);
+}
+
+#[test]
+fn test_filter_runs_once_per_item() {
+    let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+    let mut list = SelectableList::new((0..100).collect::<Vec<u32>>());
+    let counted = calls.clone();
+    list.set_filter(move |n: &u32| {
+        counted.set(counted.get() + 1);
+        n.is_multiple_of(3)
+    });
+    list.select(12);
+    assert_eq!(list.selected_item(), Some(&36));
+    assert_eq!(list.visible_rows(10), 3..13);
+    assert_eq!(list.filtered_items().len(), 34);
+    assert_eq!(calls.get(), 100);
+}
@@ -0,0 +1,30 @@
+use gcp_snap_crab::session::SavedSession;
+use gcp_snap_crab::state::restore_flow::RestoreFlow;
+
+#[test]
+fn test_saved_session_round_trips_the_restore_selections() {
+    let mut flow = RestoreFlow::new();
+    flow.source_project = Some("prod".to_string());
+    flow.source_instance = Some("db-prod".to_string());
+    flow.selected_backup = Some("1700000000000".to_string());
+    flow.target_project = Some("staging".to_string());
+    flow.operation_id = Some("op-1".to_string());
+
+    let session = SavedSession::from_flow(&flow);
+    assert_eq!(session.target_instance, None);
+
+    let path = std::env::temp_dir().join(format!("gcp-snap-crab-session-{}", std::process::id()));
+    session.save(&path).unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("op-1"), "{}", contents);
+    assert_eq!(SavedSession::load(&path).unwrap(), session);
+
+    std::fs::write(&path, "{\"source_project\": \"prod\"}").unwrap();
+    let partial = SavedSession::load(&path).unwrap();
+    assert_eq!(partial.source_project.as_deref(), Some("prod"));
+    assert_eq!(partial.backup_id, None);
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(SavedSession::load(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
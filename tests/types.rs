@@ -0,0 +1,393 @@
+use gcp_snap_crab::types::{
+    backups_by_age, describe_with_labels, has_backup_on, is_newer_version, parse_database_version,
+    parse_label_input, parse_labels, Backup, DateRange, CreateBackupConfig, DisplayZone,
+    GcpApiResponse, InstanceStatus, MaintenanceWindow, Operation, OperationPhase, ProtectPattern,
+    RestoreConfig, RestoreRequest, SqlInstance,
+};
+use chrono::{FixedOffset, NaiveDate, TimeDelta, TimeZone, Utc, Weekday};
+use std::collections::BTreeMap;
+
+#[test]
+fn test_labels_round_trip_through_description() {
+    let labels = parse_label_input("env=staging; ticket=JIRA-123").unwrap();
+    let description = describe_with_labels("pre-migration", &labels);
+    assert_eq!(description, "pre-migration | env=staging;ticket=JIRA-123");
+
+    let backup = Backup {
+        id: "1".to_string(),
+        start_time: None,
+        backup_type: "ON_DEMAND".to_string(),
+        status: "SUCCESSFUL".to_string(),
+        description,
+    };
+    assert_eq!(backup.labels(), labels);
+}
+
+#[test]
+fn test_parse_labels_ignores_plain_descriptions() {
+    assert!(parse_labels("nightly backup").is_empty());
+    assert!(parse_labels("").is_empty());
+    assert!(parse_labels("a|b=c").is_empty(), "only the separator starts labels");
+    assert!(parse_labels("key=value").is_empty());
+    assert_eq!(describe_with_labels("nightly", &BTreeMap::new()), "nightly");
+}
+
+#[test]
+fn test_parse_label_input_rejects_malformed_pairs() {
+    assert!(parse_label_input("env=staging;oops").is_err());
+    assert!(parse_label_input("=value").is_err());
+    assert!(parse_label_input("").unwrap().is_empty());
+}
+
+#[test]
+fn test_date_range_parses_single_day_and_range() {
+    let day = DateRange::parse("2024-06-01").unwrap();
+    assert_eq!(day.start, day.end);
+    assert_eq!(day.to_string(), "2024-06-01");
+
+    let range = DateRange::parse(" 2024-06-01 .. 2024-06-30 ").unwrap();
+    assert_eq!(range.to_string(), "2024-06-01..2024-06-30");
+
+    assert!(DateRange::parse("2024-06-30..2024-06-01").is_err());
+    assert!(DateRange::parse("June 1st").is_err());
+}
+
+#[test]
+fn test_has_backup_on_uses_the_given_time_zone() {
+    let backup = |hour: u32, status: &str| Backup {
+        id: hour.to_string(),
+        start_time: Some(Utc.with_ymd_and_hms(2024, 6, 1, hour, 0, 0).unwrap()),
+        backup_type: "ON_DEMAND".to_string(),
+        status: status.to_string(),
+        description: String::new(),
+    };
+    let june_1 = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+    let june_2 = NaiveDate::from_ymd_opt(2024, 6, 2).unwrap();
+    let backups = vec![backup(23, "SUCCESSFUL")];
+
+    assert!(has_backup_on(&backups, june_1, &Utc));
+    assert!(!has_backup_on(&backups, june_2, &Utc));
+    let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    assert!(has_backup_on(&backups, june_2, &tokyo));
+
+    assert!(!has_backup_on(&[backup(10, "FAILED")], june_1, &Utc));
+}
+
+#[test]
+fn test_backups_are_grouped_by_age() {
+    let now = Utc.with_ymd_and_hms(2024, 6, 30, 12, 0, 0).unwrap();
+    let backup = |days_old: Option<i64>| Backup {
+        id: format!("{:?}", days_old),
+        start_time: days_old.map(|days| now - TimeDelta::days(days)),
+        backup_type: "AUTOMATED".to_string(),
+        status: "SUCCESSFUL".to_string(),
+        description: String::new(),
+    };
+    let backups: Vec<Backup> = [Some(0), Some(6), Some(7), Some(29), Some(30), Some(400), None]
+        .into_iter()
+        .map(backup)
+        .collect();
+
+    let rows = backups_by_age(&backups, now);
+
+    let counts: Vec<(&str, usize)> = rows.iter().map(|row| (row.label, row.count)).collect();
+    assert_eq!(
+        counts,
+        vec![
+            ("Last 7 days", 2),
+            ("8-30 days", 2),
+            ("31-90 days", 1),
+            ("Older", 1),
+            ("Time unknown", 1),
+        ]
+    );
+    assert_eq!(rows[1].oldest, Some(now - TimeDelta::days(29)));
+    assert_eq!(backups_by_age(&backups[..1], now).len(), 4);
+}
+
+#[test]
+fn test_operation_phase_distinguishes_queued_from_executing() {
+    let queued = OperationPhase::from_status("PENDING");
+    let executing = OperationPhase::from_status("RUNNING");
+    assert_eq!(queued, OperationPhase::Queued);
+    assert_eq!(executing, OperationPhase::Executing);
+    assert_ne!(queued.icon(), executing.icon());
+
+    assert!(queued.detail(Some("RESTORE_VOLUME")).contains("Waiting to start the backup restore"));
+    assert!(executing.detail(Some("BACKUP_VOLUME")).contains("executing the backup"));
+    assert_eq!(
+        OperationPhase::from_status("SQL_OPERATION_STATUS_UNSPECIFIED"),
+        OperationPhase::Checking
+    );
+}
+
+#[test]
+fn test_operation_from_api_response_fills_defaults_and_parses_times() {
+    let response: GcpApiResponse = serde_json::from_str(
+        r#"{
+            "status": "DONE",
+            "operationType": "RESTORE_VOLUME",
+            "startTime": "2024-06-01T12:00:00.123Z",
+            "endTime": "not a timestamp",
+            "error": {"message": "backup not found"}
+        }"#,
+    )
+    .unwrap();
+
+    let operation: Operation = ("op-1".to_string(), response).into();
+
+    assert_eq!(operation.id, "op-1");
+    assert_eq!(operation.operation_type, "RESTORE_VOLUME");
+    assert_eq!(operation.status, "DONE");
+    assert_eq!(operation.target_id, "Unknown");
+    assert_eq!(
+        operation.start_time.map(|t| t.timestamp_millis()),
+        Some(Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap().timestamp_millis() + 123)
+    );
+    assert!(operation.end_time.is_none());
+    assert_eq!(operation.error_message.as_deref(), Some("backup not found"));
+}
+
+#[test]
+fn test_protect_pattern_matching() {
+    let default = ProtectPattern::default();
+    assert!(default.is_match("acme-PROD-1"));
+    assert!(!default.is_match("acme-staging"));
+
+    let pattern = ProtectPattern::parse("^prd-|-live$|db.*main").unwrap();
+    assert!(pattern.is_match("prd-orders"));
+    assert!(!pattern.is_match("x-prd-orders"));
+    assert!(pattern.is_match("orders-live"));
+    assert!(!pattern.is_match("orders-live-2"));
+    assert!(pattern.is_match("db-orders-main"));
+
+    let pattern = ProtectPattern::parse(r"^(prd|prod)-[a-z]+-\d{2}$").unwrap();
+    assert!(pattern.is_match("PROD-orders-01"));
+    assert!(pattern.is_match("prd-billing-42"));
+    assert!(!pattern.is_match("prod-orders-1"));
+    assert!(!pattern.is_match("staging-orders-01"));
+
+    assert!(!ProtectPattern::parse("").unwrap().is_match("prod"));
+    let error = ProtectPattern::parse("*prod").unwrap_err();
+    assert!(error.starts_with("Invalid protect pattern '*prod'"), "{}", error);
+    assert!(ProtectPattern::parse("pro[d").is_err());
+}
+
+#[test]
+fn test_instance_summary_skips_unknown_fields() {
+    let instance = SqlInstance {
+        name: "db-1".to_string(),
+        database_version: "POSTGRES_15".to_string(),
+        region: String::new(),
+        tier: "db-custom-2-7680".to_string(),
+        instance_type: "READ_REPLICA_INSTANCE".to_string(),
+    };
+    assert_eq!(instance.summary(), "db-1 (POSTGRES_15, db-custom-2-7680) (replica)");
+    assert_eq!(instance.to_string(), "db-1");
+    assert_eq!(SqlInstance::manual("typed").summary(), "typed (details unknown)");
+
+    let bare = SqlInstance {
+        database_version: String::new(),
+        tier: String::new(),
+        instance_type: String::new(),
+        ..instance
+    };
+    assert_eq!(bare.summary(), "db-1");
+}
+
+#[test]
+fn test_instance_status_parses_state_and_activation_policy() {
+    let running = InstanceStatus::parse("RUNNABLE\tALWAYS\n");
+    assert_eq!(running.state, "RUNNABLE");
+    assert_eq!(running.activation_policy, "ALWAYS");
+    assert!(running.likely_in_use());
+
+    assert!(!InstanceStatus::parse("RUNNABLE\tNEVER").likely_in_use());
+    assert!(!InstanceStatus::parse("MAINTENANCE\tALWAYS").likely_in_use());
+    assert_eq!(InstanceStatus::parse("PENDING_CREATE").activation_policy, "");
+}
+
+#[test]
+fn test_maintenance_window_is_parsed_from_the_status() {
+    let status = InstanceStatus::parse("RUNNABLE\tALWAYS\t7\t3");
+    let window = status.maintenance_window.unwrap();
+    assert_eq!(window, MaintenanceWindow { day: Weekday::Sun, hour: 3 });
+    assert_eq!(window.to_string(), "Sun 03:00 UTC");
+
+    assert_eq!(MaintenanceWindow::parse("MONDAY", "22").unwrap().day, Weekday::Mon);
+    assert_eq!(MaintenanceWindow::parse("0", "3"), None);
+    assert_eq!(MaintenanceWindow::parse("", ""), None);
+    assert_eq!(MaintenanceWindow::parse("1", "24"), None);
+    assert_eq!(InstanceStatus::parse("RUNNABLE\tALWAYS").maintenance_window, None);
+}
+
+#[test]
+fn test_maintenance_window_nearness() {
+    // 2024-06-09 is a Sunday.
+    let window = MaintenanceWindow { day: Weekday::Sun, hour: 3 };
+    let lead = TimeDelta::hours(1);
+    let at = |day, hour, minute| Utc.with_ymd_and_hms(2024, 6, day, hour, minute, 0).unwrap();
+
+    assert!(window.is_near(at(9, 3, 30), lead));
+    assert!(window.is_near(at(9, 2, 15), lead));
+    assert!(!window.is_near(at(9, 1, 59), lead));
+    assert!(!window.is_near(at(9, 4, 0), lead));
+    assert!(!window.is_near(at(10, 3, 30), lead));
+
+    let late = MaintenanceWindow { day: Weekday::Mon, hour: 0 };
+    assert!(late.is_near(at(9, 23, 30), lead));
+}
+
+#[test]
+fn test_backup_formatting_without_start_time() {
+    let mut backup = Backup {
+        id: "1700000000000".to_string(),
+        start_time: None,
+        backup_type: String::new(),
+        status: String::new(),
+        description: "nightly | env=prod".to_string(),
+    };
+    assert_eq!(backup.display_line(DisplayZone::Utc), "Unknown | 1700000000000 [env=prod]");
+    assert_eq!(backup.to_string(), "1700000000000 (taken time unknown)");
+
+    backup.start_time = Some(Utc.with_ymd_and_hms(2024, 6, 1, 3, 4, 0).unwrap());
+    backup.backup_type = "AUTOMATED".to_string();
+    backup.status = "SUCCESSFUL".to_string();
+    backup.description = String::new();
+    assert_eq!(backup.display_line(DisplayZone::Utc), "2024-06-01 03:04 | 1700000000000");
+    assert_eq!(
+        backup.to_string(),
+        "1700000000000 (taken 2024-06-01 03:04 UTC, AUTOMATED, SUCCESSFUL)"
+    );
+}
+
+#[test]
+fn test_watch_line_shows_the_phase_and_times() {
+    let mut operation = Operation {
+        id: "op-1".to_string(),
+        operation_type: "RESTORE_VOLUME".to_string(),
+        status: "RUNNING".to_string(),
+        target_id: "db-1".to_string(),
+        start_time: Some(Utc.with_ymd_and_hms(2024, 6, 1, 3, 4, 5).unwrap()),
+        end_time: None,
+        error_message: None,
+    };
+    assert_eq!(
+        operation.watch_line(),
+        "🔄 RESTORE_VOLUME op-1: RUNNING [started 2024-06-01 03:04:05 UTC]"
+    );
+
+    operation.status = "DONE".to_string();
+    operation.end_time = Some(Utc.with_ymd_and_hms(2024, 6, 1, 3, 14, 5).unwrap());
+    assert_eq!(
+        operation.watch_line(),
+        "✅ RESTORE_VOLUME op-1: DONE [started 2024-06-01 03:04:05 UTC, ended 2024-06-01 \
+         03:14:05 UTC]"
+    );
+}
+
+#[test]
+fn test_operation_and_config_display() {
+    let operation = Operation {
+        id: "op-1".to_string(),
+        operation_type: "BACKUP_VOLUME".to_string(),
+        status: "DONE".to_string(),
+        target_id: "db-1".to_string(),
+        start_time: None,
+        end_time: None,
+        error_message: Some("quota exceeded".to_string()),
+    };
+    assert_eq!(operation.to_string(), "BACKUP_VOLUME op-1: DONE - quota exceeded");
+    assert_eq!(operation.phase(), OperationPhase::Failed, "an error wins over DONE");
+
+    let restore = RestoreConfig {
+        backup_id: "42".to_string(),
+        source_project: "src".to_string(),
+        source_instance: "db-1".to_string(),
+        target_project: "dst".to_string(),
+        target_instance: "db-2".to_string(),
+        intended_databases: Vec::new(),
+        reason: None,
+    };
+    assert_eq!(restore.to_string(), "backup 42 of src/db-1 onto dst/db-2");
+
+    let mut backup = CreateBackupConfig {
+        project: "src".to_string(),
+        instance: "db-1".to_string(),
+        name: "nightly".to_string(),
+        description: "nightly".to_string(),
+        location: None,
+    };
+    assert_eq!(backup.to_string(), "'nightly' of src/db-1");
+    backup.location = Some("eu".to_string());
+    assert_eq!(backup.to_string(), "'nightly' of src/db-1 in eu");
+}
+
+#[test]
+fn test_restore_request_never_carries_a_database_filter() {
+    let config = RestoreConfig {
+        backup_id: "42".to_string(),
+        source_project: "src".to_string(),
+        source_instance: "db-1".to_string(),
+        target_project: "dst".to_string(),
+        target_instance: "db-2".to_string(),
+        intended_databases: vec!["orders".to_string()],
+        reason: None,
+    };
+
+    let body = serde_json::to_value(RestoreRequest::from(&config)).unwrap();
+
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "restoreBackupContext": {"backupRunId": "42", "project": "src", "instanceId": "db-1"}
+        })
+    );
+}
+
+#[test]
+fn test_database_versions_are_parsed_and_compared() {
+    assert_eq!(parse_database_version("POSTGRES_15"), Some(("POSTGRES", vec![15])));
+    assert_eq!(parse_database_version("MYSQL_8_0_31"), Some(("MYSQL", vec![8, 0, 31])));
+    assert_eq!(
+        parse_database_version("SQLSERVER_2019_STANDARD"),
+        Some(("SQLSERVER", vec![2019]))
+    );
+    assert_eq!(parse_database_version("Manual"), None);
+
+    assert!(is_newer_version("POSTGRES_15", "POSTGRES_13"));
+    assert!(!is_newer_version("POSTGRES_13", "POSTGRES_15"));
+    assert!(!is_newer_version("POSTGRES_15", "POSTGRES_15"));
+    assert!(is_newer_version("MYSQL_8_0_31", "MYSQL_8_0_26"));
+    assert!(!is_newer_version("MYSQL_8_0", "MYSQL_8_0_31"));
+    assert!(is_newer_version("MYSQL_8_0", "MYSQL_5_7"));
+    assert!(!is_newer_version("POSTGRES_15", "MYSQL_8_0"));
+    assert!(!is_newer_version("POSTGRES_15", "Manual"));
+}
+
+#[test]
+fn test_display_zone_converts_only_what_is_shown() {
+    // A fixed zone eight hours ahead of UTC (POSIX offsets count westwards), so the local
+    // day differs from the UTC one. Nothing else in this test binary uses the local zone.
+    std::env::set_var("TZ", "CST-8");
+    let time = Utc.with_ymd_and_hms(2024, 6, 1, 23, 30, 0).unwrap();
+    assert_eq!(DisplayZone::Utc.stamp(time), "2024-06-01 23:30 UTC");
+    assert_eq!(DisplayZone::Utc.label(), "UTC");
+
+    assert_eq!(DisplayZone::Local.stamp(time), "2024-06-02 07:30 +08:00");
+    assert_eq!(DisplayZone::Local.format(time, "%Y-%m-%d"), "2024-06-02");
+
+    let june_2 = DateRange::parse("2024-06-02").unwrap();
+    assert!(june_2.contains(time, DisplayZone::Local), "filters on the day shown");
+    assert!(!june_2.contains(time, DisplayZone::Utc));
+
+    let backup = Backup {
+        id: "1".to_string(),
+        start_time: Some(time),
+        backup_type: "AUTOMATED".to_string(),
+        status: "SUCCESSFUL".to_string(),
+        description: String::new(),
+    };
+    assert_eq!(backup.taken_at(DisplayZone::Local), "2024-06-02 07:30 +08:00");
+    assert!(backup.to_string().contains("2024-06-01 23:30 UTC"), "Display stays in UTC");
+}
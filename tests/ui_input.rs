@@ -1,6 +1,9 @@
 use gcp_snap_crab::app::App;
 use gcp_snap_crab::gcp::MockGcpClientTrait;
-use gcp_snap_crab::types::InputMode;
+use gcp_snap_crab::keymap::KeyMap;
+use gcp_snap_crab::types::{
+    AppState, Backup, InputMode, OperationMode, RestoreConfig, SqlInstance,
+};
 use gcp_snap_crab::ui::{handle_edit_input, handle_normal_input};
 use crossterm::event::{KeyCode, KeyModifiers};
 
@@ -9,6 +12,50 @@ fn create_test_app() -> App {
     App::new(Box::new(mock_gcp_client), false)
 }
 
+/// An app whose restore was confirmed and started in the background like in the TUI,
+/// which leaves it back on the target step once the operation id arrives.
+async fn app_with_started_restore() -> App {
+    let mut mock_gcp_client = MockGcpClientTrait::new();
+    mock_gcp_client.expect_list_backups().returning(|_, _| {
+        Ok(vec![Backup {
+            id: "1700000000000".to_string(),
+            start_time: None,
+            backup_type: "AUTOMATED".to_string(),
+            status: "SUCCESSFUL".to_string(),
+            description: String::new(),
+        }])
+    });
+    mock_gcp_client
+        .expect_restore_backup()
+        .times(1)
+        .returning(|_, _, _| Ok("op-restore".to_string()));
+    let mut app = App::new(Box::new(mock_gcp_client), false);
+    app.operation_mode = Some(OperationMode::Restore);
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.config = Some(RestoreConfig {
+        backup_id: "1700000000000".to_string(),
+        source_project: "source-project".to_string(),
+        source_instance: "source-instance".to_string(),
+        target_project: "target-project".to_string(),
+        target_instance: "target-instance".to_string(),
+        intended_databases: Vec::new(),
+        reason: None,
+    });
+    app.enable_background_requests();
+
+    app.perform_restore().await.unwrap();
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while app.apply_background_results().await == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+    })
+    .await
+    .expect("the restore did not start");
+    assert_eq!(app.restore_flow.operation_id.as_deref(), Some("op-restore"));
+    assert_eq!(app.state, AppState::SelectingTargetInstance);
+    app
+}
+
 #[tokio::test]
 async fn test_handle_normal_input_toggle_help() {
     let mut app = create_test_app();
@@ -25,6 +72,26 @@ async fn test_handle_normal_input_toggle_help() {
     assert!(!app.show_help);
 }
 
+#[tokio::test]
+async fn test_arrow_keys_scroll_help_and_closing_resets_it() {
+    let mut app = create_test_app();
+    app.toggle_help();
+
+    for key in [KeyCode::Down, KeyCode::Down, KeyCode::Up] {
+        handle_normal_input(&mut app, key, KeyModifiers::NONE)
+            .await
+            .unwrap();
+    }
+    assert_eq!(app.help_scroll, 1);
+    assert_eq!(app.operations.selected(), 0);
+
+    handle_normal_input(&mut app, KeyCode::Esc, KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.show_help);
+    assert_eq!(app.help_scroll, 0);
+}
+
 #[tokio::test]
 async fn test_handle_normal_input_escape_from_manual_input() {
     let mut app = create_test_app();
@@ -65,4 +132,153 @@ async fn test_handle_edit_input_escape() {
     assert!(!app.manual_input_active);
     assert_eq!(app.input_mode, InputMode::Normal);
     assert!(app.manual_input_buffer.is_empty());
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_handle_normal_input_quit_requests_shutdown() {
+    let mut app = create_test_app();
+    assert!(!app.should_quit);
+
+    handle_normal_input(&mut app, KeyCode::Char('q'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.should_quit);
+}
+
+#[tokio::test]
+async fn test_handle_normal_input_uses_remapped_keys() {
+    let mut app = create_test_app();
+    app.keymap = KeyMap::from_toml(r#"quit = "x""#).unwrap();
+
+    handle_normal_input(&mut app, KeyCode::Char('q'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.should_quit);
+
+    handle_normal_input(&mut app, KeyCode::Char('x'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.should_quit);
+}
+
+#[tokio::test]
+async fn test_handle_normal_input_uses_remapped_step_keys() {
+    let mut app = create_test_app();
+    app.state = AppState::SelectingBackup;
+    app.keymap = KeyMap::from_toml(r#"date_filter = "/""#).unwrap();
+
+    handle_normal_input(&mut app, KeyCode::Char('f'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.manual_input_active);
+
+    handle_normal_input(&mut app, KeyCode::Char('/'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.manual_input_active);
+    assert_eq!(app.manual_input_type, "backup_date_filter");
+}
+
+#[tokio::test]
+async fn test_p_pauses_polling_only_while_an_operation_is_monitored() {
+    let mut app = create_test_app();
+    app.state = AppState::SelectingTargetInstance;
+    handle_normal_input(&mut app, KeyCode::Char('p'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.polling_paused, "nothing has been started");
+
+    let mut app = app_with_started_restore().await;
+    handle_normal_input(&mut app, KeyCode::Char('p'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.polling_paused);
+    handle_normal_input(&mut app, KeyCode::Char('p'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.polling_paused);
+}
+
+#[tokio::test]
+async fn test_p_pauses_polling_on_the_source_step_once_project_backups_is_rebound() {
+    let mut app = app_with_started_restore().await;
+    app.state = AppState::SelectingSourceInstance;
+    handle_normal_input(&mut app, KeyCode::Char('p'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(!app.polling_paused, "p opens the project backups here");
+
+    app.state = AppState::SelectingSourceInstance;
+    app.keymap = KeyMap::from_toml(r#"project_backups = "b""#).unwrap();
+    handle_normal_input(&mut app, KeyCode::Char('p'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+    assert!(app.polling_paused);
+}
+
+#[tokio::test]
+async fn test_escape_from_confirm_keeps_the_target_selected() {
+    let mut app = create_test_app();
+    app.state = AppState::ConfirmRestore;
+    app.restore_flow.target_instance = Some("db-2".to_string());
+    app.restore_flow.instances.set_items(vec![
+        SqlInstance::manual("db-1"),
+        SqlInstance::manual("db-2"),
+    ]);
+    app.restore_flow.instances.select(1);
+
+    handle_normal_input(&mut app, KeyCode::Esc, KeyModifiers::NONE)
+        .await
+        .unwrap();
+
+    assert_eq!(app.state, AppState::SelectingTargetInstance);
+    assert_eq!(app.restore_flow.instances.selected(), 1);
+    assert!(app.restore_flow.target_instance.is_none());
+}
+
+#[tokio::test]
+async fn test_escape_from_backup_confirm_prefills_the_name() {
+    let mut app = create_test_app();
+    app.operation_mode = Some(OperationMode::CreateBackup);
+    app.create_backup_flow.project = Some("project".to_string());
+    app.create_backup_flow.instance = Some("db-1".to_string());
+    app.state = AppState::EnteringBackupName;
+    app.start_manual_input("backup_name");
+    app.manual_input_buffer = "nightly".to_string();
+    app.finish_manual_input().await.unwrap();
+    assert_eq!(app.state, AppState::ConfirmCreateBackup);
+
+    handle_normal_input(&mut app, KeyCode::Esc, KeyModifiers::NONE)
+        .await
+        .unwrap();
+
+    assert_eq!(app.state, AppState::EnteringBackupName);
+    assert!(app.manual_input_active);
+    assert_eq!(app.manual_input_type, "backup_name");
+    assert_eq!(app.manual_input_buffer, "nightly");
+    assert!(app.create_backup_flow.config.is_none());
+}
+
+#[tokio::test]
+async fn test_i_opens_the_details_of_a_started_restore() {
+    let mut app = app_with_started_restore().await;
+
+    handle_normal_input(&mut app, KeyCode::Char('i'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+
+    assert!(app.show_operation_details);
+    assert_eq!(app.followed_operation(), Some(OperationMode::Restore));
+}
+
+#[tokio::test]
+async fn test_c_shows_the_gcloud_command_of_a_started_restore() {
+    let mut app = app_with_started_restore().await;
+
+    handle_normal_input(&mut app, KeyCode::Char('c'), KeyModifiers::NONE)
+        .await
+        .unwrap();
+
+    let command = app.gcloud_preview.as_deref().unwrap_or_default();
+    assert!(command.contains("gcloud sql backups restore 1700000000000"), "{}", command);
+}
@@ -0,0 +1,44 @@
+use gcp_snap_crab::app::App;
+use gcp_snap_crab::gcp::MockGcpClientTrait;
+use gcp_snap_crab::ui::{centered_rect_fixed, uses_compact_layout};
+use ratatui::layout::Rect;
+
+#[test]
+fn test_centered_rect_fixed_is_centered_and_in_bounds() {
+    let sizes = [(0, 0), (1, 1), (5, 3), (50, 9), (80, 24), (200, 60)];
+
+    for (term_width, term_height) in sizes {
+        let area = Rect::new(0, 0, term_width, term_height);
+        let popup = centered_rect_fixed(50, 9, area);
+
+        assert!(popup.width <= area.width);
+        assert!(popup.height <= area.height);
+        assert!(popup.right() <= area.right());
+        assert!(popup.bottom() <= area.bottom());
+
+        let left_gap = popup.x - area.x;
+        let right_gap = area.right() - popup.right();
+        assert!(left_gap.abs_diff(right_gap) <= 1);
+        let top_gap = popup.y - area.y;
+        let bottom_gap = area.bottom() - popup.bottom();
+        assert!(top_gap.abs_diff(bottom_gap) <= 1);
+    }
+}
+
+#[test]
+fn test_centered_rect_fixed_respects_offset_area() {
+    let area = Rect::new(10, 5, 40, 20);
+    let popup = centered_rect_fixed(20, 10, area);
+
+    assert_eq!(popup, Rect::new(20, 10, 20, 10));
+}
+
+#[test]
+fn test_compact_layout_on_narrow_terminals_or_when_forced() {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    assert!(uses_compact_layout(&app, 80));
+    assert!(!uses_compact_layout(&app, 160));
+
+    app.compact_layout = true;
+    assert!(uses_compact_layout(&app, 160));
+}
@@ -0,0 +1,485 @@
+use chrono::{TimeZone, Utc};
+use gcp_snap_crab::app::App;
+use gcp_snap_crab::gcp::MockGcpClientTrait;
+use gcp_snap_crab::keymap::KeyMap;
+use gcp_snap_crab::state::watch_flow::WatchFlow;
+use gcp_snap_crab::types::{
+    AppState, Backup, CreateBackupConfig, DateRange, DisplayZone, OperationMode, RestoreConfig,
+    SqlInstance,
+};
+use gcp_snap_crab::ui::ui;
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    style::{Color, Modifier},
+    Terminal,
+};
+
+fn instance(name: &str) -> SqlInstance {
+    SqlInstance {
+        name: name.to_string(),
+        database_version: "POSTGRES_15".to_string(),
+        region: "europe-west1".to_string(),
+        tier: "db-custom-2-7680".to_string(),
+        instance_type: "CLOUD_SQL_INSTANCE".to_string(),
+    }
+}
+
+fn backup(id: &str) -> Backup {
+    Backup {
+        id: id.to_string(),
+        start_time: Some(Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()),
+        backup_type: "AUTOMATED".to_string(),
+        status: "SUCCESSFUL".to_string(),
+        description: String::new(),
+    }
+}
+
+/// An `App` in `state`, with the choices a user would have made to get there.
+fn app_in_state(state: AppState) -> App {
+    let mut app = App::new(Box::new(MockGcpClientTrait::new()), false);
+    app.authenticated_user = Some("user@example.com".to_string());
+    app.remembered_projects = vec!["source-project".to_string(), "target-project".to_string()];
+
+    let backup_step = match state {
+        AppState::SelectingProjectForBackup => Some(0),
+        AppState::SelectingInstanceForBackup => Some(1),
+        AppState::EnteringBackupName => Some(2),
+        AppState::ConfirmCreateBackup => Some(3),
+        AppState::PerformingCreateBackup => Some(4),
+        _ => None,
+    };
+    let restore_step = match state {
+        AppState::SelectingSourceProject => 0,
+        AppState::SelectingSourceInstance => 1,
+        AppState::SelectingBackup | AppState::BrowsingProjectBackups => 2,
+        AppState::SelectingTargetProject => 3,
+        AppState::SelectingTargetInstance => 4,
+        AppState::ConfirmRestore => 5,
+        AppState::PerformingRestore => 6,
+        _ => 0,
+    };
+
+    if let Some(step) = backup_step {
+        app.operation_mode = Some(OperationMode::CreateBackup);
+        let flow = &mut app.create_backup_flow;
+        flow.instances
+            .set_items(vec![instance("db-1"), instance("db-2")]);
+        if step >= 1 {
+            flow.project = Some("source-project".to_string());
+        }
+        if step >= 2 {
+            flow.instance = Some("db-1".to_string());
+        }
+        if step >= 3 {
+            flow.config = Some(CreateBackupConfig {
+                project: "source-project".to_string(),
+                instance: "db-1".to_string(),
+                name: "nightly".to_string(),
+                description: "nightly".to_string(),
+                location: None,
+            });
+        }
+        if step >= 4 {
+            flow.operation_id = Some("op-backup".to_string());
+            flow.status = Some("RUNNING".to_string());
+        }
+    } else if state != AppState::SelectingOperation {
+        app.operation_mode = Some(OperationMode::Restore);
+        let flow = &mut app.restore_flow;
+        flow.instances
+            .set_items(vec![instance("db-1"), instance("db-2")]);
+        flow.backups
+            .set_items(vec![backup("1700000000000"), backup("1700000000001")]);
+        flow.project_backups
+            .set_items(vec![("db-1".to_string(), backup("1700000000000"))]);
+        if restore_step >= 1 {
+            flow.source_project = Some("source-project".to_string());
+        }
+        if restore_step >= 2 {
+            flow.source_instance = Some("db-1".to_string());
+        }
+        if restore_step >= 3 {
+            flow.selected_backup = Some("1700000000000".to_string());
+        }
+        if restore_step >= 4 {
+            flow.target_project = Some("target-project".to_string());
+        }
+        if restore_step >= 5 {
+            flow.target_instance = Some("db-2".to_string());
+            flow.config = Some(RestoreConfig {
+                backup_id: "1700000000000".to_string(),
+                source_project: "source-project".to_string(),
+                source_instance: "db-1".to_string(),
+                target_project: "target-project".to_string(),
+                target_instance: "db-2".to_string(),
+                intended_databases: Vec::new(),
+                reason: None,
+            });
+        }
+        if restore_step >= 6 {
+            flow.operation_id = Some("op-restore".to_string());
+            flow.status = Some("RUNNING".to_string());
+        }
+    }
+
+    if state == AppState::WatchingOperation {
+        app.watch_flow = Some(WatchFlow::new("source-project", "op-watched"));
+    }
+
+    app.state = state;
+    app
+}
+
+fn render(app: &mut App, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| ui(f, app)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            (area.left()..area.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const ALL_STATES: [AppState; 17] = [
+    AppState::SelectingOperation,
+    AppState::CheckingPrerequisites,
+    AppState::SelectingSourceProject,
+    AppState::SelectingSourceInstance,
+    AppState::SelectingBackup,
+    AppState::BrowsingProjectBackups,
+    AppState::SelectingTargetProject,
+    AppState::SelectingTargetInstance,
+    AppState::ConfirmRestore,
+    AppState::PerformingRestore,
+    AppState::MonitoringStoredOperations,
+    AppState::WatchingOperation,
+    AppState::SelectingProjectForBackup,
+    AppState::SelectingInstanceForBackup,
+    AppState::EnteringBackupName,
+    AppState::ConfirmCreateBackup,
+    AppState::PerformingCreateBackup,
+];
+
+#[test]
+fn test_every_state_renders_at_common_and_tiny_sizes() {
+    let mut states = ALL_STATES.to_vec();
+    states.push(AppState::Error("gcloud not found".to_string()));
+
+    for state in states {
+        for (width, height) in [(160, 48), (80, 24), (20, 8), (1, 1)] {
+            let mut app = app_in_state(state.clone());
+            let buffer = render(&mut app, width, height);
+            assert_eq!(buffer.area.width, width, "{:?}", state);
+        }
+    }
+}
+
+#[test]
+fn test_states_show_their_key_content() {
+    let expectations = [
+        (AppState::SelectingSourceInstance, "db-2"),
+        (AppState::SelectingBackup, "1700000000001"),
+        (AppState::SelectingTargetInstance, "db-2"),
+        (AppState::ConfirmRestore, "PROCEED WITH RESTORATION"),
+        (AppState::PerformingRestore, "Restore in progress"),
+        (AppState::ConfirmCreateBackup, "CREATE BACKUP"),
+        (AppState::PerformingCreateBackup, "Backup in progress"),
+        (AppState::WatchingOperation, "Watching op-watched in source-project"),
+    ];
+
+    for (state, expected) in expectations {
+        let mut app = app_in_state(state.clone());
+        let text = buffer_text(&render(&mut app, 160, 48));
+        assert!(text.contains(expected), "{:?} should show {:?}:\n{}", state, expected, text);
+    }
+}
+
+#[test]
+fn test_header_and_footer_frame_the_content() {
+    let mut app = app_in_state(AppState::SelectingBackup);
+    let buffer = render(&mut app, 160, 48);
+    let text = buffer_text(&buffer);
+    let lines: Vec<&str> = text.lines().collect();
+
+    let footer = lines[lines.len() - 3..].join("\n");
+    assert!(footer.contains("[Enter] Select"), "{}", footer);
+    assert!(footer.contains("[q] Quit"), "{}", footer);
+
+    let header = lines[..3].join("\n");
+    assert!(header.contains("user@example.com"), "{}", header);
+}
+
+#[test]
+fn test_footer_drops_step_hints_before_essential_ones_when_narrow() {
+    let footer = |app: &mut App, width: u16| {
+        let text = buffer_text(&render(app, width, 40));
+        let lines: Vec<&str> = text.lines().collect();
+        lines[lines.len() - 2].to_string()
+    };
+    let mut app = app_in_state(AppState::SelectingBackup);
+
+    let wide = footer(&mut app, 200);
+    assert!(wide.contains("[f] Filter by Date"), "{}", wide);
+    assert!(wide.contains("[r] Refresh"), "{}", wide);
+
+    let narrow = footer(&mut app, 90);
+    assert!(!narrow.contains("[r] Refresh"), "{}", narrow);
+    assert!(narrow.contains("[Esc] Back"), "{}", narrow);
+    assert!(narrow.contains("[q] Quit"), "{}", narrow);
+
+    app.compact_footer = true;
+    let compact = footer(&mut app, 200);
+    assert!(!compact.contains("[f] Filter by Date"), "{}", compact);
+    assert!(compact.contains("[v] All Keys"), "{}", compact);
+}
+
+#[test]
+fn test_help_scroll_stops_at_the_last_line() {
+    let mut app = app_in_state(AppState::SelectingOperation);
+    app.show_help = true;
+    app.help_scroll = u16::MAX;
+
+    let text = buffer_text(&render(&mut app, 80, 24));
+
+    assert!(app.help_scroll > 0);
+    assert!(app.help_scroll < u16::MAX);
+    assert!(text.contains("to close this help"), "{}", text);
+}
+
+#[test]
+fn test_reduce_motion_removes_blinking_from_restore_warning() {
+    let blinking_cells = |reduce_motion: bool| {
+        let mut app = app_in_state(AppState::ConfirmRestore);
+        app.reduce_motion = reduce_motion;
+        let buffer = render(&mut app, 160, 48);
+        buffer
+            .content()
+            .iter()
+            .filter(|cell| cell.modifier.contains(Modifier::SLOW_BLINK))
+            .count()
+    };
+
+    assert!(blinking_cells(false) > 0);
+    assert_eq!(blinking_cells(true), 0);
+}
+
+#[test]
+fn test_paused_polling_is_shown_in_the_status_pane() {
+    let mut app = app_in_state(AppState::PerformingRestore);
+    app.polling_paused = true;
+
+    let text = buffer_text(&render(&mut app, 160, 48));
+
+    assert!(text.contains("Polling paused"), "{}", text);
+    assert!(text.contains("[p] Resume Polling"), "{}", text);
+
+    app.keymap = KeyMap::from_toml("refresh = \"F5\"\npause_polling = \"P\"").unwrap();
+    let text = buffer_text(&render(&mut app, 160, 48));
+    assert!(text.contains("press P to resume / F5 to refresh once"), "{}", text);
+    assert!(text.contains("[P] Resume Polling"), "{}", text);
+}
+
+#[test]
+fn test_long_error_grows_the_popup_and_scrolls_to_its_end() {
+    let mut app = app_in_state(AppState::SelectingOperation);
+    let body: Vec<String> = (1..=60).map(|i| format!("detail line {}", i)).collect();
+    app.error = Some(format!("Restore failed:\n{}", body.join("\n")));
+    app.error_scroll = u16::MAX;
+
+    let text = buffer_text(&render(&mut app, 80, 24));
+
+    assert!(app.error_scroll > 0 && app.error_scroll < u16::MAX);
+    assert!(text.contains("detail line 60"), "{}", text);
+    assert!(!text.contains("detail line 1 "), "{}", text);
+    assert!(text.contains("to scroll"), "{}", text);
+
+    app.error = Some("Short message. Press ESC to clear.".to_string());
+    app.error_scroll = 0;
+    let text = buffer_text(&render(&mut app, 80, 24));
+    assert!(!text.contains("to scroll"), "{}", text);
+}
+
+#[test]
+fn test_backup_comparison_shows_both_backups() {
+    let mut app = app_in_state(AppState::SelectingBackup);
+    let mut backups = app.restore_flow.backups.items().to_vec();
+    backups[1].status = "FAILED".to_string();
+    app.restore_flow.backups.set_items(backups);
+    app.restore_flow.compare_marks = vec!["1700000000000".to_string(), "1700000000001".to_string()];
+    app.restore_flow.show_comparison = true;
+
+    let text = buffer_text(&render(&mut app, 160, 48));
+
+    assert!(text.contains("Compare Backups"), "{}", text);
+    assert!(text.contains("SUCCESSFUL") && text.contains("FAILED"), "{}", text);
+}
+
+#[test]
+fn test_retention_view_counts_backups_by_age() {
+    let mut app = app_in_state(AppState::SelectingBackup);
+    app.open_retention_view();
+
+    let text = buffer_text(&render(&mut app, 160, 48));
+
+    assert!(text.contains("by Age"), "{}", text);
+    assert!(text.contains("Last 7 days"), "{}", text);
+    assert!(text.contains("Older"), "{}", text);
+}
+
+#[test]
+fn test_confirm_shows_engines_and_warns_about_a_newer_source() {
+    let mut app = app_in_state(AppState::ConfirmRestore);
+    app.restore_flow.source_version = Some("POSTGRES_15".to_string());
+
+    let text = buffer_text(&render(&mut app, 160, 48));
+    assert!(text.contains("POSTGRES_15 → POSTGRES_15"), "{}", text);
+    assert!(!text.contains("NEWER version"), "{}", text);
+
+    let mut instances = app.restore_flow.instances.items().to_vec();
+    instances[1].database_version = "POSTGRES_13".to_string();
+    app.restore_flow.instances.set_items(instances);
+    let text = buffer_text(&render(&mut app, 160, 48));
+    assert!(text.contains("POSTGRES_15 → POSTGRES_13"), "{}", text);
+    assert!(text.contains("NEWER version"), "{}", text);
+}
+
+#[test]
+fn test_huge_backup_list_highlights_the_selected_row() {
+    let mut app = app_in_state(AppState::SelectingBackup);
+    app.restore_flow.backups.set_items(
+        (0..5000)
+            .map(|i| backup(&format!("{}", 1700000000000u64 + i)))
+            .collect(),
+    );
+    app.restore_flow.backups.select(4321);
+
+    let buffer = render(&mut app, 160, 48);
+    let text = buffer_text(&buffer);
+
+    let selected_line = text
+        .lines()
+        .find(|line| line.contains("► "))
+        .expect("a highlighted row");
+    assert!(selected_line.contains("1700000004321"), "{}", selected_line);
+    assert!(!text.contains("1700000000000 "), "{}", text);
+    assert!(text.contains("[f] to filter by date"), "{}", text);
+
+    app.restore_flow.backups.select(0);
+    let text = buffer_text(&render(&mut app, 160, 48));
+    let selected_line = text.lines().find(|line| line.contains("► ")).unwrap();
+    assert!(selected_line.contains("1700000000000"), "{}", selected_line);
+}
+
+#[test]
+fn test_backup_rows_are_colored_by_status() {
+    let mut app = app_in_state(AppState::SelectingBackup);
+    let mut backups = app.restore_flow.backups.items().to_vec();
+    backups[0].status = "FAILED".to_string();
+    backups[1].status = "RUNNING".to_string();
+    app.restore_flow.backups.set_items(backups);
+    app.restore_flow.backups.select(0);
+
+    let buffer = render(&mut app, 160, 48);
+    let text = buffer_text(&buffer);
+    let id_cell = |id: &str| {
+        let (y, line) = text
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.contains(&format!("| {}", id)))
+            .unwrap_or_else(|| panic!("no row for {}", id));
+        let x = line.chars().take_while(|c| *c != '|').count() + 2;
+        buffer[(x as u16, y as u16)].clone()
+    };
+
+    let failed = id_cell("1700000000000");
+    assert_eq!(failed.fg, Color::Red);
+    assert_eq!(failed.bg, Color::Rgb(59, 66, 82), "the selection background is kept");
+    assert!(failed.modifier.contains(Modifier::BOLD));
+    assert_ne!(id_cell("1700000000001").fg, Color::Red);
+}
+
+#[test]
+fn test_confirm_shows_how_long_the_backup_type_is_kept() {
+    let mut app = app_in_state(AppState::ConfirmRestore);
+    let text = buffer_text(&render(&mut app, 160, 48));
+    let automated = "(AUTOMATED, expires under the instance's backup retention)";
+    assert!(text.contains(automated), "{}", text);
+
+    let mut backups = app.restore_flow.backups.items().to_vec();
+    backups[0].backup_type = "ON_DEMAND".to_string();
+    app.restore_flow.backups.set_items(backups);
+    let text = buffer_text(&render(&mut app, 160, 48));
+    assert!(text.contains("(ON_DEMAND, kept until deleted)"), "{}", text);
+}
+
+#[test]
+fn test_confirm_warns_that_a_typed_backup_id_has_unknown_status() {
+    let mut app = app_in_state(AppState::ConfirmRestore);
+    let mut backups = app.restore_flow.backups.items().to_vec();
+    backups[0].backup_type = Backup::MANUAL.to_string();
+    backups[0].status = Backup::MANUAL.to_string();
+    app.restore_flow.backups.set_items(backups);
+
+    let text = buffer_text(&render(&mut app, 160, 48));
+
+    assert!(text.contains("status unknown - make sure this backup completed"), "{}", text);
+}
+
+#[test]
+fn test_restore_and_backup_confirmations_look_different() {
+    let popup_look = |state: AppState| {
+        let mut app = app_in_state(state);
+        let buffer = render(&mut app, 160, 48);
+        let text = buffer_text(&buffer);
+        let (y, line) = text
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.contains("═"))
+            .expect("a double-bordered popup");
+        let x = line.chars().position(|c| c == '═').unwrap();
+        (text, buffer[(x as u16, y as u16)].bg)
+    };
+
+    let (restore_text, restore_bg) = popup_look(AppState::ConfirmRestore);
+    let (backup_text, backup_bg) = popup_look(AppState::ConfirmCreateBackup);
+
+    assert!(restore_text.contains("CRITICAL WARNING - BACKUP RESTORATION"));
+    assert!(restore_text.contains("PERMANENTLY LOST"));
+    assert!(backup_text.contains("Confirm Backup Creation"));
+    assert!(backup_text.contains("Nothing is overwritten or lost."));
+    assert_ne!(restore_bg, backup_bg);
+}
+
+#[test]
+fn test_same_day_backups_show_their_time() {
+    let mut app = app_in_state(AppState::SelectingBackup);
+    let mut backups = app.restore_flow.backups.items().to_vec();
+    backups[1].start_time = Some(Utc.with_ymd_and_hms(2024, 6, 1, 14, 30, 0).unwrap());
+    app.restore_flow.backups.set_items(backups);
+
+    let text = buffer_text(&render(&mut app, 160, 48));
+
+    assert!(text.contains("2024-06-01 12:00 | 1700000000000"), "{}", text);
+    assert!(text.contains("2024-06-01 14:30 | 1700000000001"), "{}", text);
+}
+
+#[test]
+fn test_filter_hiding_every_backup_says_so() {
+    let mut app = app_in_state(AppState::SelectingBackup);
+    app.restore_flow
+        .set_date_filter(DateRange::parse("2023-01-01").unwrap(), DisplayZone::Utc);
+
+    let text = buffer_text(&render(&mut app, 160, 48));
+
+    assert!(text.contains("No backups in 2023-01-01"), "{}", text);
+    assert!(!text.contains("No backups found"), "{}", text);
+}